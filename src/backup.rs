@@ -0,0 +1,81 @@
+use crate::exercise::Exercise;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const BACKUP_DIR: &str = ".rustlings/backups";
+
+// The directory holding all backups of the given exercise
+fn backup_dir(exercise: &Exercise) -> PathBuf {
+    PathBuf::from(BACKUP_DIR).join(&exercise.name)
+}
+
+// Save the current contents of the exercise before they get overwritten.
+// Backups are named after the time they were taken, so they sort chronologically.
+pub fn backup(exercise: &Exercise) -> io::Result<PathBuf> {
+    let dir = backup_dir(exercise);
+    fs::create_dir_all(&dir)?;
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|time| time.as_millis())
+        .unwrap_or_default();
+    // Exercises made of several files are backed up as a directory
+    if exercise.path.is_dir() {
+        let path = dir.join(timestamp.to_string());
+        copy_dir(&exercise.path, &path)?;
+        return Ok(path);
+    }
+    let path = dir.join(format!("{timestamp}.rs"));
+    fs::copy(&exercise.path, &path)?;
+    Ok(path)
+}
+
+// Put the backup in place of the exercise
+pub fn restore(backup: &Path, exercise: &Exercise) -> io::Result<()> {
+    if backup.is_dir() {
+        if exercise.path.exists() {
+            fs::remove_dir_all(&exercise.path)?;
+        }
+        copy_dir(backup, &exercise.path)
+    } else {
+        fs::copy(backup, &exercise.path).map(|_| ())
+    }
+}
+
+// Remove a backup once it's restored
+pub fn remove(backup: &Path) -> io::Result<()> {
+    if backup.is_dir() {
+        fs::remove_dir_all(backup)
+    } else {
+        fs::remove_file(backup)
+    }
+}
+
+fn copy_dir(from: &Path, to: &Path) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
+// The most recent backup of the exercise, if there is any
+pub fn latest_backup(exercise: &Exercise) -> Option<PathBuf> {
+    fs::read_dir(backup_dir(exercise))
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_dir() || path.extension().is_some_and(|ext| ext == "rs"))
+        .max_by_key(|path| {
+            path.file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.parse::<u128>().ok())
+                .unwrap_or_default()
+        })
+}
@@ -0,0 +1,149 @@
+// The build cache shared by everything Rustlings builds with Cargo: the
+// workspace backend, exercises that use crates and linting with Clippy. With
+// one target directory, every crate only builds once, and rebuilding an
+// exercise only pays for the exercise itself. `init --warmup` builds the
+// crates of the course right away, so the first exercise that uses them
+// doesn't wait, e.g. on slow classroom laptops.
+//
+// Cargo's own `CARGO_TARGET_DIR` is honored, e.g. to share the cache between
+// several courses.
+use crate::exercise::Exercise;
+use crate::ui;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const TARGET_DIR: &str = ".rustlings/target";
+// Where the package that builds the crates of the course is generated
+const PRIME_DIR: &str = ".rustlings/prime";
+// An exercise with a test, which builds nothing but the compiler and the
+// test harness need
+const EMPTY_EXERCISE: &str = "fn main() {}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn warm_up() {}
+}
+";
+
+pub fn target_dir() -> PathBuf {
+    env::var_os("CARGO_TARGET_DIR")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(TARGET_DIR))
+}
+
+// Get the first builds of the course out of the way: compile an empty test
+// exercise, so the compiler and the standard library are loaded once, and
+// build the crates the exercises use into the cache, as the dependencies of
+// the empty exercise, both for running and for testing.
+pub fn warm_up(exercises: &[Exercise]) -> Result<(), String> {
+    let cargo_toml = prime_manifest(exercises)?;
+    let dir = Path::new(PRIME_DIR);
+    fs::create_dir_all(dir)
+        .and_then(|_| fs::write(dir.join("main.rs"), EMPTY_EXERCISE))
+        .map_err(|e| format!("Couldn't write {}: {e}", dir.display()))?;
+
+    let spinner = ui::spinner("Compiling an empty exercise...".to_string());
+    let result = (|| {
+        run(Command::new("rustc")
+            .arg("--test")
+            .arg(dir.join("main.rs"))
+            .args(["--edition", "2021", "-o"])
+            .arg(dir.join("main")))?;
+        let Some(cargo_toml) = cargo_toml else {
+            return Ok(());
+        };
+        fs::write(dir.join("Cargo.toml"), cargo_toml)
+            .map_err(|e| format!("Couldn't write {}: {e}", dir.display()))?;
+        for (message, args) in [
+            (
+                "Building the crates the exercises use...",
+                ["build", "--quiet"],
+            ),
+            ("Building them for testing...", ["test", "--no-run"]),
+        ] {
+            spinner.set_message(message);
+            run(Command::new("cargo")
+                .args(args)
+                .arg("--manifest-path")
+                .arg(dir.join("Cargo.toml"))
+                .arg("--target-dir")
+                .arg(target_dir()))?;
+        }
+        Ok(())
+    })();
+    spinner.finish_and_clear();
+    result
+}
+
+// Run the command, returning what it printed to stderr if it failed
+fn run(command: &mut Command) -> Result<(), String> {
+    let output = command
+        .output()
+        .map_err(|e| format!("Failed to run {:?}: {e}", command.get_program()))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+// A package depending on the crates of all exercises, or None if they don't
+// use any
+fn prime_manifest(exercises: &[Exercise]) -> Result<Option<String>, String> {
+    let mut dependencies = toml::Table::new();
+    for exercise in exercises {
+        dependencies.extend(exercise.dependency_table()?);
+    }
+    if dependencies.is_empty() {
+        return Ok(None);
+    }
+    let mut manifest = toml::Table::new();
+    manifest.insert("dependencies".into(), toml::Value::Table(dependencies));
+    let dependencies = toml::to_string(&manifest).map_err(|e| e.to_string())?;
+    Ok(Some(format!(
+        r#"[package]
+name = "prime"
+version = "0.0.1"
+edition = "2021"
+
+[workspace]
+
+[[bin]]
+name = "prime"
+path = "main.rs"
+
+{dependencies}"#
+    )))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::exercise::Mode;
+
+    fn exercise(mode: Mode, dependencies: &[&str]) -> Exercise {
+        Exercise {
+            mode,
+            dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+            ..Exercise::new("exercise", "exercises/exercise.rs")
+        }
+    }
+
+    #[test]
+    fn test_prime_manifest() {
+        assert_eq!(prime_manifest(&[exercise(Mode::Compile, &[])]), Ok(None));
+        let manifest = prime_manifest(&[
+            exercise(Mode::Compile, &[r#"rand = "0.8""#]),
+            exercise(Mode::Async, &[]),
+        ])
+        .unwrap()
+        .unwrap();
+        assert!(manifest.contains("[[bin]]\nname = \"prime\"\npath = \"main.rs\""));
+        assert!(manifest.contains("rand = \"0.8\""));
+        assert!(manifest.contains("[dependencies.tokio]"));
+    }
+}
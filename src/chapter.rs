@@ -0,0 +1,163 @@
+// The chapters of the course, which are the directories the exercises are in,
+// e.g. `exercises/move_semantics`. Exercises that aren't in a directory, like
+// the quizzes, don't belong to a chapter.
+use crate::exercise::Exercise;
+use crate::state::ProgressState;
+use std::fs;
+
+pub struct Chapter<'a> {
+    // The position of the chapter in the course, starting at 1
+    pub number: usize,
+    pub section: String,
+    pub exercises: Vec<&'a Exercise>,
+}
+
+impl Chapter<'_> {
+    // The name of the directory made readable, e.g. "Move Semantics" for
+    // `06_move_semantics`
+    pub fn title(&self) -> String {
+        self.section
+            .trim_start_matches(|c: char| c.is_ascii_digit())
+            .split('_')
+            .filter(|word| !word.is_empty())
+            .map(|word| {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    // Like "Chapter 6: Move Semantics — 3/6 done"
+    pub fn summary(&self, state: &ProgressState) -> String {
+        let done = self
+            .exercises
+            .iter()
+            .filter(|exercise| state.is_done(exercise))
+            .count();
+        tr!(
+            "chapter-summary",
+            number = self.number,
+            title = self.title(),
+            done = done,
+            total = self.exercises.len()
+        )
+    }
+
+    // Like "Chapter 6: Move Semantics"
+    pub fn heading(&self) -> String {
+        tr!(
+            "chapter-heading",
+            number = self.number,
+            title = self.title()
+        )
+    }
+
+    // What the chapter is about, from the README.md in its directory. Its
+    // heading is left out, since it only repeats the title of the chapter.
+    pub fn about(&self) -> Option<String> {
+        let dir = self.exercises.first()?.path.parent()?;
+        let readme = fs::read_to_string(dir.join("README.md")).ok()?;
+        let text = match readme.split_once('\n') {
+            Some((first, rest)) if first.starts_with("# ") => rest,
+            _ => &readme,
+        };
+        Some(text.trim().to_string())
+    }
+}
+
+// The directory of the chapter the exercise belongs to, if any
+pub fn section(exercise: &Exercise) -> Option<String> {
+    exercise.metadata().section
+}
+
+// The chapters in the order they start in the course
+pub fn chapters(exercises: &[Exercise]) -> Vec<Chapter<'_>> {
+    let mut chapters: Vec<Chapter> = Vec::new();
+    for exercise in exercises {
+        let Some(section) = section(exercise) else {
+            continue;
+        };
+        match chapters
+            .iter_mut()
+            .find(|chapter| chapter.section == section)
+        {
+            Some(chapter) => chapter.exercises.push(exercise),
+            None => chapters.push(Chapter {
+                number: chapters.len() + 1,
+                section,
+                exercises: vec![exercise],
+            }),
+        }
+    }
+    chapters
+}
+
+// The chapter the exercise belongs to, if any
+pub fn chapter_of<'a>(exercise: &Exercise, exercises: &'a [Exercise]) -> Option<Chapter<'a>> {
+    let section = section(exercise)?;
+    chapters(exercises)
+        .into_iter()
+        .find(|chapter| chapter.section == section)
+}
+
+// The chapter with the given number, directory or title, ignoring case
+pub fn find_chapter<'a>(query: &str, exercises: &'a [Exercise]) -> Option<Chapter<'a>> {
+    chapters(exercises).into_iter().find(|chapter| {
+        query.parse() == Ok(chapter.number)
+            || chapter.section.eq_ignore_ascii_case(query)
+            || chapter.title().eq_ignore_ascii_case(query)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn exercise(path: &str) -> Exercise {
+        Exercise::new(path, path)
+    }
+
+    #[test]
+    fn test_chapters() {
+        let exercises = [
+            exercise("exercises/intro/intro1.rs"),
+            exercise("exercises/quiz1.rs"),
+            exercise("exercises/06_move_semantics/move_semantics1.rs"),
+            exercise("exercises/intro/intro2.rs"),
+        ];
+        let chapters = chapters(&exercises);
+        assert_eq!(chapters.len(), 2);
+        assert_eq!(chapters[0].exercises.len(), 2);
+        assert_eq!(chapters[1].number, 2);
+        assert_eq!(chapters[1].title(), "Move Semantics");
+        assert!(chapter_of(&exercises[1], &exercises).is_none());
+    }
+
+    #[test]
+    fn test_find_chapter() {
+        let exercises = [
+            exercise("exercises/intro/intro1.rs"),
+            exercise("exercises/06_move_semantics/move_semantics1.rs"),
+        ];
+        let number = |query| find_chapter(query, &exercises).map(|chapter| chapter.number);
+        assert_eq!(number("2"), Some(2));
+        assert_eq!(number("intro"), Some(1));
+        assert_eq!(number("06_move_semantics"), Some(2));
+        assert_eq!(number("move semantics"), Some(2));
+        assert_eq!(number("3"), None);
+    }
+
+    #[test]
+    fn test_about() {
+        let exercises = [exercise("exercises/intro/intro1.rs")];
+        let about = chapters(&exercises)[0].about().unwrap();
+        assert!(about.starts_with("Rust uses the `print!` and `println!` macros"));
+        assert!(about.contains("## Further information"));
+        let exercises = [exercise("tests/fixture/success/compSuccess.rs")];
+        assert_eq!(chapters(&exercises)[0].about(), None);
+    }
+}
@@ -0,0 +1,256 @@
+// Verifying the exercises in continuous integration, where graders like
+// GitHub Classroom or GitLab CI read the results. Nothing is colored and
+// nothing is asked, every exercise is checked, and the results can be
+// written as a JUnit XML or TAP report, with one test case per exercise.
+use crate::chapter;
+use crate::exercise::Exercise;
+use crate::util::escape_html;
+use crate::verify::{check_all, print_outcomes, Outcome, VerifyOptions};
+use console::strip_ansi_codes;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+// The exit codes of `verify --ci`. Invalid arguments exit with 2, like with
+// every other command.
+pub const EXIT_PASSED: i32 = 0;
+pub const EXIT_FAILED: i32 = 1;
+pub const EXIT_ERROR: i32 = 3;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum ReportFormat {
+    Junit,
+    Tap,
+}
+
+impl ReportFormat {
+    fn from_path(path: &Path) -> Option<ReportFormat> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "xml" => Some(ReportFormat::Junit),
+            "tap" => Some(ReportFormat::Tap),
+            _ => None,
+        }
+    }
+}
+
+// Verify all the exercises and write the report, if one was asked for.
+// Returns the code to exit with.
+pub fn verify(exercises: &[Exercise], options: &VerifyOptions, report: Option<&Path>) -> i32 {
+    let format = match report.map(|path| (path, ReportFormat::from_path(path))) {
+        Some((path, None)) => {
+            warn!(
+                "Don't know how to write {}, the report can be written to .xml or .tap files",
+                path.display()
+            );
+            return EXIT_ERROR;
+        }
+        Some((path, Some(format))) => Some((path, format)),
+        None => None,
+    };
+    if crate::repair::ensure_complete(exercises).is_err() {
+        return EXIT_ERROR;
+    }
+
+    // Skipping an exercise or a stale state file mustn't pass the build
+    let outcomes = check_all(exercises, options, true);
+    let passed = print_outcomes(&outcomes).is_ok();
+    if let Some((path, format)) = format {
+        let report = match format {
+            ReportFormat::Junit => junit(&outcomes),
+            ReportFormat::Tap => tap(&outcomes),
+        };
+        if let Err(e) = fs::write(path, report) {
+            warn!("Couldn't write the report to {}", path.display());
+            println!("{e}");
+            return EXIT_ERROR;
+        }
+        println!("Wrote the report to {}", path.display());
+    }
+    if passed {
+        EXIT_PASSED
+    } else {
+        EXIT_FAILED
+    }
+}
+
+// The text without colors and the control characters XML doesn't allow
+fn escape_xml(text: &str) -> String {
+    let text: String = strip_ansi_codes(text)
+        .chars()
+        .filter(|c| !c.is_control() || matches!(c, '\n' | '\r' | '\t'))
+        .collect();
+    escape_html(&text).replace('\'', "&apos;")
+}
+
+// The chapter of the exercise, or the suite for exercises outside of one
+fn classname(exercise: &Exercise) -> String {
+    chapter::section(exercise).unwrap_or_else(|| "rustlings".to_string())
+}
+
+fn seconds(outcome: &Outcome) -> f32 {
+    outcome.compile_time.map_or(0.0, |time| time.as_secs_f32())
+}
+
+fn junit(outcomes: &[Outcome]) -> String {
+    let failures = outcomes.iter().filter(|o| o.error.is_some()).count();
+    let skipped = outcomes.iter().filter(|o| o.skipped).count();
+    let time: f32 = outcomes.iter().map(seconds).sum();
+    let counts = format!(
+        "tests=\"{}\" failures=\"{failures}\" skipped=\"{skipped}\" time=\"{time:.3}\"",
+        outcomes.len()
+    );
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(xml, "<testsuites {counts}>");
+    let _ = writeln!(xml, "  <testsuite name=\"rustlings\" {counts}>");
+    for outcome in outcomes {
+        let exercise = outcome.exercise;
+        let _ = write!(
+            xml,
+            "    <testcase name=\"{}\" classname=\"{}\" file=\"{}\" time=\"{:.3}\"",
+            escape_xml(&exercise.name),
+            escape_xml(&classname(exercise)),
+            escape_xml(&exercise.main_file().display().to_string()),
+            seconds(outcome)
+        );
+        if let Some(error) = &outcome.error {
+            let _ = writeln!(
+                xml,
+                ">\n      <failure message=\"{}\">{}</failure>\n    </testcase>",
+                escape_xml(error),
+                escape_xml(outcome.output.trim_end())
+            );
+        } else if outcome.skipped {
+            xml.push_str(
+                ">\n      <skipped message=\"Skipped without being solved\"/>\n    </testcase>\n",
+            );
+        } else {
+            xml.push_str("/>\n");
+        }
+    }
+    xml.push_str("  </testsuite>\n</testsuites>\n");
+    xml
+}
+
+fn tap(outcomes: &[Outcome]) -> String {
+    let mut tap = format!("TAP version 13\n1..{}\n", outcomes.len());
+    for (i, outcome) in outcomes.iter().enumerate() {
+        let name = &outcome.exercise.name;
+        let number = i + 1;
+        if let Some(error) = &outcome.error {
+            let _ = writeln!(tap, "not ok {number} - {name}");
+            // The details are a YAML block, in which JSON strings are valid
+            let message = serde_json::to_string(error).unwrap_or_default();
+            let _ = writeln!(tap, "  ---\n  message: {message}");
+            tap.push_str("  output: |\n");
+            for line in strip_ansi_codes(outcome.output.trim_end()).lines() {
+                let _ = writeln!(tap, "    {line}");
+            }
+            tap.push_str("  ...\n");
+        } else if outcome.skipped {
+            let _ = writeln!(
+                tap,
+                "ok {number} - {name} # SKIP skipped without being solved"
+            );
+        } else {
+            let _ = writeln!(tap, "ok {number} - {name}");
+        }
+    }
+    tap
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    fn exercise(name: &str) -> Exercise {
+        Exercise::new(name, format!("exercises/intro/{name}.rs"))
+    }
+
+    fn outcomes(exercises: &[Exercise]) -> Vec<Outcome<'_>> {
+        vec![
+            Outcome {
+                exercise: &exercises[0],
+                error: None,
+                output: String::new(),
+                compile_time: Some(Duration::from_millis(1500)),
+                skipped: false,
+            },
+            Outcome {
+                exercise: &exercises[1],
+                error: Some("error[E0308]: expected `i32`, found `&str`".to_string()),
+                output: "\x1b[31merror[E0308]\x1b[0m: expected `i32`, found `&str`\n  --> a.rs\n"
+                    .to_string(),
+                compile_time: Some(Duration::from_millis(500)),
+                skipped: false,
+            },
+            Outcome {
+                exercise: &exercises[2],
+                error: None,
+                output: String::new(),
+                compile_time: None,
+                skipped: true,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_report_format_from_path() {
+        assert_eq!(
+            ReportFormat::from_path(Path::new("junit.xml")),
+            Some(ReportFormat::Junit)
+        );
+        assert_eq!(
+            ReportFormat::from_path(Path::new("out/results.TAP")),
+            Some(ReportFormat::Tap)
+        );
+        assert_eq!(ReportFormat::from_path(Path::new("results.json")), None);
+    }
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(
+            escape_xml("\x1b[1m<a href='x'>&</a>\x1b[0m\x07\n"),
+            "&lt;a href=&apos;x&apos;&gt;&amp;&lt;/a&gt;\n"
+        );
+    }
+
+    #[test]
+    fn test_junit() {
+        let exercises = [exercise("intro1"), exercise("intro2"), exercise("intro3")];
+        let xml = junit(&outcomes(&exercises));
+        assert!(xml.contains(
+            "<testsuite name=\"rustlings\" tests=\"3\" failures=\"1\" skipped=\"1\" time=\"2.000\">"
+        ));
+        assert!(xml.contains(
+            "<testcase name=\"intro1\" classname=\"intro\" file=\"exercises/intro/intro1.rs\" time=\"1.500\"/>"
+        ));
+        assert!(xml.contains(
+            "<failure message=\"error[E0308]: expected `i32`, found `&amp;str`\">error[E0308]: expected `i32`, found `&amp;str`\n  --&gt; a.rs</failure>"
+        ));
+        assert!(xml.contains("<skipped message=\"Skipped without being solved\"/>"));
+        assert!(xml.ends_with("</testsuites>\n"));
+    }
+
+    #[test]
+    fn test_tap() {
+        let exercises = [exercise("intro1"), exercise("intro2"), exercise("intro3")];
+        assert_eq!(
+            tap(&outcomes(&exercises)),
+            "TAP version 13
+1..3
+ok 1 - intro1
+not ok 2 - intro2
+  ---
+  message: \"error[E0308]: expected `i32`, found `&str`\"
+  output: |
+    error[E0308]: expected `i32`, found `&str`
+      --> a.rs
+  ...
+ok 3 - intro3 # SKIP skipped without being solved
+"
+        );
+    }
+}
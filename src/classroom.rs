@@ -0,0 +1,366 @@
+// Tools for teachers, who collect the progress of their students with
+// `rustlings progress export`, or grade their exercises with GitHub
+// Classroom's autograder.
+use crate::chapter::chapters;
+use crate::exercise::Exercise;
+use crate::list::csv_field;
+use crate::progress::read_export;
+use crate::ui;
+use crate::verify::{check_quietly, first_error_line, VerifyOptions};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+// A student's progress, with the number of exercises done in each chapter
+struct StudentProgress {
+    name: String,
+    done: Vec<usize>,
+    // The exercises done in the whole course, including those outside of
+    // the chapters
+    total: usize,
+}
+
+// Print how many exercises of each chapter every student completed. The
+// progress files of the students are read from the given directory, and
+// every student is named after their file.
+pub fn report(exercises: &[Exercise], inputs: &Path, csv: bool) -> Result<(), ()> {
+    let mut files: Vec<_> = fs::read_dir(inputs)
+        .map_err(|e| {
+            warn!("Couldn't read the progress files in {}", inputs.display());
+            println!("{e}");
+        })?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    files.sort();
+
+    let chapters = chapters(exercises);
+    let mut students = Vec::new();
+    for file in &files {
+        let export = match read_export(file) {
+            Ok(export) => export,
+            Err(e) => {
+                warn!("Skipping {}, since it couldn't be read", file.display());
+                println!("{e}");
+                continue;
+            }
+        };
+        let progress = &export.state.exercises;
+        let solved = |exercise: &Exercise| progress.get(&exercise.name).is_some_and(|p| p.solved);
+        students.push(StudentProgress {
+            name: file
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            done: chapters
+                .iter()
+                .map(|chapter| chapter.exercises.iter().filter(|e| solved(e)).count())
+                .collect(),
+            total: exercises.iter().filter(|e| solved(e)).count(),
+        });
+    }
+    if students.is_empty() {
+        warn!("There are no progress files in {}", inputs.display());
+        return Err(());
+    }
+
+    let totals: Vec<(&str, usize)> = chapters
+        .iter()
+        .map(|chapter| (chapter.section.as_str(), chapter.exercises.len()))
+        .collect();
+    if csv {
+        print!("{}", matrix_csv(&totals, exercises.len(), &students));
+    } else {
+        print!("{}", matrix_table(&totals, exercises.len(), &students));
+    }
+    Ok(())
+}
+
+// The matrix as aligned columns, with the done and total exercises in each cell
+fn matrix_table(chapters: &[(&str, usize)], total: usize, students: &[StudentProgress]) -> String {
+    let mut rows = vec![std::iter::once("Student".to_string())
+        .chain(chapters.iter().map(|(name, _)| name.to_string()))
+        .chain(std::iter::once("Total".to_string()))
+        .collect::<Vec<_>>()];
+    for student in students {
+        rows.push(
+            std::iter::once(student.name.clone())
+                .chain(
+                    student
+                        .done
+                        .iter()
+                        .zip(chapters)
+                        .map(|(done, (_, total))| format!("{done}/{total}")),
+                )
+                .chain(std::iter::once(format!("{}/{total}", student.total)))
+                .collect(),
+        );
+    }
+
+    let widths: Vec<usize> = (0..rows[0].len())
+        .map(|column| rows.iter().map(|row| row[column].len()).max().unwrap_or(0))
+        .collect();
+    let mut out = String::new();
+    for row in rows {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect();
+        out.push_str(cells.join("  ").trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+// The matrix as comma separated values for spreadsheets, with the number of
+// exercises done in each chapter. The header has the number of exercises.
+fn matrix_csv(chapters: &[(&str, usize)], total: usize, students: &[StudentProgress]) -> String {
+    let mut out = String::from("student");
+    for (name, total) in chapters {
+        out.push_str(&format!(",{}", csv_field(&format!("{name} ({total})"))));
+    }
+    out.push_str(&format!(",total ({total})\n"));
+    for student in students {
+        out.push_str(&csv_field(&student.name));
+        for done in &student.done {
+            out.push_str(&format!(",{done}"));
+        }
+        out.push_str(&format!(",{}\n", student.total));
+    }
+    out
+}
+
+// The exercises that count towards the grade, from a file like
+//
+//     [points]
+//     intro1 = 1
+//     variables1 = 2
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GradingConfig {
+    points: HashMap<String, u32>,
+}
+
+// The results in the format of GitHub Classroom's autograder
+#[derive(Serialize)]
+struct GradingResult {
+    version: u32,
+    status: &'static str,
+    max_score: u32,
+    tests: Vec<GradedTest>,
+}
+
+#[derive(Serialize)]
+struct GradedTest {
+    name: String,
+    status: &'static str,
+    score: u32,
+    // What the exercise is worth, which is only part of the total
+    #[serde(skip)]
+    points: u32,
+    message: String,
+    test_code: String,
+    filename: String,
+    line_no: u32,
+    execution_time: String,
+}
+
+// Verify the exercises listed in the grading config and print the points
+// the student scored as JSON, or write them to the output file. Nothing
+// recorded in the progress is trusted, so every graded exercise is checked,
+// and failing ones only cost their points.
+pub fn grade(
+    exercises: &[Exercise],
+    config: &Path,
+    output: Option<&Path>,
+    options: &VerifyOptions,
+) -> Result<(), ()> {
+    let graded = fs::read_to_string(config)
+        .map_err(|e| e.to_string())
+        .and_then(|contents| graded_exercises(&contents, exercises))
+        .map_err(|e| {
+            warn!("Couldn't read the grading config {}", config.display());
+            println!("{e}");
+        })?;
+
+    let bar = ui::progress_bar(graded.len() as u64);
+    let mut tests = Vec::new();
+    for (exercise, points) in graded {
+        bar.set_message(exercise.name.clone());
+        let (result, time) = check_quietly(exercise, options);
+        tests.push(graded_test(exercise, points, &result, time));
+        bar.inc(1);
+    }
+    bar.finish_and_clear();
+
+    let result = grading_result(tests);
+    let score: u32 = result.tests.iter().map(|test| test.score).sum();
+    let json = serde_json::to_string_pretty(&result).expect("Failed to serialize the grades");
+    match output {
+        Some(output) => fs::write(output, json + "\n").map_err(|e| {
+            warn!("Couldn't write the grades to {}", output.display());
+            println!("{e}");
+        })?,
+        None => println!("{json}"),
+    }
+    // On stderr, so it doesn't get into the JSON
+    eprintln!("Scored {score} of {} points", result.max_score);
+    if score == result.max_score {
+        Ok(())
+    } else {
+        Err(())
+    }
+}
+
+// The exercises to grade in the order of the course, along with their points
+fn graded_exercises<'a>(
+    config: &str,
+    exercises: &'a [Exercise],
+) -> Result<Vec<(&'a Exercise, u32)>, String> {
+    let config: GradingConfig = toml::from_str(config).map_err(|e| e.message().to_string())?;
+    let mut unknown: Vec<&str> = config
+        .points
+        .keys()
+        .filter(|name| !exercises.iter().any(|e| &e.name == *name))
+        .map(String::as_str)
+        .collect();
+    if !unknown.is_empty() {
+        unknown.sort();
+        return Err(format!(
+            "There are no exercises named {}",
+            unknown.join(", ")
+        ));
+    }
+    let graded: Vec<(&Exercise, u32)> = exercises
+        .iter()
+        .filter_map(|e| Some((e, *config.points.get(&e.name)?)))
+        .collect();
+    if graded.is_empty() {
+        return Err("No exercise is given any points".to_string());
+    }
+    Ok(graded)
+}
+
+fn graded_test(
+    exercise: &Exercise,
+    points: u32,
+    result: &Result<(), String>,
+    time: Duration,
+) -> GradedTest {
+    let (status, score, message) = match result {
+        Ok(()) => ("pass", points, String::new()),
+        Err(output) => ("fail", 0, first_error_line(output).unwrap_or_default()),
+    };
+    GradedTest {
+        name: exercise.name.clone(),
+        status,
+        score,
+        points,
+        message,
+        test_code: format!("rustlings verify --only {}", exercise.name),
+        filename: exercise.main_file().display().to_string(),
+        line_no: 0,
+        execution_time: format!("{:.2}s", time.as_secs_f32()),
+    }
+}
+
+fn grading_result(tests: Vec<GradedTest>) -> GradingResult {
+    let max_score = tests.iter().map(|test| test.points).sum();
+    GradingResult {
+        version: 1,
+        status: if tests.iter().all(|test| test.status == "pass") {
+            "pass"
+        } else {
+            "fail"
+        },
+        max_score,
+        tests,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn exercise(name: &str) -> Exercise {
+        Exercise::new(name, format!("exercises/intro/{name}.rs"))
+    }
+
+    #[test]
+    fn test_graded_exercises() {
+        let exercises = [exercise("intro1"), exercise("intro2"), exercise("intro3")];
+        let names = |config| -> Result<Vec<(String, u32)>, String> {
+            graded_exercises(config, &exercises)
+                .map(|graded| graded.iter().map(|(e, p)| (e.name.clone(), *p)).collect())
+        };
+        assert_eq!(
+            names("[points]\nintro3 = 2\nintro1 = 1\n"),
+            Ok(vec![("intro1".to_string(), 1), ("intro3".to_string(), 2)])
+        );
+        assert_eq!(
+            names("[points]\nintro9 = 1\nintro0 = 1\n"),
+            Err("There are no exercises named intro0, intro9".to_string())
+        );
+        assert!(names("[points]\n").is_err());
+        assert!(names("[point]\nintro1 = 1\n").is_err());
+    }
+
+    #[test]
+    fn test_grading_result() {
+        let (intro1, intro2) = (exercise("intro1"), exercise("intro2"));
+        let time = Duration::from_millis(250);
+        let result = grading_result(vec![
+            graded_test(&intro1, 2, &Ok(()), time),
+            graded_test(&intro2, 3, &Err("error: expected `;`".to_string()), time),
+        ]);
+        assert_eq!(result.status, "fail");
+        assert_eq!(result.max_score, 5);
+        assert_eq!(result.tests[0].score, 2);
+        assert_eq!(result.tests[0].execution_time, "0.25s");
+        assert_eq!(result.tests[1].score, 0);
+        assert_eq!(result.tests[1].message, "error: expected `;`");
+    }
+
+    fn students() -> Vec<StudentProgress> {
+        vec![
+            StudentProgress {
+                name: "alex".to_string(),
+                done: vec![2, 1],
+                total: 4,
+            },
+            StudentProgress {
+                name: "sam".to_string(),
+                done: vec![1, 0],
+                total: 1,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_matrix_table() {
+        assert_eq!(
+            matrix_table(&[("intro", 2), ("variables", 6)], 9, &students()),
+            "Student  intro  variables  Total\nalex     2/2    1/6        4/9\nsam      1/2    0/6        1/9\n"
+        );
+    }
+
+    #[test]
+    fn test_matrix_csv() {
+        assert_eq!(
+            matrix_csv(&[("intro", 2), ("variables", 6)], 9, &students()),
+            "student,intro (2),variables (6),total (9)\nalex,2,1,4\nsam,1,0,1\n"
+        );
+        let student = StudentProgress {
+            name: "Lee, Kim".to_string(),
+            done: vec![1],
+            total: 1,
+        };
+        assert_eq!(
+            matrix_csv(&[("intro, part 1", 2)], 2, &[student]),
+            "student,\"intro, part 1 (2)\",total (2)\n\"Lee, Kim\",1,1\n"
+        );
+    }
+}
@@ -0,0 +1,2539 @@
+use crate::config::{is_ignored, Config, RebuildPolicy};
+use crate::events::OutputFormat;
+use crate::exercise::{Difficulty, Exercise, ExerciseList, Mode, RunInput};
+use crate::init::{init_from_git, write_vscode_workspace, InitOptions};
+use crate::list::{list, ListFormat, ListOptions, ListSort};
+use crate::notes::{add_note, create_notes, notes_path, read_notes};
+use crate::project::RustAnalyzerProject;
+use crate::report::report;
+use crate::run::{
+    reset, reset_from_solution, reset_many, run, runs_on_host, show, show_reset_changes, solution,
+    target_installed, unreset,
+};
+use crate::state::{ProgressState, Status};
+use crate::stats::stats;
+use crate::terminal::KeyInput;
+#[cfg(unix)]
+use crate::tui;
+use crate::ui::confirm;
+use crate::verify::{
+    self, verify, verify_after_change, verify_keep_going, verify_parallel, VerifyOptions,
+};
+use crate::workspace::Backend;
+use crate::{
+    cache, chapter, ci, classroom, debug, dev, doctor, events, hooks, i18n, info, list, lsp,
+    markdown, notification, pager, pick, plan, prerequisites, progress, repair, review, serve,
+    sync, terminal, ui, upgrade, webhook, workspace,
+};
+use clap::{Parser, Subcommand};
+use console::style;
+use notify::DebouncedEvent;
+use notify::{PollWatcher, RecommendedWatcher, RecursiveMode, Watcher};
+use std::any::Any;
+use std::ffi::OsStr;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// Rustlings is a collection of small exercises to get you used to writing and reading Rust code
+#[derive(Parser)]
+#[command(version)]
+struct Args {
+    /// Show outputs from the test exercises
+    #[arg(long)]
+    nocapture: bool,
+    /// Also lint the exercises with Clippy and fail on any warning
+    #[arg(long)]
+    clippy: bool,
+    /// Stop exercises that are still running after this many seconds [default: 30]
+    #[arg(
+        long,
+        value_name = "SECS",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    timeout: Option<u64>,
+    /// Commit each exercise to git once it's solved, with a message like "solve intro2"
+    #[arg(long)]
+    git_autocommit: bool,
+    /// Print plain text without colors, emoji or animations,
+    /// e.g. for screen readers. Also enabled by setting NO_COLOR
+    #[arg(long)]
+    plain: bool,
+    /// Show messages in the given language, e.g. `de`
+    #[arg(long, value_name = "LANG")]
+    lang: Option<String>,
+    /// Use the exercises of the course in the given directory
+    #[arg(long, value_name = "PATH")]
+    course: Option<PathBuf>,
+    /// Build every exercise on its own with rustc, or all of them as binaries
+    /// of one Cargo package, which rebuilds them faster [default: rustc]
+    #[arg(long, value_enum)]
+    backend: Option<Backend>,
+    #[command(subcommand)]
+    command: Option<Subcommands>,
+}
+
+#[derive(Subcommand)]
+enum Subcommands {
+    /// Verify all exercises according to the recommended order
+    Verify {
+        /// Compile and run this many exercises in parallel
+        #[arg(short, long, value_name = "N")]
+        jobs: Option<usize>,
+        /// Also check that the exercises are formatted with rustfmt
+        #[arg(long)]
+        fmt: bool,
+        /// Report the results as human-readable messages or as JSON events, one per line
+        #[arg(long, value_enum, default_value_t, conflicts_with = "jobs")]
+        format: OutputFormat,
+        /// Start verifying at the given exercise, skipping those before it
+        #[arg(long, value_name = "NAME")]
+        from: Option<String>,
+        /// Verify only the exercises matching the filter. Comma separated
+        /// patterns are accepted, like with `list --filter`
+        #[arg(long, value_name = "FILTER")]
+        only: Option<String>,
+        /// Verify all exercises instead of stopping at the first failing one,
+        /// then list the failures along with how long compiling took
+        #[arg(long, conflicts_with_all = ["jobs", "format"])]
+        keep_going: bool,
+        /// Also verify the optional exercises
+        #[arg(long)]
+        include_optional: bool,
+        /// Verify all exercises without colors or questions, for graders in
+        /// continuous integration. Exits with 0 if all exercises passed, 1 if
+        /// any failed and 3 if they couldn't be verified
+        #[arg(long, conflicts_with_all = ["jobs", "format"])]
+        ci: bool,
+        /// Write a report with one test case per exercise, as JUnit XML or
+        /// TAP depending on whether the file ends in .xml or .tap
+        #[arg(long, value_name = "FILE", requires = "ci")]
+        report: Option<PathBuf>,
+        /// List the exercises that weren't checked again, because they passed
+        /// before and haven't changed or because you skipped them, apart from
+        /// those that were
+        #[arg(long, conflicts_with_all = ["keep_going", "ci", "format"])]
+        report_skipped: bool,
+        /// Check every exercise again, even those that passed before and
+        /// haven't changed since
+        #[arg(long)]
+        no_cache: bool,
+    },
+    /// Rerun `verify` when files were edited
+    Watch {
+        /// Show hints on success
+        #[arg(long)]
+        success_hints: bool,
+        /// Automatically show the hint every N failed attempts at an exercise
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+        auto_hint_after: Option<u32>,
+        /// Show a desktop notification whenever an exercise passes or fails
+        #[arg(long)]
+        notify: bool,
+        /// Also check that the exercises are formatted with rustfmt
+        #[arg(long)]
+        fmt: bool,
+        /// Check the files for changes every SECS seconds instead of relying on
+        /// the file system to report them, e.g. on network drives or in WSL 1
+        #[arg(
+            long,
+            value_name = "SECS",
+            num_args = 0..=1,
+            default_missing_value = "1",
+            value_parser = clap::value_parser!(u64).range(1..)
+        )]
+        poll: Option<u64>,
+        /// Only watch the given exercise instead of the whole course
+        #[arg(long, value_name = "NAME")]
+        exercise: Option<String>,
+        /// Also go through the optional exercises
+        #[arg(long, conflicts_with = "exercise")]
+        include_optional: bool,
+        /// Wait this long for further changes before checking the exercises again
+        /// [default: 1000]
+        #[arg(long, value_name = "MS")]
+        debounce_ms: Option<u64>,
+        /// Check the exercises again on changes to any exercise, or only to
+        /// unsolved ones [default: any]
+        #[arg(long, value_enum, value_name = "POLICY")]
+        rebuild: Option<RebuildPolicy>,
+    },
+    /// Run/Test a single exercise
+    Run {
+        /// The names of the exercises, or glob patterns matching exercise names or paths
+        #[arg(required_unless_present_any = ["topic", "next_hard"])]
+        names: Vec<String>,
+        /// Run the first unsolved exercise about the given topic
+        #[arg(long, conflicts_with = "names")]
+        topic: Option<String>,
+        /// Run the first unsolved hard exercise
+        #[arg(long, conflicts_with_all = ["names", "topic"])]
+        next_hard: bool,
+        /// Build the exercise for the given target triple instead of the host.
+        /// Exercises built for another platform are only compiled, not run
+        #[arg(long, value_name = "TRIPLE")]
+        target: Option<String>,
+        /// Print the resolved configuration of the exercise as JSON without building it
+        #[arg(long)]
+        emit_metadata: bool,
+        /// A file to pipe into the standard input of the exercise
+        #[arg(long, value_name = "FILE")]
+        stdin: Option<PathBuf>,
+        /// Show what the exercise prints, even if it passes. Test harnesses
+        /// print the output of the tests as they run
+        #[arg(long)]
+        nocapture: bool,
+        /// Report the results as human-readable messages or as JSON events, one per line
+        #[arg(
+            long,
+            value_enum,
+            default_value_t,
+            conflicts_with_all = ["target", "emit_metadata"]
+        )]
+        format: OutputFormat,
+        /// Arguments passed on to the exercise binary
+        #[arg(last = true)]
+        args: Vec<String>,
+    },
+    /// Reset exercises using "git stash -- <filename>"
+    Reset {
+        /// The names of the exercises, or glob patterns matching exercise names or paths
+        #[arg(required_unless_present = "all")]
+        names: Vec<String>,
+        /// Reset all exercises
+        #[arg(long, conflicts_with = "names")]
+        all: bool,
+        /// Replace the exercise with its solution, but only if it's already done
+        #[arg(long)]
+        from_solution_if_done: bool,
+        /// Reset without showing the changes that are lost and asking first
+        #[arg(long)]
+        force: bool,
+    },
+    /// Restore exercises whose files are missing, leaving all others alone
+    Repair,
+    /// Check your setup for problems, like an outdated Rust or missing exercises
+    Doctor,
+    /// Restore an exercise to how it was before it was last reset
+    Unreset {
+        /// The name of the exercise
+        name: String,
+    },
+    /// Mark an exercise as done without solving it, so it doesn't hold up the course
+    Skip {
+        /// The name of the exercise
+        name: String,
+    },
+    /// Write down notes about an exercise in your editor, or show them
+    Note {
+        /// The name of the exercise
+        name: String,
+        /// Print the notes instead of opening them
+        #[arg(long, conflicts_with = "text")]
+        show: bool,
+        /// Add this text to the notes instead of opening them
+        text: Vec<String>,
+    },
+    /// Return a hint for the given exercise
+    Hint {
+        /// The names of the exercises, or glob patterns matching exercise names or paths
+        #[arg(required_unless_present = "all")]
+        names: Vec<String>,
+        /// Show every hint of all exercises, after asking first
+        #[arg(long, conflicts_with = "names")]
+        all: bool,
+        /// Only show the hints of the exercises in this chapter
+        #[arg(long, requires = "all")]
+        chapter: Option<usize>,
+    },
+    /// Explain what a chapter of the course is about, the chapter of the
+    /// next exercise unless another one is given
+    About {
+        /// The number, directory or title of the chapter, like 6 or move_semantics
+        chapter: Option<String>,
+    },
+    /// Propose the next exercises you can likely do in the given time
+    Plan {
+        /// How many minutes you have
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+        minutes: u32,
+    },
+    /// Pick an exercise by typing parts of its name, then run it
+    Pick {
+        /// Open the picked exercise in your editor instead of running it
+        #[arg(long)]
+        edit: bool,
+    },
+    /// Show everything known about an exercise: its mode, topics, difficulty,
+    /// status, attempts and whether it has hints and a solution
+    Info {
+        /// The name of the exercise
+        name: String,
+    },
+    /// Compile the exercise with debug info and run it in rust-gdb or rust-lldb
+    Debug {
+        /// The name of the exercise
+        name: String,
+    },
+    /// Print an exercise with syntax highlighting and line numbers
+    Show {
+        /// The name of the exercise [default: the current exercise]
+        name: Option<String>,
+        /// Print the original version of the exercise, as committed to git
+        #[arg(long)]
+        original: bool,
+    },
+    /// Show the solution for the given exercise
+    Solution {
+        /// The name of the exercise
+        name: String,
+        /// Show how the solution differs from your version of the exercise
+        #[arg(long)]
+        diff: bool,
+    },
+    /// List the exercises available in Rustlings
+    List {
+        /// Show only the paths of the exercises
+        #[arg(short, long)]
+        paths: bool,
+        /// Show only the names of the exercises
+        #[arg(short, long)]
+        names: bool,
+        /// Provide a string to match exercise names.
+        /// Comma separated patterns are accepted
+        #[arg(short, long)]
+        filter: Option<String>,
+        /// Display only exercises not yet solved
+        #[arg(short, long)]
+        unsolved: bool,
+        /// Display only exercises that have been solved
+        #[arg(short, long)]
+        solved: bool,
+        /// Display only exercises about the given topic
+        #[arg(short, long)]
+        topic: Option<String>,
+        /// Display only exercises of the given difficulty
+        #[arg(long, value_enum)]
+        difficulty: Option<Difficulty>,
+        /// Print the exercises as a table, or as JSON or CSV for other programs
+        #[arg(long, value_enum, default_value_t, conflicts_with_all = ["paths", "names"])]
+        format: ListFormat,
+        /// Display only exercises you wrote notes about, along with the notes
+        #[arg(long, conflicts_with_all = ["paths", "names"])]
+        with_notes: bool,
+        /// Display only the exercises that have to be done before the given
+        /// one, followed by the exercise itself
+        #[arg(long, value_name = "NAME")]
+        prereqs: Option<String>,
+        /// The order to list the exercises in: that of the course, by name or
+        /// path, pending ones first, the most failed attempts first or the most
+        /// recently changed first
+        #[arg(long, value_enum, default_value_t)]
+        sort: ListSort,
+        /// Print only the progress of the course and of every chapter
+        #[arg(long, conflicts_with_all = ["paths", "names", "format", "with_notes"])]
+        summary_only: bool,
+        /// Display only exercises whose name, path, hints or code match the
+        /// given regular expression, along with the lines that match
+        #[arg(long, value_name = "REGEX")]
+        search: Option<String>,
+        /// Estimate how long every exercise that isn't done will take you, from
+        /// how long you took for the solved exercises of its chapter
+        #[arg(long, conflicts_with_all = ["paths", "names", "format", "summary_only"])]
+        estimate_time: bool,
+    },
+    /// Show how many attempts and how much time the exercises took
+    Stats {
+        /// Print the statistics as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Tools for teachers
+    Classroom {
+        #[command(subcommand)]
+        command: ClassroomCommands,
+    },
+    /// Verify the exercises of a grading config and print the points scored
+    /// as JSON for GitHub Classroom's autograder
+    Grade {
+        /// The file with the points of each graded exercise, like
+        /// `[points]` followed by `intro1 = 1`
+        #[arg(long, value_name = "FILE", default_value = "grading.toml")]
+        config: PathBuf,
+        /// Write the results to this file instead of printing them
+        #[arg(long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+    /// Move your progress to another machine
+    Progress {
+        #[command(subcommand)]
+        command: ProgressCommands,
+    },
+    /// Sync your progress with a GitHub gist or a URL, to continue on
+    /// another machine
+    Sync {
+        /// Where to keep the progress: `gist` for a new secret gist, `gist:<ID>`
+        /// for an existing one, or a URL to GET and PUT it at.
+        /// Defaults to `sync_remote` in the config
+        #[arg(long)]
+        remote: Option<String>,
+        /// Overwrite the progress at the remote, even if it changed
+        #[arg(long, conflicts_with = "pull")]
+        push: bool,
+        /// Overwrite your progress with the one at the remote, even if yours changed
+        #[arg(long)]
+        pull: bool,
+        /// Also sync the exercises you worked on
+        #[arg(long)]
+        files: bool,
+    },
+    /// Write a report of your progress to share with others
+    Report {
+        /// The file to write the report to, as Markdown (.md) or HTML (.html)
+        #[arg(long, value_name = "FILE")]
+        output: PathBuf,
+    },
+    /// Show the progress in the browser, updating along with watch mode
+    Serve {
+        /// The port to serve the dashboard on
+        #[arg(long, default_value_t = 8080)]
+        port: u16,
+    },
+    /// Practice a few random solved exercises again, in a temporary copy
+    /// that leaves your solutions alone
+    Review {
+        /// How many exercises to review
+        #[arg(long, default_value_t = 5)]
+        count: usize,
+    },
+    /// Enable rust-analyzer for exercises
+    Lsp {
+        /// Run a language server on stdin and stdout instead, which shows the
+        /// diagnostics of the exercises in the editor and serves their hints
+        #[arg(long)]
+        stdio: bool,
+    },
+    /// Tools for authors of exercises
+    Dev {
+        #[command(subcommand)]
+        command: DevCommands,
+    },
+    /// Replace Rustlings with its latest release
+    Upgrade {
+        /// Only check whether there's a newer release
+        #[arg(long)]
+        check: bool,
+        /// Replace the binary without asking first
+        #[arg(long)]
+        force: bool,
+    },
+    /// Set up a course from a git repository
+    Init {
+        /// The URL of the git repository containing the course
+        #[arg(long, value_name = "GIT_URL")]
+        from: String,
+        /// The directory to set the course up in
+        path: Option<PathBuf>,
+        /// The directory to set the course up in, like the positional argument
+        #[arg(long = "path", value_name = "DIR", conflicts_with = "path")]
+        path_option: Option<PathBuf>,
+        /// Replace a copy of a course that's already in the directory, e.g. a broken one
+        #[arg(long)]
+        force: bool,
+        /// Never ask for input, e.g. for git credentials, for setting up courses from scripts
+        #[arg(long)]
+        non_interactive: bool,
+        /// Also set up VS Code with rust-analyzer and tasks to run the open exercise
+        #[arg(long)]
+        vscode: bool,
+        /// Build the crates the exercises use and the test harness ahead of time,
+        /// so the first exercises build quickly
+        #[arg(long)]
+        warmup: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum DevCommands {
+    /// Check that every exercise has a file, a hint and a compiling solution
+    Check,
+    /// Create a new exercise along with its solution and add it to info.toml
+    New {
+        /// The name of the exercise
+        name: String,
+        /// How the exercise is checked
+        #[arg(long, value_enum, default_value_t = Mode::Compile)]
+        mode: Mode,
+        /// The directory inside `exercises` to put the exercise in.
+        /// Defaults to the name of the exercise without its number
+        #[arg(long, value_name = "DIR")]
+        section: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ClassroomCommands {
+    /// Show how many exercises of each chapter your students completed
+    Report {
+        /// The directory with the files of your students from `rustlings progress export`
+        #[arg(long, value_name = "DIR")]
+        inputs: PathBuf,
+        /// Print comma separated values for spreadsheets instead of a table
+        #[arg(long)]
+        csv: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ProgressCommands {
+    /// Save your progress, solutions and notes to a file
+    Export {
+        /// The file to save the progress to
+        file: PathBuf,
+    },
+    /// Restore the progress from a file saved by `progress export`
+    Import {
+        /// The file to restore the progress from
+        file: PathBuf,
+    },
+}
+
+pub fn main() {
+    let args = Args::parse();
+    let config = Config::load();
+    ui::setup_terminal();
+    if args.plain || ui::no_color_requested() {
+        ui::enable_plain();
+    }
+    if config.color == Some(false) {
+        ui::disable_color();
+    }
+    if config.emoji == Some(false) {
+        ui::disable_emoji();
+    }
+    hooks::enable(config.hooks.clone());
+    pager::configure(config.pager.clone());
+    if let Some(url) = &config.progress_webhook {
+        webhook::enable(url.clone());
+    }
+    if let Some(lang) = args.lang.as_deref().or(config.lang.as_deref()) {
+        if !i18n::set_language(lang) {
+            let languages: Vec<&str> = i18n::languages().collect();
+            println!(
+                "Rustlings hasn't been translated to '{lang}' yet, so it's using English. Available languages: {}",
+                languages.join(", ")
+            );
+        }
+    }
+
+    if args.command.is_none() {
+        println!("\n{WELCOME}\n");
+    }
+
+    if let Some(Subcommands::Upgrade { check, force }) = args.command {
+        upgrade::upgrade(check, force).unwrap_or_else(|_| std::process::exit(1));
+        std::process::exit(0);
+    }
+
+    if let Some(Subcommands::Init {
+        from,
+        path,
+        path_option,
+        force,
+        non_interactive,
+        vscode,
+        warmup,
+    }) = args.command
+    {
+        let options = InitOptions {
+            dir: path.or(path_option),
+            force,
+            non_interactive,
+        };
+        let dir = init_from_git(&from, options).unwrap_or_else(|_| std::process::exit(1));
+        success!("The course was set up in {}", dir.display());
+        if vscode {
+            match write_vscode_workspace(&dir) {
+                Ok(written) if written.is_empty() => {
+                    println!("The course already comes with its VS Code settings.")
+                }
+                Ok(_) => println!(
+                    "Open {} in VS Code and install the recommended extensions.",
+                    dir.display()
+                ),
+                Err(e) => warn!("Couldn't set up VS Code: {}", e),
+            }
+        }
+        if warmup {
+            warm_up(&dir);
+        }
+        println!(
+            "Run `cd {}` and `rustlings watch` to get started!",
+            dir.display()
+        );
+        std::process::exit(0);
+    }
+
+    if let Some(course) = args.course.clone().or_else(|| config.course.clone()) {
+        if let Err(e) = std::env::set_current_dir(&course) {
+            println!("Couldn't open the course at {}: {e}", course.display());
+            std::process::exit(1);
+        }
+    }
+
+    // The doctor checks everything the rest of Rustlings relies on itself
+    if let Some(Subcommands::Doctor) = args.command {
+        doctor::doctor().unwrap_or_else(|_| std::process::exit(1));
+        std::process::exit(0);
+    }
+
+    if !Path::new("info.toml").exists() {
+        println!(
+            "{} must be run from the rustlings directory",
+            std::env::current_exe().unwrap().to_str().unwrap()
+        );
+        println!("Try `cd rustlings/`!");
+        std::process::exit(1);
+    }
+
+    if !rustc_exists() {
+        println!("We cannot find `rustc`.");
+        println!("Try running `rustc --version` to diagnose your problem.");
+        println!("For instructions on how to install Rust, check the README.");
+        std::process::exit(1);
+    }
+
+    let exercises = ExerciseList::load().unwrap_or_else(|e| {
+        println!("{e}");
+        std::process::exit(1);
+    });
+    if args.backend.or(config.backend) == Some(Backend::Workspace) {
+        if let Err(e) = workspace::enable(&exercises) {
+            println!("Couldn't set up the workspace to build the exercises in: {e}");
+            std::process::exit(1);
+        }
+    }
+    let options = VerifyOptions {
+        verbose: args.nocapture,
+        success_hints: config.success_hints.unwrap_or(false),
+        clippy: args.clippy || config.clippy.unwrap_or(false),
+        fmt: config.fmt.unwrap_or(false),
+        timeout: Duration::from_secs(args.timeout.or(config.timeout).unwrap_or(30).max(1)),
+        git_autocommit: args.git_autocommit || config.git_autocommit.unwrap_or(false),
+        no_cache: false,
+    };
+
+    let command = args.command.unwrap_or_else(|| {
+        println!("{}\n", tr!("default-out"));
+        std::process::exit(0);
+    });
+
+    match command {
+        Subcommands::List {
+            paths,
+            names,
+            filter,
+            unsolved,
+            solved,
+            topic,
+            difficulty,
+            format,
+            with_notes,
+            prereqs,
+            sort,
+            summary_only,
+            search,
+            estimate_time,
+        } => {
+            let search = search.map(|search| {
+                regex::Regex::new(&search).unwrap_or_else(|e| {
+                    println!("Invalid search pattern: {e}");
+                    std::process::exit(1);
+                })
+            });
+            let chain: Vec<Exercise> = match &prereqs {
+                Some(name) => {
+                    let exercise = find_exercise(name, &exercises);
+                    prerequisites::prerequisites(exercise, &exercises)
+                        .into_iter()
+                        .chain(std::iter::once(exercise))
+                        .cloned()
+                        .collect()
+                }
+                None => Vec::new(),
+            };
+            list(
+                if prereqs.is_some() {
+                    &chain
+                } else {
+                    &exercises
+                },
+                &ListOptions {
+                    paths,
+                    names,
+                    filter,
+                    unsolved,
+                    solved,
+                    topic,
+                    difficulty,
+                    format,
+                    with_notes,
+                    sort,
+                    summary_only,
+                    search,
+                    estimate_time,
+                },
+            );
+            std::process::exit(0);
+        }
+
+        Subcommands::Run {
+            names,
+            topic,
+            next_hard,
+            target,
+            emit_metadata,
+            stdin,
+            nocapture,
+            format,
+            args,
+        } => {
+            let selected = match &topic {
+                _ if next_hard => vec![find_hard_exercise(&exercises)],
+                Some(topic) => vec![find_topic_exercise(topic, &exercises)],
+                None => find_exercises(&names, &exercises),
+            };
+
+            if emit_metadata {
+                let metadata: Vec<_> = selected.iter().map(|e| e.metadata()).collect();
+                let metadata = match &metadata[..] {
+                    [metadata] => serde_json::to_string_pretty(metadata),
+                    _ => serde_json::to_string_pretty(&metadata),
+                }
+                .expect("Failed to serialize exercise metadata");
+                println!("{metadata}");
+                std::process::exit(0);
+            }
+
+            if let Some(target) = &target {
+                match target_installed(target) {
+                    Some(false) => {
+                        println!("The target `{target}` isn't installed.");
+                        println!("Try running `rustup target add {target}` first.");
+                        std::process::exit(1);
+                    }
+                    None => println!(
+                        "Couldn't check with rustup whether `{target}` is installed, trying anyway."
+                    ),
+                    Some(true) => {}
+                }
+            }
+
+            let input = RunInput {
+                args: &args,
+                stdin: stdin.as_deref(),
+                nocapture,
+            };
+            let options = VerifyOptions {
+                verbose: options.verbose || nocapture,
+                ..options
+            };
+            let mut failed = Vec::new();
+            for exercise in &selected {
+                let result = if format == OutputFormat::Json {
+                    events::run(exercise, &options, &input)
+                } else {
+                    run_and_record(exercise, &options, target.as_deref(), &input)
+                };
+                if result.is_err() {
+                    failed.push(exercise.name.as_str());
+                }
+            }
+            if selected.len() > 1 && format != OutputFormat::Json {
+                println!();
+                if failed.is_empty() {
+                    success!("All {} exercises ran successfully", selected.len());
+                } else {
+                    warn!(
+                        "{} of {} exercises failed: {}",
+                        failed.len(),
+                        selected.len(),
+                        failed.join(", ")
+                    );
+                }
+            }
+            if !failed.is_empty() {
+                std::process::exit(1);
+            }
+        }
+
+        Subcommands::Reset {
+            names,
+            all,
+            from_solution_if_done,
+            force,
+        } => {
+            let selected: Vec<&Exercise> = if all {
+                exercises.iter().collect()
+            } else {
+                // Resetting throws work away, so it doesn't guess from the
+                // start of a name which exercise is meant
+                for name in names.iter().filter(|name| !is_pattern(name)) {
+                    require_full_name(name, &exercises);
+                }
+                find_exercises(&names, &exercises)
+            };
+
+            if from_solution_if_done {
+                let failed = selected
+                    .iter()
+                    .filter(|exercise| reset_from_solution(exercise).is_err())
+                    .count();
+                if failed > 0 {
+                    std::process::exit(1);
+                }
+            } else if let (false, [exercise]) = (all, &selected[..]) {
+                if !force
+                    && show_reset_changes(&selected) > 0
+                    && !confirm(&format!("Reset {exercise} and lose the changes above?"))
+                {
+                    println!("Nothing was reset.");
+                    std::process::exit(1);
+                }
+                reset(exercise).unwrap_or_else(|_| std::process::exit(1));
+            } else {
+                if !force {
+                    let changed = show_reset_changes(&selected);
+                    let question = format!(
+                        "This will reset {} exercises and discard your changes to {changed} of them. Continue?",
+                        selected.len()
+                    );
+                    if !confirm(&question) {
+                        println!("Nothing was reset.");
+                        std::process::exit(1);
+                    }
+                }
+                reset_many(&selected).unwrap_or_else(|_| std::process::exit(1));
+            }
+        }
+
+        Subcommands::Repair => {
+            repair::repair(&exercises).unwrap_or_else(|_| std::process::exit(1));
+        }
+
+        Subcommands::Unreset { name } => {
+            let exercise = find_exercise(&name, &exercises);
+
+            unreset(exercise).unwrap_or_else(|_| std::process::exit(1));
+        }
+
+        Subcommands::Skip { name } => {
+            let exercise = find_exercise(&name, &exercises);
+            let mut state = ProgressState::load();
+            if state.is_done(exercise) {
+                println!("{} is already done!", exercise.name);
+                std::process::exit(1);
+            }
+            state.skip(exercise);
+            if let Err(e) = state.save() {
+                println!("Failed to save your progress: {e}");
+                std::process::exit(1);
+            }
+            success!("Skipped {}", exercise);
+            println!("It won't hold up `verify`, `watch` or `run next` anymore, but you can still solve it later.");
+        }
+
+        Subcommands::Note { name, show, text } => {
+            let exercise = find_exercise(&name, &exercises);
+            if show {
+                let Some(notes) = read_notes(exercise) else {
+                    println!("There are no notes about {} yet.", exercise.name);
+                    std::process::exit(1);
+                };
+                println!("{}", notes.trim_end());
+            } else if !text.is_empty() {
+                add_note(exercise, &text.join(" ")).unwrap_or_else(|e| {
+                    println!("Failed to save the note: {e}");
+                    std::process::exit(1)
+                });
+                success!("Added the note to {}", notes_path(exercise).display());
+            } else {
+                let path = create_notes(exercise).unwrap_or_else(|e| {
+                    println!("Failed to create {}: {e}", notes_path(exercise).display());
+                    std::process::exit(1)
+                });
+                open_in_editor(&path, config.editor().as_deref()).unwrap_or_else(|e| {
+                    println!("{e}");
+                    std::process::exit(1)
+                });
+            }
+        }
+
+        Subcommands::Hint {
+            names,
+            all,
+            chapter,
+        } => {
+            if all {
+                let selected: Vec<&Exercise> = match chapter {
+                    Some(number) => {
+                        let chapters = chapter::chapters(&exercises);
+                        let Some(chapter) = chapters.into_iter().find(|c| c.number == number)
+                        else {
+                            warn!("There is no chapter {}", number);
+                            std::process::exit(1);
+                        };
+                        chapter.exercises
+                    }
+                    None => exercises.iter().collect(),
+                };
+                show_all_hints(&selected);
+            } else {
+                match &find_exercises(&names, &exercises)[..] {
+                    [exercise] => show_next_hint(exercise),
+                    selected => {
+                        let sections: Vec<String> = selected
+                            .iter()
+                            .map(|exercise| {
+                                let hint = next_hint(exercise);
+                                ui::section(&exercise.name, &hint, console::Color::Cyan)
+                            })
+                            .collect();
+                        pager::page(&sections.join("\n"));
+                    }
+                }
+            }
+        }
+
+        Subcommands::About { chapter } => {
+            let chapter = match chapter {
+                Some(query) => chapter::find_chapter(&query, &exercises).unwrap_or_else(|| {
+                    warn!("There is no chapter {}", query);
+                    std::process::exit(1)
+                }),
+                None => {
+                    let state = ProgressState::load();
+                    exercises
+                        .iter()
+                        .find(|e| state.status(e) == Status::Pending)
+                        .and_then(|exercise| chapter::chapter_of(exercise, &exercises))
+                        .unwrap_or_else(|| {
+                            println!("Name the chapter to read about, like `rustlings about 1`.");
+                            std::process::exit(1)
+                        })
+                }
+            };
+            show_about(&chapter).unwrap_or_else(|_| std::process::exit(1));
+        }
+
+        Subcommands::Plan { minutes } => {
+            plan::plan(&exercises, minutes).unwrap_or_else(|_| std::process::exit(1));
+        }
+
+        Subcommands::Pick { edit } => {
+            if KeyInput::enable().is_none() {
+                println!("Picking an exercise needs a terminal, try `rustlings list` instead.");
+                std::process::exit(1);
+            }
+            let state = ProgressState::load();
+            let Some(exercise) = pick::pick(&exercises, &state) else {
+                std::process::exit(1);
+            };
+            if edit {
+                open_in_editor(&exercise.path, config.editor().as_deref()).unwrap_or_else(|e| {
+                    println!("{e}");
+                    std::process::exit(1)
+                });
+            } else {
+                run_and_record(exercise, &options, None, &RunInput::default())
+                    .unwrap_or_else(|_| std::process::exit(1));
+            }
+        }
+
+        Subcommands::Info { name } => {
+            let exercise = find_exercise(&name, &exercises);
+            info::info(exercise, &exercises);
+        }
+
+        Subcommands::Debug { name } => {
+            let exercise = find_exercise(&name, &exercises);
+            debug::debug(exercise).unwrap_or_else(|_| std::process::exit(1));
+        }
+
+        Subcommands::Show { name, original } => {
+            let exercise = find_exercise(name.as_deref().unwrap_or("next"), &exercises);
+            show(exercise, original).unwrap_or_else(|_| std::process::exit(1));
+        }
+
+        Subcommands::Solution { name, diff } => {
+            let exercise = find_exercise(&name, &exercises);
+
+            solution(exercise, diff).unwrap_or_else(|_| std::process::exit(1));
+        }
+
+        Subcommands::Verify {
+            jobs,
+            fmt,
+            format,
+            from,
+            only,
+            keep_going,
+            include_optional,
+            ci,
+            report,
+            report_skipped,
+            no_cache,
+        } => {
+            if ci {
+                ui::enable_plain();
+            }
+            let options = VerifyOptions {
+                fmt: fmt || options.fmt,
+                git_autocommit: options.git_autocommit && !ci,
+                no_cache,
+                ..options
+            };
+            let start = from.map_or(0, |name| {
+                let from = find_exercise(&name, &exercises);
+                exercises
+                    .iter()
+                    .position(|e| e.name == from.name)
+                    .unwrap_or(0)
+            });
+            let selected: Vec<Exercise> = exercises[start..]
+                .iter()
+                .filter(|e| include_optional || !e.optional)
+                .filter(|e| {
+                    only.as_ref()
+                        .is_none_or(|only| list::matches_filter(e, only))
+                })
+                .cloned()
+                .collect();
+            if selected.is_empty() {
+                println!("No exercise matches '{}'!", only.unwrap_or_default());
+                std::process::exit(1);
+            }
+            if format == OutputFormat::Json {
+                events::verify(&selected, &options).unwrap_or_else(|_| std::process::exit(1));
+                std::process::exit(0);
+            }
+            if ci {
+                std::process::exit(ci::verify(&selected, &options, report.as_deref()));
+            }
+            repair::ensure_complete(&selected).unwrap_or_else(|_| std::process::exit(1));
+            if keep_going {
+                verify_keep_going(&selected, &options).unwrap_or_else(|_| std::process::exit(1));
+                std::process::exit(0);
+            }
+            let reasons = report_skipped.then(|| verify::skip_reasons(&selected, &options));
+            let result = match jobs {
+                Some(jobs) if jobs > 1 => {
+                    verify_parallel(&selected, (0, selected.len()), &options, jobs)
+                }
+                _ => verify(&selected, (0, selected.len()), &options),
+            };
+            if let Some(reasons) = reasons {
+                verify::report_skipped(&selected, &reasons, result.err());
+            }
+            result.unwrap_or_else(|_| std::process::exit(1));
+            if course_done(&exercises) {
+                hooks::fire(hooks::Event::AllDone, None);
+            }
+        }
+
+        Subcommands::Stats { json } => stats(&exercises, json),
+
+        Subcommands::Classroom { command } => {
+            let result = match command {
+                ClassroomCommands::Report { inputs, csv } => {
+                    classroom::report(&exercises, &inputs, csv)
+                }
+            };
+            result.unwrap_or_else(|_| std::process::exit(1));
+        }
+
+        Subcommands::Grade { config, output } => {
+            classroom::grade(&exercises, &config, output.as_deref(), &options)
+                .unwrap_or_else(|_| std::process::exit(1));
+        }
+
+        Subcommands::Progress { command } => {
+            let result = match command {
+                ProgressCommands::Export { file } => progress::export(&exercises, &file),
+                ProgressCommands::Import { file } => progress::import(&exercises, &file),
+            };
+            result.unwrap_or_else(|_| std::process::exit(1));
+        }
+
+        Subcommands::Sync {
+            remote,
+            push,
+            pull,
+            files,
+        } => {
+            let Some(remote) = remote.or(config.sync_remote.clone()) else {
+                println!(
+                    "Name where to sync to with `--remote`, like `rustlings sync --remote gist`."
+                );
+                std::process::exit(1);
+            };
+            let options = sync::SyncOptions { push, pull, files };
+            sync::sync(&exercises, &remote, &options).unwrap_or_else(|_| std::process::exit(1));
+        }
+
+        Subcommands::Report { output } => {
+            report(&exercises, &output).unwrap_or_else(|_| std::process::exit(1));
+            success!("Wrote the report to {}", output.display());
+        }
+
+        Subcommands::Serve { port } => {
+            serve::serve(&exercises, &options, port).unwrap_or_else(|e| {
+                println!("Couldn't serve the dashboard on port {port}: {e}");
+                std::process::exit(1);
+            });
+        }
+
+        Subcommands::Review { count } => {
+            let review =
+                review::prepare(&exercises, count).unwrap_or_else(|_| std::process::exit(1));
+            let course_dir = std::env::current_dir().expect("Couldn't read the current directory");
+            std::env::set_current_dir(&review.dir).expect("Couldn't enter the review directory");
+            // The review needs a workspace of its own, with only its copies
+            if workspace::is_enabled() {
+                if let Err(e) = workspace::enable(&review.exercises) {
+                    println!("Couldn't set up the workspace to build the exercises in: {e}");
+                    std::process::exit(1);
+                }
+            }
+            let result = watch(
+                &review.exercises,
+                WatchOptions {
+                    // The temporary copies aren't the learner's solutions
+                    verify: VerifyOptions {
+                        git_autocommit: false,
+                        ..options
+                    },
+                    auto_hint_after: config.auto_hint_after,
+                    notify: config.notify.unwrap_or(false),
+                    debounce: config.watch_debounce(),
+                    rebuild: config.watch_rebuild.unwrap_or_default(),
+                    poll: None,
+                    ignore: config.watch_ignore_patterns(),
+                    editor: config.editor(),
+                },
+            );
+            let _ = std::env::set_current_dir(course_dir);
+            match result {
+                Err(e) => {
+                    println!("Error: Could not watch the review. Error message was {e:?}.");
+                    std::process::exit(1);
+                }
+                Ok(WatchStatus::Finished) => {
+                    success!("Reviewed {} exercises, well done!", review.exercises.len());
+                }
+                Ok(WatchStatus::Unfinished) => {
+                    println!("The review was stopped, your solutions are untouched.");
+                }
+            }
+        }
+
+        Subcommands::Lsp { stdio: true } => {
+            lsp::serve(&exercises, &options).unwrap_or_else(|e| {
+                eprintln!("The language server stopped: {e}");
+                std::process::exit(1);
+            });
+        }
+
+        Subcommands::Lsp { stdio: false } => {
+            let mut project = RustAnalyzerProject::new();
+            project
+                .get_sysroot_src()
+                .expect("Couldn't find toolchain path, do you have `rustc` installed?");
+            project
+                .exercises_to_json()
+                .expect("Couldn't parse rustlings exercises files");
+
+            if project.crates.is_empty() {
+                println!("Failed find any exercises, make sure you're in the `rustlings` folder");
+            } else if project.write_to_disk().is_err() {
+                println!("Failed to write rust-project.json to disk for rust-analyzer");
+            } else {
+                println!("Successfully generated rust-project.json");
+                println!("rust-analyzer will now parse exercises, restart your language server or editor")
+            }
+        }
+
+        Subcommands::Dev { command } => {
+            let result = match command {
+                DevCommands::Check => dev::check(&exercises),
+                DevCommands::New {
+                    name,
+                    mode,
+                    section,
+                } => dev::new(&name, mode, section.as_deref(), &exercises),
+            };
+            result.unwrap_or_else(|_| std::process::exit(1));
+        }
+
+        Subcommands::Init { .. } => unreachable!("init is handled before info.toml is read"),
+        Subcommands::Upgrade { .. } => {
+            unreachable!("upgrade is handled before info.toml is read")
+        }
+        Subcommands::Doctor => unreachable!("doctor is handled before info.toml is read"),
+
+        Subcommands::Watch {
+            success_hints,
+            auto_hint_after,
+            notify,
+            fmt,
+            poll,
+            exercise,
+            include_optional,
+            debounce_ms,
+            rebuild,
+        } => {
+            let single = exercise.map(|name| find_exercise(&name, &exercises));
+            let course: Vec<Exercise> = exercises
+                .iter()
+                .filter(|e| include_optional || !e.optional)
+                .cloned()
+                .collect();
+            let watched = match single {
+                Some(exercise) => std::slice::from_ref(exercise),
+                None => &course,
+            };
+            repair::ensure_complete(watched).unwrap_or_else(|_| std::process::exit(1));
+            let state = ProgressState::load();
+            let done_before: Vec<&str> = watched
+                .iter()
+                .filter(|exercise| state.is_done(exercise))
+                .map(|exercise| exercise.name.as_str())
+                .collect();
+            match watch(
+                watched,
+                WatchOptions {
+                    verify: VerifyOptions {
+                        success_hints: success_hints || options.success_hints,
+                        fmt: fmt || options.fmt,
+                        ..options
+                    },
+                    auto_hint_after: auto_hint_after.or(config.auto_hint_after),
+                    notify: notify || config.notify.unwrap_or(false),
+                    debounce: debounce_ms
+                        .map(Duration::from_millis)
+                        .unwrap_or_else(|| config.watch_debounce()),
+                    rebuild: rebuild.or(config.watch_rebuild).unwrap_or_default(),
+                    poll: poll.map(Duration::from_secs),
+                    ignore: config.watch_ignore_patterns(),
+                    editor: config.editor(),
+                },
+            ) {
+                Err(e) => {
+                    println!(
+                        "Error: Could not watch your progress. Error message was {:?}.",
+                        e
+                    );
+                    if doctor::is_watch_limit(&e) {
+                        println!("Your system doesn't allow watching any more files.");
+                    } else {
+                        println!("Most likely you've run out of disk space or your 'inotify limit' has been reached.");
+                    }
+                    if poll.is_none() {
+                        println!("Try `rustlings watch --poll` to check the files for changes periodically instead.");
+                    }
+                    std::process::exit(1);
+                }
+                Ok(WatchStatus::Finished) => match single {
+                    Some(exercise) => {
+                        println!(
+                            "{} {}",
+                            ui::emoji("🎉", "★"),
+                            tr!("watch-exercise-done", exercise = exercise.name)
+                        );
+                        println!("{}", tr!("watch-continue-course"));
+                    }
+                    None => {
+                        println!(
+                            "{emoji} {} {emoji}",
+                            tr!("watch-all-done"),
+                            emoji = ui::emoji("🎉", "★")
+                        );
+                        println!("\n{FENISH_LINE}\n");
+                        if course_done(&exercises) {
+                            hooks::fire(hooks::Event::AllDone, None);
+                        }
+                        let state = ProgressState::load();
+                        let optional = exercises
+                            .iter()
+                            .filter(|e| e.optional && !include_optional && !state.is_done(e))
+                            .count();
+                        if optional > 0 {
+                            println!("{}", tr!("watch-optional-left", count = optional));
+                        }
+                    }
+                },
+                Ok(WatchStatus::Unfinished) => {
+                    println!("{}", tr!("watch-unfinished"));
+                    show_watch_summary(watched, &done_before);
+                }
+            }
+        }
+    }
+}
+
+// Sum up the session when watch mode is quit: what was solved since it
+// started, and where to pick up next time
+fn show_watch_summary(watched: &[Exercise], done_before: &[&str]) {
+    let state = ProgressState::load();
+    let solved: Vec<&str> = watched
+        .iter()
+        .filter(|exercise| {
+            state.is_done(exercise) && !done_before.contains(&exercise.name.as_str())
+        })
+        .map(|exercise| exercise.name.as_str())
+        .collect();
+    println!();
+    if solved.is_empty() {
+        println!("{}", tr!("watch-summary-none"));
+    } else {
+        println!(
+            "{}",
+            tr!(
+                "watch-summary-solved",
+                count = solved.len(),
+                exercises = solved.join(", ")
+            )
+        );
+    }
+    let current = watched
+        .iter()
+        .find(|exercise| state.status(exercise) == Status::Pending);
+    if let Some(current) = current {
+        println!(
+            "{}",
+            tr!(
+                "watch-summary-current",
+                exercise = current.name,
+                path = current.path.display()
+            )
+        );
+        println!("{}", tr!("watch-summary-next", exercise = current.name));
+    }
+}
+
+// A command of the watch mode shell
+#[derive(Clone, Copy)]
+enum WatchCommand {
+    Hint,
+    About,
+    Edit,
+    List,
+    Rerun,
+    Skip,
+    Clear,
+    Output,
+    Quit,
+    // Restore the exercises whose file was deleted or renamed
+    Restore,
+    Help,
+    // Execute a command line, like `rustc --explain E0381`
+    Execute,
+}
+
+// The key and the word that run each command,
+// along with the id of the message describing what it does
+const WATCH_COMMANDS: &[(WatchCommand, char, &str, &str)] = &[
+    (WatchCommand::Hint, 'h', "hint", "watch-command-hint"),
+    (WatchCommand::About, 'a', "about", "watch-command-about"),
+    (WatchCommand::Edit, 'e', "edit", "watch-command-edit"),
+    (WatchCommand::List, 'l', "list", "watch-command-list"),
+    (WatchCommand::Rerun, 'r', "rerun", "watch-command-rerun"),
+    (WatchCommand::Skip, 's', "skip", "watch-command-skip"),
+    (WatchCommand::Clear, 'c', "clear", "watch-command-clear"),
+    (WatchCommand::Output, 'o', "output", "watch-command-output"),
+    (WatchCommand::Quit, 'q', "quit", "watch-command-quit"),
+    (
+        WatchCommand::Restore,
+        'R',
+        "restore",
+        "watch-command-restore",
+    ),
+    (
+        WatchCommand::Execute,
+        '!',
+        "!<cmd>",
+        "watch-command-execute",
+    ),
+    (WatchCommand::Help, '?', "help", "watch-command-help"),
+];
+
+// The signals the watch mode shell sends to the watch loop
+#[derive(Clone, Default)]
+struct WatchSignals {
+    quit: Arc<AtomicBool>,
+    skip: Arc<AtomicBool>,
+    rerun: Arc<AtomicBool>,
+    // Whether to show the output of exercises that pass
+    show_output: Arc<AtomicBool>,
+    // Whether the exercises are being verified right now
+    verifying: Arc<AtomicBool>,
+}
+
+// Print which keys run which commands
+fn show_key_legend() {
+    println!("{}", style(key_legend().join(" · ")).dim());
+}
+
+fn key_legend() -> Vec<String> {
+    WATCH_COMMANDS
+        .iter()
+        .map(|(command, key, word, _)| match command {
+            WatchCommand::Execute => format!("{key}: {}", tr!("watch-legend-execute")),
+            _ => format!("{key}: {word}"),
+        })
+        .collect()
+}
+
+fn show_watch_help(keys: bool) {
+    println!("{}", tr!("watch-help-title"));
+    for (_, key, word, description) in WATCH_COMMANDS {
+        let description = i18n::message(description);
+        if keys {
+            println!("  {key} - {description}");
+        } else {
+            println!("  {word:<6} - {description}");
+        }
+    }
+    println!();
+    println!("{}", tr!("watch-help-footer"));
+}
+
+// Read the next command, either as a single key press or as a line of input.
+// Returns None once there's no more input to read.
+fn read_watch_command(keys: bool) -> Option<(WatchCommand, String)> {
+    loop {
+        if keys {
+            let key = match terminal::read_key() {
+                Ok(Some(key)) => key,
+                Ok(None) => return None,
+                Err(error) => {
+                    println!("error reading command: {error}");
+                    return None;
+                }
+            };
+            // Ctrl-D quits, like it does in most shells
+            if key == '\x04' {
+                return Some((WatchCommand::Quit, String::new()));
+            }
+            let Some((command, ..)) = WATCH_COMMANDS.iter().find(|(_, k, ..)| *k == key) else {
+                continue;
+            };
+            if let WatchCommand::Execute = command {
+                let cmd = terminal::with_line_input(|| {
+                    print!("!");
+                    let _ = io::Write::flush(&mut io::stdout());
+                    let mut cmd = String::new();
+                    io::stdin().read_line(&mut cmd).map(|_| cmd)
+                });
+                return cmd
+                    .ok()
+                    .map(|cmd| (WatchCommand::Execute, cmd.trim().to_string()));
+            }
+            return Some((*command, String::new()));
+        }
+
+        let mut input = String::new();
+        match io::stdin().read_line(&mut input) {
+            Ok(0) => return None,
+            Ok(_) => {
+                let input = input.trim();
+                if let Some(cmd) = input.strip_prefix('!') {
+                    return Some((WatchCommand::Execute, cmd.to_string()));
+                }
+                match WATCH_COMMANDS.iter().find(|(.., word, _)| *word == input) {
+                    Some((command, ..)) => return Some((*command, String::new())),
+                    None => println!("{}", tr!("watch-unknown-command", input = input)),
+                }
+            }
+            Err(error) => println!("error reading command: {error}"),
+        }
+    }
+}
+
+fn spawn_watch_shell(
+    exercises: Vec<Exercise>,
+    current_exercise: &Arc<Mutex<Option<Exercise>>>,
+    signals: WatchSignals,
+    editor: Option<String>,
+    keys: bool,
+) {
+    let current_exercise = Arc::clone(current_exercise);
+    if keys {
+        println!("{}", tr!("watch-welcome-keys"));
+    } else {
+        println!("{}", tr!("watch-welcome-words"));
+    }
+    thread::spawn(move || {
+        while let Some((command, cmd)) = read_watch_command(keys) {
+            match command {
+                WatchCommand::Hint => {
+                    if signals.verifying.load(Ordering::SeqCst) {
+                        println!("{}", tr!("watch-still-verifying"));
+                    }
+                    // Watch mode keeps printing while a pager would be open,
+                    // so the hint is printed as is
+                    match &*current_exercise.lock().unwrap() {
+                        Some(exercise) => println!("{}", next_hint(exercise)),
+                        None => println!("{}", tr!("watch-no-current-exercise")),
+                    }
+                }
+                WatchCommand::About => {
+                    let chapter = current_exercise
+                        .lock()
+                        .unwrap()
+                        .as_ref()
+                        .and_then(|exercise| chapter::chapter_of(exercise, &exercises));
+                    match chapter {
+                        Some(chapter) => {
+                            let _ = show_about(&chapter);
+                        }
+                        None => println!("{}", tr!("watch-no-chapter")),
+                    }
+                }
+                WatchCommand::Edit => {
+                    if let Some(exercise) = &*current_exercise.lock().unwrap() {
+                        if let Err(e) = open_in_editor(&exercise.path, editor.as_deref()) {
+                            println!("{e}");
+                        }
+                    }
+                }
+                WatchCommand::List => list(&exercises, &ListOptions::default()),
+                WatchCommand::Rerun => signals.rerun.store(true, Ordering::SeqCst),
+                WatchCommand::Skip => signals.skip.store(true, Ordering::SeqCst),
+                WatchCommand::Clear => ui::clear_screen(),
+                WatchCommand::Output => {
+                    let shown = !signals.show_output.fetch_xor(true, Ordering::SeqCst);
+                    if shown {
+                        println!("{}", tr!("watch-output-shown"));
+                    } else {
+                        println!("{}", tr!("watch-output-hidden"));
+                    }
+                    signals.rerun.store(true, Ordering::SeqCst);
+                }
+                WatchCommand::Quit => {
+                    signals.quit.store(true, Ordering::SeqCst);
+                    println!("{}", tr!("watch-bye"));
+                }
+                WatchCommand::Restore => {
+                    if repair::repair(&exercises).is_ok() {
+                        signals.rerun.store(true, Ordering::SeqCst);
+                    }
+                }
+                WatchCommand::Help => show_watch_help(keys),
+                WatchCommand::Execute => {
+                    let parts: Vec<&str> = cmd.split_whitespace().collect();
+                    if parts.is_empty() {
+                        println!("{}", tr!("watch-no-command"));
+                    } else if let Err(e) = terminal::with_line_input(|| {
+                        Command::new(parts[0]).args(&parts[1..]).status()
+                    }) {
+                        println!("failed to execute command `{}`: {}", cmd, e);
+                    }
+                }
+            }
+        }
+    });
+}
+
+// Run the exercise and remember whether it's solved now, like `verify` does.
+// Exercises that were only built for another target weren't run, so whether
+// they pass is still unknown.
+fn run_and_record(
+    exercise: &Exercise,
+    options: &VerifyOptions,
+    target: Option<&str>,
+    input: &RunInput,
+) -> Result<(), ()> {
+    let result = run(exercise, options, target, input);
+    if !runs_on_host(target) {
+        return result;
+    }
+    let mut state = ProgressState::load();
+    verify::record(
+        &mut state,
+        exercise,
+        result.is_ok() && exercise.looks_done(),
+        options,
+    );
+    let _ = state.save();
+    result
+}
+
+// Open the file with the editor command, waiting for it to finish
+// in case it's an editor running in the terminal
+fn open_in_editor(path: &Path, editor: Option<&str>) -> Result<(), String> {
+    let Some(editor) = editor else {
+        return Err(format!(
+            "No editor to open {} with.\nSet the EDITOR environment variable or `editor` in rustlings.toml, e.g. `editor = \"code --wait\"`.",
+            path.display()
+        ));
+    };
+    let mut parts = editor.split_whitespace();
+    let Some(program) = parts.next() else {
+        return Err(format!("No editor to open {} with.", path.display()));
+    };
+    terminal::with_line_input(|| Command::new(program).args(parts).arg(path).status())
+        .map(|_| ())
+        .map_err(|e| format!("failed to execute editor `{editor}`: {e}"))
+}
+
+fn find_exercise<'a>(name: &str, exercises: &'a [Exercise]) -> &'a Exercise {
+    if name.eq("next") {
+        let state = ProgressState::load();
+        // Optional exercises aren't on the way through the course
+        exercises
+            .iter()
+            .find(|e| !e.optional && state.status(e) == Status::Pending)
+            .unwrap_or_else(|| {
+                println!(
+                    "{}Congratulations! You have done all the exercises!",
+                    ui::emoji("🎉 ", "")
+                );
+                println!(
+                    "{}There are no more exercises to do next!",
+                    ui::emoji("🔚 ", "")
+                );
+                std::process::exit(1)
+            })
+    } else if let Some(exercise) = exercises.iter().find(|e| e.name == name) {
+        exercise
+    } else {
+        // A name that only one exercise starts with is as good as its full name
+        let mut starting = exercises.iter().filter(|e| e.name.starts_with(name));
+        if let (Some(exercise), None) = (starting.next(), starting.next()) {
+            // On stderr, so it doesn't get into output meant for other programs
+            eprintln!(
+                "Taking {}, the only exercise starting with '{name}'.",
+                exercise.name
+            );
+            return exercise;
+        }
+        println!("No exercise found for '{name}'!");
+        let similar = pick::similar_names(name, exercises);
+        if !similar.is_empty() {
+            println!("Did you mean {}?", similar.join(", "));
+        }
+        std::process::exit(1)
+    }
+}
+
+// Exit unless an exercise has exactly the given name
+fn require_full_name(name: &str, exercises: &[Exercise]) {
+    if name == "next" || exercises.iter().any(|e| e.name == name) {
+        return;
+    }
+    println!("No exercise is called '{name}'!");
+    let starting: Vec<&str> = exercises
+        .iter()
+        .filter(|e| e.name.starts_with(name))
+        .map(|e| e.name.as_str())
+        .collect();
+    let similar = if starting.is_empty() {
+        pick::similar_names(name, exercises)
+    } else {
+        starting
+    };
+    if !similar.is_empty() {
+        println!("Did you mean {}?", similar.join(", "));
+    }
+    std::process::exit(1)
+}
+
+// Whether an exercise name given by the user is a glob pattern
+fn is_pattern(name: &str) -> bool {
+    name.contains(['*', '?', '['])
+}
+
+// Find the exercises for the names the user gave, which may also be glob
+// patterns, without taking any exercise twice
+fn find_exercises<'a>(names: &[String], exercises: &'a [Exercise]) -> Vec<&'a Exercise> {
+    let mut found: Vec<&Exercise> = Vec::new();
+    for name in names {
+        let matching = if is_pattern(name) {
+            find_matching_exercises(name, exercises)
+        } else {
+            vec![find_exercise(name, exercises)]
+        };
+        for exercise in matching {
+            if !found.iter().any(|e| e.name == exercise.name) {
+                found.push(exercise);
+            }
+        }
+    }
+    found
+}
+
+// Find all exercises whose name or path matches the given glob pattern
+fn find_matching_exercises<'a>(pattern: &str, exercises: &'a [Exercise]) -> Vec<&'a Exercise> {
+    let pattern = glob::Pattern::new(pattern).unwrap_or_else(|e| {
+        println!("Invalid pattern '{pattern}': {e}");
+        std::process::exit(1)
+    });
+    let matching: Vec<&Exercise> = exercises
+        .iter()
+        .filter(|e| pattern.matches(&e.name) || pattern.matches_path(&e.path))
+        .collect();
+    if matching.is_empty() {
+        println!("No exercise matches '{pattern}'!");
+        std::process::exit(1);
+    }
+    matching
+}
+
+// Find the first unsolved exercise about the given topic
+fn find_topic_exercise<'a>(topic: &str, exercises: &'a [Exercise]) -> &'a Exercise {
+    let state = ProgressState::load();
+    let mut about_topic = exercises.iter().filter(|e| e.has_topic(topic)).peekable();
+    if about_topic.peek().is_none() {
+        println!("No exercise found for the topic '{topic}'!");
+        std::process::exit(1);
+    }
+    about_topic
+        .find(|e| state.status(e) == Status::Pending)
+        .unwrap_or_else(|| {
+            println!(
+                "{}You have done all the exercises about '{topic}'!",
+                ui::emoji("🎉 ", "")
+            );
+            std::process::exit(1)
+        })
+}
+
+// Find the first hard exercise that isn't done yet
+fn find_hard_exercise(exercises: &[Exercise]) -> &Exercise {
+    let state = ProgressState::load();
+    let mut hard = exercises
+        .iter()
+        .filter(|e| e.difficulty == Some(Difficulty::Hard))
+        .peekable();
+    if hard.peek().is_none() {
+        println!("None of the exercises are marked as hard!");
+        std::process::exit(1);
+    }
+    hard.find(|e| state.status(e) == Status::Pending)
+        .unwrap_or_else(|| {
+            println!(
+                "{}You have done all the hard exercises!",
+                ui::emoji("🎉 ", "")
+            );
+            std::process::exit(1)
+        })
+}
+
+enum WatchStatus {
+    Finished,
+    Unfinished,
+}
+
+// Whether watch mode should show the next hint on its own now that the
+// exercise failed, which it does every `threshold` attempts at it. Attempts
+// are counted in the state, and only when the exercise changed since it was
+// last checked, so checking it again or saving another file doesn't count.
+fn is_hint_due(
+    before: &ProgressState,
+    after: &ProgressState,
+    exercise: &Exercise,
+    threshold: Option<u32>,
+) -> bool {
+    let attempts = |state: &ProgressState| {
+        state
+            .exercises
+            .get(&exercise.name)
+            .map_or(0, |progress| progress.attempts)
+    };
+    let attempts_now = attempts(after);
+    match threshold {
+        Some(n) if n > 0 => attempts_now > attempts(before) && attempts_now % n == 0,
+        _ => false,
+    }
+}
+
+// Point the user to the file they should be editing
+fn show_current_exercise(exercise: &Exercise, exercises: &[Exercise]) {
+    println!();
+    if let Some(chapter) = chapter::chapter_of(exercise, exercises) {
+        println!("{}", style(chapter.summary(&ProgressState::load())).bold());
+    }
+    println!(
+        "{} {}",
+        style(tr!("watch-editing")).bold(),
+        style(exercise.path.display()).bold().cyan()
+    );
+}
+
+// Print what the chapter is about, from the README.md in its directory
+fn show_about(chapter: &chapter::Chapter) -> Result<(), ()> {
+    let Some(about) = chapter.about() else {
+        warn!("{} has no README.md to read", chapter.heading());
+        return Err(());
+    };
+    ui::print_section(
+        &chapter.heading(),
+        &markdown::render(&about),
+        console::Color::Cyan,
+    );
+    Ok(())
+}
+
+// Explain a chapter when its first exercise comes up before the learner
+// worked on it
+fn introduce_chapter(exercise: &Exercise, exercises: &[Exercise]) {
+    let Some(chapter) = chapter::chapter_of(exercise, exercises) else {
+        return;
+    };
+    let attempts = ProgressState::load()
+        .exercises
+        .get(&exercise.name)
+        .map_or(0, |progress| progress.attempts);
+    if chapter.exercises[0].name == exercise.name && attempts <= 1 {
+        println!();
+        let _ = show_about(&chapter);
+    }
+}
+
+fn show_auto_hint(exercise: &Exercise) {
+    println!();
+    println!(
+        "{} {}",
+        ui::emoji("💡", "*"),
+        tr!("watch-stuck", exercise = exercise.name)
+    );
+    println!("{}", next_hint(exercise));
+}
+
+// Print the next hint level of the exercise and remember that it was revealed
+fn show_next_hint(exercise: &Exercise) {
+    pager::page(&format!("{}\n", next_hint(exercise)));
+}
+
+// Reveal the next hint level of the exercise, returning it as it's shown
+fn next_hint(exercise: &Exercise) -> String {
+    let hints = exercise.hints();
+    let mut state = ProgressState::load();
+    let Some(level) = state.next_hint(exercise) else {
+        return tr!("hint-missing", exercise = exercise.name);
+    };
+    let _ = state.save();
+
+    let hint = markdown::render(hints[level]);
+    if hints.len() > 1 {
+        let level = tr!("hint-level", level = level + 1, count = hints.len());
+        format!("{level}\n{hint}")
+    } else {
+        hint
+    }
+}
+
+// Print every hint level of the exercises, one section per exercise. Since
+// that spoils them, it's only done after asking.
+fn show_all_hints(exercises: &[&Exercise]) {
+    let with_hints: Vec<&Exercise> = exercises
+        .iter()
+        .copied()
+        .filter(|exercise| !exercise.hints().is_empty())
+        .collect();
+    if with_hints.is_empty() {
+        println!("None of these exercises has a hint.");
+        return;
+    }
+    let question = format!(
+        "Show all hints of {} exercises? This may spoil the ones you haven't solved yet.",
+        with_hints.len()
+    );
+    if !confirm(&question) {
+        println!("No hints were shown.");
+        std::process::exit(1);
+    }
+
+    let mut state = ProgressState::load();
+    let mut sections = Vec::new();
+    for exercise in &with_hints {
+        let hints = exercise.hints();
+        let text = hints
+            .iter()
+            .enumerate()
+            .map(|(level, hint)| {
+                let hint = markdown::render(hint);
+                if hints.len() > 1 {
+                    let level = tr!("hint-level", level = level + 1, count = hints.len());
+                    format!("{}\n{hint}", style(level).bold())
+                } else {
+                    hint
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        sections.push(ui::section(&exercise.name, &text, console::Color::Cyan));
+        state.reveal_all_hints(exercise);
+    }
+    let _ = state.save();
+    pager::page(&sections.join("\n"));
+}
+
+// How watch mode should behave, as requested on the command line
+struct WatchOptions {
+    verify: VerifyOptions,
+    auto_hint_after: Option<u32>,
+    notify: bool,
+    // How long to wait for further changes before checking the exercises
+    debounce: Duration,
+    // Which changes make it check the exercises again
+    rebuild: RebuildPolicy,
+    // Poll for changes at this interval instead of waiting for file system events
+    poll: Option<Duration>,
+    // Files whose changes don't trigger a new verification
+    ignore: Vec<glob::Pattern>,
+    // The command that the `edit` command opens the exercise with
+    editor: Option<String>,
+}
+
+// Warn that the file of an exercise was deleted or renamed, before checking
+// the exercises fails with a confusing error about the missing file. Editors
+// that save by replacing the file only remove it for a moment, so it's only
+// a warning if the file is still gone.
+fn warn_removed(exercises: &[Exercise], file: &Path, renamed_to: Option<&Path>, keys: bool) {
+    let Some(exercise) = exercises
+        .iter()
+        .find(|e| e.contains_file(file) && repair::is_missing(e))
+    else {
+        return;
+    };
+    let warning = match renamed_to {
+        Some(to) => tr!(
+            "watch-exercise-renamed",
+            exercise = exercise.name,
+            path = to.display()
+        ),
+        None => tr!(
+            "watch-exercise-deleted",
+            exercise = exercise.name,
+            path = exercise
+                .source_files()
+                .into_iter()
+                .find(|file| !file.exists())
+                .unwrap_or_else(|| exercise.path.clone())
+                .display()
+        ),
+    };
+    warn!("{}", warning);
+    if keys {
+        println!("{}", tr!("watch-restore-offer-keys"));
+    } else {
+        println!("{}", tr!("watch-restore-offer-words"));
+    }
+}
+
+// Whether a change to the file makes watch mode check the exercises again.
+// The change is what removes the `I AM NOT DONE` marker, so whether the
+// exercise is unsolved goes by the last check, not by how it looks now.
+fn triggers_rebuild(exercises: &[Exercise], file: &Path, rebuild: RebuildPolicy) -> bool {
+    let mut changed = exercises.iter().filter(|e| e.contains_file(file));
+    match rebuild {
+        RebuildPolicy::Any => changed.next().is_some(),
+        RebuildPolicy::Unsolved => {
+            let state = ProgressState::load();
+            changed.any(|e| !state.was_solved(e))
+        }
+    }
+}
+
+// Tell the user via a desktop notification how the latest verification went
+fn notify_result(previous: &Exercise, result: Result<(), &Exercise>, skipped: bool) {
+    let failed = match result {
+        Ok(()) => {
+            notification::notify("Rustlings", "All exercises completed!");
+            return;
+        }
+        Err(failed) => failed,
+    };
+    if failed.name != previous.name && !skipped {
+        notification::notify("Rustlings", &format!("{} passed!", previous.name));
+    }
+    let title = format!("Rustlings: {}", failed.name);
+    match ProgressState::load().last_error(failed) {
+        Some(error) => notification::notify(&title, error),
+        // Without an error, it passes and only the marker is left to remove
+        None if !failed.looks_done() => notification::notify(
+            &title,
+            "It passes! Remove the `I AM NOT DONE` comment to move on.",
+        ),
+        None => {}
+    }
+}
+
+fn watch(exercises: &[Exercise], options: WatchOptions) -> notify::Result<WatchStatus> {
+    let (tx, rx) = channel();
+
+    fn watch_exercises<W: Watcher>(
+        tx: Sender<DebouncedEvent>,
+        delay: Duration,
+    ) -> notify::Result<W> {
+        let mut watcher = W::new(tx, delay)?;
+        watcher.watch(Path::new("./exercises"), RecursiveMode::Recursive)?;
+        Ok(watcher)
+    }
+    // The watcher stops once it's dropped, so it has to be kept around
+    let _watcher: Box<dyn Any> = match options.poll {
+        Some(interval) => Box::new(watch_exercises::<PollWatcher>(tx, interval)?),
+        None => Box::new(watch_exercises::<RecommendedWatcher>(tx, options.debounce)?),
+    };
+    hooks::fire(hooks::Event::WatchStarted, None);
+
+    // Commands are single key presses while this is alive, if the input is a terminal
+    let key_input = KeyInput::enable();
+    #[cfg(unix)]
+    if key_input.is_some() && console::Term::stdout().is_term() && !ui::is_plain() {
+        return Ok(watch_full_screen(exercises, &rx, &options));
+    }
+
+    let WatchOptions {
+        verify: options,
+        auto_hint_after,
+        notify,
+        rebuild,
+        ignore,
+        editor,
+        ..
+    } = options;
+    let signals = WatchSignals::default();
+    ui::redraw_screen();
+
+    // The shell takes commands right away, even before the exercises are
+    // verified. Until then, the exercise the learner is on is the first one
+    // they haven't done yet.
+    let state = ProgressState::load();
+    let shell_exercise = Arc::new(Mutex::new(
+        exercises
+            .iter()
+            .find(|e| state.status(e) == Status::Pending)
+            .cloned(),
+    ));
+    spawn_watch_shell(
+        exercises.to_vec(),
+        &shell_exercise,
+        signals.clone(),
+        editor,
+        key_input.is_some(),
+    );
+
+    signals.verifying.store(true, Ordering::SeqCst);
+    let result = verify(exercises.iter(), (0, exercises.len()), &options);
+    signals.verifying.store(false, Ordering::SeqCst);
+    let mut current_exercise = match result {
+        Ok(_) => return Ok(WatchStatus::Finished),
+        Err(exercise) => exercise,
+    };
+    *shell_exercise.lock().unwrap() = Some(current_exercise.clone());
+    introduce_chapter(current_exercise, exercises);
+    show_current_exercise(current_exercise, exercises);
+    if key_input.is_some() {
+        show_key_legend();
+    }
+    loop {
+        let mut changed_file = None;
+        match rx.recv_timeout(Duration::from_secs(1)) {
+            Ok(event) => match event {
+                DebouncedEvent::Create(b) | DebouncedEvent::Chmod(b) | DebouncedEvent::Write(b)
+                    if b.extension() == Some(OsStr::new("rs"))
+                        && b.exists()
+                        && !is_ignored(&ignore, &b)
+                        && triggers_rebuild(exercises, &b, rebuild) =>
+                {
+                    changed_file = Some(b.as_path().canonicalize().unwrap());
+                }
+                DebouncedEvent::Remove(b) => {
+                    warn_removed(exercises, &b, None, key_input.is_some());
+                }
+                DebouncedEvent::Rename(b, to) => {
+                    warn_removed(exercises, &b, Some(&to), key_input.is_some());
+                }
+                _ => {}
+            },
+            Err(RecvTimeoutError::Timeout) => {
+                // the timeout expired, just check the signals below then loop again
+            }
+            Err(e) => println!("watch error: {e:?}"),
+        }
+        let skipped = signals.skip.swap(false, Ordering::SeqCst);
+        let rerun = signals.rerun.swap(false, Ordering::SeqCst);
+        if skipped {
+            let mut state = ProgressState::load();
+            state.skip(current_exercise);
+            let _ = state.save();
+        }
+        // Checking exercises that are missing their file would only fail
+        if (changed_file.is_some() || skipped || rerun)
+            && repair::ensure_complete(exercises).is_ok()
+        {
+            let is_changed =
+                |e: &Exercise| changed_file.as_ref().is_some_and(|f| e.contains_file(f));
+            let state = ProgressState::load();
+            let changed = exercises.iter().find(|e| is_changed(e));
+            let pending_exercises = changed.into_iter().chain(
+                exercises
+                    .iter()
+                    .filter(|e| state.status(e) == Status::Pending && !is_changed(e)),
+            );
+            let num_done = exercises
+                .iter()
+                .filter(|e| state.status(e) != Status::Pending)
+                .count();
+            ui::redraw_screen();
+            if skipped {
+                println!("{}", tr!("watch-skipped", exercise = current_exercise));
+            }
+            let options = VerifyOptions {
+                verbose: options.verbose || signals.show_output.load(Ordering::SeqCst),
+                ..options
+            };
+            signals.verifying.store(true, Ordering::SeqCst);
+            let before = ProgressState::load();
+            let progress = (num_done, exercises.len());
+            let result = verify_after_change(pending_exercises, progress, &options, changed);
+            signals.verifying.store(false, Ordering::SeqCst);
+            if notify {
+                notify_result(current_exercise, result, skipped);
+            }
+            match result {
+                Ok(_) => return Ok(WatchStatus::Finished),
+                Err(exercise) => {
+                    if exercise.name != current_exercise.name {
+                        introduce_chapter(exercise, exercises);
+                    }
+                    current_exercise = exercise;
+                    show_current_exercise(exercise, exercises);
+                    if key_input.is_some() {
+                        show_key_legend();
+                    }
+                    if is_hint_due(&before, &ProgressState::load(), exercise, auto_hint_after) {
+                        show_auto_hint(exercise);
+                    }
+                    *shell_exercise.lock().unwrap() = Some(exercise.clone());
+                }
+            }
+        }
+        // Check if we need to exit
+        if signals.quit.load(Ordering::SeqCst) {
+            return Ok(WatchStatus::Unfinished);
+        }
+    }
+}
+
+// Watch mode on the whole screen: the progress and the exercise to edit at the
+// top, the output of the latest check in a pane that can be scrolled, and the
+// keys at the bottom. The commands are the same as on a line by line terminal,
+// and what they print is shown in the pane.
+#[cfg(unix)]
+fn watch_full_screen(
+    exercises: &[Exercise],
+    events: &Receiver<DebouncedEvent>,
+    options: &WatchOptions,
+) -> WatchStatus {
+    // Output is only in color when it goes to a terminal, which it doesn't
+    // while it's captured for the pane
+    console::set_colors_enabled(console::colors_enabled());
+    // The keys are read on their own thread, which waits for each key to be
+    // handled before reading the next one. That way it doesn't take the keys
+    // meant for an editor opened in the terminal.
+    let (key_tx, keys) = channel();
+    let (handled, wait_for_handling) = channel::<()>();
+    thread::spawn(move || {
+        while let Ok(Some(key)) = tui::read_key() {
+            if key_tx.send(key).is_err() || wait_for_handling.recv().is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut screen = tui::Screen::enter();
+    let legend = format!(
+        "{} · ↑↓: {}",
+        key_legend().join(" · "),
+        tr!("watch-legend-scroll")
+    );
+    let mut view = tui::View::default();
+    let mut current: Option<&Exercise> = None;
+    let mut show_output = false;
+    // Shown above the output of the next check
+    let mut notice = format!("{}\n", tr!("watch-welcome-keys"));
+    // What to check next: the file that changed, if any, and whether the
+    // current exercise was skipped
+    let mut check = Some((None, false));
+    // The command line typed after `!`, until Enter is pressed
+    let mut command_line: Option<String> = None;
+    loop {
+        if let Some((changed_file, skipped)) = check.take() {
+            view.header = full_screen_header(exercises, current, true);
+            screen.draw(&view);
+            // Checking exercises that are missing their file would only fail
+            let (complete, missing) = tui::capture(|| repair::ensure_complete(exercises));
+            if complete.is_err() {
+                view.show(missing);
+            } else {
+                let verify_options = VerifyOptions {
+                    verbose: options.verify.verbose || show_output,
+                    ..options.verify
+                };
+                let before = ProgressState::load();
+                let (result, output) = tui::capture(|| match current {
+                    None => verify(exercises.iter(), (0, exercises.len()), &verify_options),
+                    Some(_) => {
+                        let is_changed = |e: &Exercise| {
+                            changed_file
+                                .as_ref()
+                                .is_some_and(|f: &PathBuf| e.contains_file(f))
+                        };
+                        let state = ProgressState::load();
+                        let changed = exercises.iter().find(|e| is_changed(e));
+                        let pending = changed.into_iter().chain(
+                            exercises
+                                .iter()
+                                .filter(|e| state.status(e) == Status::Pending && !is_changed(e)),
+                        );
+                        let num_done = exercises
+                            .iter()
+                            .filter(|e| state.status(e) != Status::Pending)
+                            .count();
+                        let progress = (num_done, exercises.len());
+                        verify_after_change(pending, progress, &verify_options, changed)
+                    }
+                });
+                if let (true, Some(previous)) = (options.notify, current) {
+                    notify_result(previous, result, skipped);
+                }
+                let exercise = match result {
+                    Ok(()) => {
+                        drop(screen);
+                        print!("{notice}{output}");
+                        return WatchStatus::Finished;
+                    }
+                    Err(exercise) => exercise,
+                };
+                let mut body = std::mem::take(&mut notice) + &output;
+                if current.is_none_or(|current| current.name != exercise.name) {
+                    body += &tui::capture(|| introduce_chapter(exercise, exercises)).1;
+                }
+                let after = ProgressState::load();
+                if is_hint_due(&before, &after, exercise, options.auto_hint_after) {
+                    body += &tui::capture(|| show_auto_hint(exercise)).1;
+                }
+                current = Some(exercise);
+                view.show(body);
+            }
+            view.header = full_screen_header(exercises, current, false);
+        }
+
+        view.footer = match &command_line {
+            Some(line) => format!("!{line}{}", style(" ").reverse()),
+            None => style(&legend).dim().to_string(),
+        };
+        screen.draw(&view);
+
+        match events.recv_timeout(Duration::from_millis(100)) {
+            Ok(DebouncedEvent::Create(b) | DebouncedEvent::Chmod(b) | DebouncedEvent::Write(b))
+                if b.extension() == Some(OsStr::new("rs"))
+                    && b.exists()
+                    && !is_ignored(&options.ignore, &b)
+                    && triggers_rebuild(exercises, &b, options.rebuild) =>
+            {
+                check = Some((b.canonicalize().ok(), false));
+            }
+            Ok(DebouncedEvent::Remove(b)) => {
+                let warning = tui::capture(|| warn_removed(exercises, &b, None, true)).1;
+                if !warning.is_empty() {
+                    view.append(&warning, tui::size().1);
+                }
+            }
+            Ok(DebouncedEvent::Rename(b, to)) => {
+                let warning = tui::capture(|| warn_removed(exercises, &b, Some(&to), true)).1;
+                if !warning.is_empty() {
+                    view.append(&warning, tui::size().1);
+                }
+            }
+            _ => {}
+        }
+
+        while let Ok(key) = keys.try_recv() {
+            // An editor in the terminal gets the keys until it's closed
+            let editing = command_line.is_none() && key == tui::Key::Char('e');
+            if !editing {
+                let _ = handled.send(());
+            }
+            if let Some(line) = &mut command_line {
+                match key {
+                    tui::Key::Char('\r' | '\n') => {
+                        view.show(run_command_line(line));
+                        command_line = None;
+                    }
+                    tui::Key::Char('\x7F' | '\x08') => {
+                        line.pop();
+                    }
+                    tui::Key::Escape => command_line = None,
+                    tui::Key::Char(c) if !c.is_control() => line.push(c),
+                    _ => {}
+                }
+                continue;
+            }
+            if view.scroll_by(key, tui::size()) {
+                continue;
+            }
+            let command = match key {
+                // Ctrl-D quits, like it does in most shells
+                tui::Key::Char('\x04') => WatchCommand::Quit,
+                tui::Key::Char(c) => match WATCH_COMMANDS.iter().find(|(_, k, ..)| *k == c) {
+                    Some((command, ..)) => *command,
+                    None => continue,
+                },
+                _ => continue,
+            };
+            match command {
+                WatchCommand::Hint => view.show(match current {
+                    Some(exercise) => next_hint(exercise),
+                    None => tr!("watch-no-current-exercise").to_string(),
+                }),
+                WatchCommand::About => {
+                    let chapter = current.and_then(|e| chapter::chapter_of(e, exercises));
+                    view.show(match chapter {
+                        Some(chapter) => tui::capture(|| show_about(&chapter)).1,
+                        None => tr!("watch-no-chapter").to_string(),
+                    });
+                }
+                WatchCommand::Edit => {
+                    if let Some(exercise) = current {
+                        if let Err(e) = open_in_editor(&exercise.path, options.editor.as_deref()) {
+                            view.show(e);
+                        }
+                        screen.invalidate();
+                    }
+                    let _ = handled.send(());
+                }
+                WatchCommand::List => {
+                    view.show(tui::capture(|| list(exercises, &ListOptions::default())).1);
+                }
+                WatchCommand::Rerun => check = Some((None, false)),
+                WatchCommand::Skip => {
+                    if let Some(exercise) = current {
+                        let mut state = ProgressState::load();
+                        state.skip(exercise);
+                        let _ = state.save();
+                        notice = format!("{}\n", tr!("watch-skipped", exercise = exercise));
+                    }
+                    check = Some((None, true));
+                }
+                WatchCommand::Clear => view.show(String::new()),
+                WatchCommand::Output => {
+                    show_output = !show_output;
+                    notice = if show_output {
+                        format!("{}\n", tr!("watch-output-shown"))
+                    } else {
+                        format!("{}\n", tr!("watch-output-hidden"))
+                    };
+                    check = Some((None, false));
+                }
+                WatchCommand::Quit => {
+                    drop(screen);
+                    println!("{}", tr!("watch-bye"));
+                    return WatchStatus::Unfinished;
+                }
+                WatchCommand::Restore => {
+                    let (restored, output) = tui::capture(|| repair::repair(exercises));
+                    if restored.is_ok() {
+                        notice = output;
+                        check = Some((None, false));
+                    } else {
+                        view.show(output);
+                    }
+                }
+                WatchCommand::Help => view.show(tui::capture(|| show_watch_help(true)).1),
+                WatchCommand::Execute => command_line = Some(String::new()),
+            }
+        }
+    }
+}
+
+// The top of the full screen watch mode: the progress bar, the chapter and
+// the file to edit, or that the exercises are being checked
+#[cfg(unix)]
+fn full_screen_header(
+    exercises: &[Exercise],
+    current: Option<&Exercise>,
+    checking: bool,
+) -> Vec<String> {
+    let state = ProgressState::load();
+    let done = exercises.iter().filter(|e| state.is_done(e)).count();
+    let mut header = vec![format!(
+        "Progress: {} {done}/{}",
+        ui::text_bar(done, exercises.len(), 40),
+        exercises.len()
+    )];
+    if let Some(chapter) = current.and_then(|e| chapter::chapter_of(e, exercises)) {
+        header.push(style(chapter.summary(&state)).bold().to_string());
+    }
+    header.push(match (checking, current) {
+        (false, Some(exercise)) => format!(
+            "{} {}",
+            style(tr!("watch-editing")).bold(),
+            style(exercise.path.display()).bold().cyan()
+        ),
+        _ => style(tr!("watch-checking")).dim().to_string(),
+    });
+    header
+}
+
+// Run a command line typed after `!` in full screen watch mode, returning
+// what it printed
+#[cfg(unix)]
+fn run_command_line(line: &str) -> String {
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    let Some((program, args)) = parts.split_first() else {
+        return tr!("watch-no-command").to_string();
+    };
+    match Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+    {
+        Ok(output) => {
+            String::from_utf8_lossy(&output.stdout).into_owned()
+                + &String::from_utf8_lossy(&output.stderr)
+        }
+        Err(e) => format!("failed to execute command `{line}`: {e}"),
+    }
+}
+
+// Get the first builds of the new course out of the way, so its first
+// exercises build quickly. The course works without, so failing to is only
+// reported.
+fn warm_up(dir: &Path) {
+    if std::env::set_current_dir(dir).is_err() {
+        return;
+    }
+    let Ok(exercises) = ExerciseList::load() else {
+        return;
+    };
+    match cache::warm_up(&exercises) {
+        Ok(()) => success!("{}", "Warmed up, the first exercises build quickly now"),
+        Err(e) => {
+            warn!("{}", "Couldn't warm up the builds of the exercises");
+            println!("{e}");
+        }
+    }
+}
+
+// Whether all exercises that aren't optional are done
+fn course_done(exercises: &[Exercise]) -> bool {
+    let state = ProgressState::load();
+    exercises.iter().all(|e| e.optional || state.is_done(e))
+}
+
+fn rustc_exists() -> bool {
+    Command::new("rustc")
+        .args(["--version"])
+        .stdout(Stdio::null())
+        .spawn()
+        .and_then(|mut child| child.wait())
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+const FENISH_LINE: &str = r"+----------------------------------------------------+
+|          You made it to the Fe-nish line!          |
++--------------------------  ------------------------+
+                          \\/
+     ▒▒          ▒▒▒▒▒▒▒▒      ▒▒▒▒▒▒▒▒          ▒▒
+   ▒▒▒▒  ▒▒    ▒▒        ▒▒  ▒▒        ▒▒    ▒▒  ▒▒▒▒
+   ▒▒▒▒  ▒▒  ▒▒            ▒▒            ▒▒  ▒▒  ▒▒▒▒
+ ░░▒▒▒▒░░▒▒  ▒▒            ▒▒            ▒▒  ▒▒░░▒▒▒▒
+   ▓▓▓▓▓▓▓▓  ▓▓      ▓▓██  ▓▓  ▓▓██      ▓▓  ▓▓▓▓▓▓▓▓
+     ▒▒▒▒    ▒▒      ████  ▒▒  ████      ▒▒░░  ▒▒▒▒
+       ▒▒  ▒▒▒▒▒▒        ▒▒▒▒▒▒        ▒▒▒▒▒▒  ▒▒
+         ▒▒▒▒▒▒▒▒▒▒▓▓▓▓▓▓▒▒▒▒▒▒▒▒▓▓▒▒▓▓▒▒▒▒▒▒▒▒
+           ▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒
+             ▒▒▒▒▒▒▒▒▒▒██▒▒▒▒▒▒██▒▒▒▒▒▒▒▒▒▒
+           ▒▒  ▒▒▒▒▒▒▒▒▒▒██████▒▒▒▒▒▒▒▒▒▒  ▒▒
+         ▒▒    ▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒    ▒▒
+       ▒▒    ▒▒    ▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒    ▒▒    ▒▒
+       ▒▒  ▒▒    ▒▒                  ▒▒    ▒▒  ▒▒
+           ▒▒  ▒▒                      ▒▒  ▒▒
+
+We hope you enjoyed learning about the various aspects of Rust!
+If you noticed any issues, please don't hesitate to report them to our repo.
+You can also contribute your own exercises to help the greater community!
+
+Before reporting an issue or contributing, please read our guidelines:
+https://github.com/rust-lang/rustlings/blob/main/CONTRIBUTING.md";
+
+const WELCOME: &str = r"       welcome to...
+                 _   _ _
+  _ __ _   _ ___| |_| (_)_ __   __ _ ___
+ | '__| | | / __| __| | | '_ \ / _` / __|
+ | |  | |_| \__ \ |_| | | | | | (_| \__ \
+ |_|   \__,_|___/\__|_|_|_| |_|\__, |___/
+                               |___/";
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::state::ExerciseProgress;
+
+    fn with_attempts(attempts: u32) -> ProgressState {
+        let mut state = ProgressState::default();
+        state.exercises.insert(
+            "exercise".to_string(),
+            ExerciseProgress {
+                attempts,
+                ..Default::default()
+            },
+        );
+        state
+    }
+
+    #[test]
+    fn test_hint_is_due_every_threshold_attempts() {
+        let exercise = Exercise::new("exercise", "exercise.rs");
+        let due = |before, after| {
+            is_hint_due(
+                &with_attempts(before),
+                &with_attempts(after),
+                &exercise,
+                Some(2),
+            )
+        };
+        assert!(!due(0, 1));
+        assert!(due(1, 2));
+        assert!(!due(2, 3));
+        assert!(due(3, 4));
+    }
+
+    #[test]
+    fn test_hint_isnt_due_without_a_new_attempt() {
+        // Checking the exercise again without changing it isn't an attempt
+        let exercise = Exercise::new("exercise", "exercise.rs");
+        let state = with_attempts(2);
+        assert!(!is_hint_due(&state, &state, &exercise, Some(2)));
+    }
+
+    #[test]
+    fn test_hint_isnt_due_without_a_threshold() {
+        let exercise = Exercise::new("exercise", "exercise.rs");
+        let (before, after) = (with_attempts(4), with_attempts(5));
+        assert!(!is_hint_due(&before, &after, &exercise, None));
+        assert!(!is_hint_due(&before, &after, &exercise, Some(0)));
+        assert!(is_hint_due(&before, &after, &exercise, Some(5)));
+    }
+}
@@ -0,0 +1,232 @@
+use crate::hooks::Hooks;
+use crate::workspace::Backend;
+use clap::ValueEnum;
+use glob::Pattern;
+use serde::Deserialize;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const CONFIG_FILE: &str = "rustlings.toml";
+
+// Temporary, swap and backup files of common editors,
+// which watch mode shouldn't mistake for changes to exercises
+const DEFAULT_WATCH_IGNORE: &[&str] = &["*.swp", "*.swo", "*.swx", "*~", "#*#", ".#*", "4913"];
+
+// Which changes make watch mode check the exercises again
+#[derive(Deserialize, ValueEnum, Copy, Clone, PartialEq, Eq, Default, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum RebuildPolicy {
+    // Changes to any exercise
+    #[default]
+    Any,
+    // Only changes to exercises that aren't solved yet, so tinkering with
+    // solved ones or editors saving all open files don't set it off
+    Unsolved,
+}
+
+// Settings read from the user's config file and the `rustlings.toml` file in
+// the current directory. Flags on the command line take precedence over both.
+#[derive(Deserialize, Default, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    // A course directory with its own `info.toml` and exercises
+    pub course: Option<PathBuf>,
+    // Glob patterns of further file names that watch mode ignores
+    #[serde(default)]
+    pub watch_ignore: Vec<String>,
+    // How many milliseconds watch mode waits for further changes to a file
+    // before checking the exercises again
+    pub watch_debounce_ms: Option<u64>,
+    // Which changes make watch mode check the exercises again
+    pub watch_rebuild: Option<RebuildPolicy>,
+    // The command that opens an exercise for editing, e.g. `code --wait`
+    pub editor: Option<String>,
+    // The command that shows long output, e.g. `less -R`, or "" to not page it
+    pub pager: Option<String>,
+    // Whether to print colors
+    pub color: Option<bool>,
+    // Whether to print emoji
+    pub emoji: Option<bool>,
+    // The language to show messages in, e.g. `de`
+    pub lang: Option<String>,
+    // The defaults of the flags with the same names
+    pub clippy: Option<bool>,
+    pub fmt: Option<bool>,
+    pub timeout: Option<u64>,
+    pub success_hints: Option<bool>,
+    pub auto_hint_after: Option<u32>,
+    pub notify: Option<bool>,
+    pub git_autocommit: Option<bool>,
+    pub backend: Option<Backend>,
+    // The URL that solved exercises are posted to, for courses that collect
+    // the progress of their learners
+    pub progress_webhook: Option<String>,
+    // Where `sync` keeps the progress, like `gist` or a URL
+    pub sync_remote: Option<String>,
+    // Shell commands to run on events, like an exercise being solved
+    #[serde(default)]
+    pub hooks: Hooks,
+}
+
+impl Config {
+    // Read the user's config file and the one in the current directory,
+    // with the latter taking precedence. An invalid config file is reported,
+    // but doesn't stop Rustlings.
+    pub fn load() -> Config {
+        let user = user_config_path()
+            .map(|path| load_file(&path))
+            .unwrap_or_default();
+        load_file(Path::new(CONFIG_FILE)).or(user)
+    }
+
+    // Use the settings of `self`, falling back to those of `other`.
+    // The watch ignore patterns of both are kept.
+    fn or(self, other: Config) -> Config {
+        Config {
+            course: self.course.or(other.course),
+            watch_ignore: other
+                .watch_ignore
+                .into_iter()
+                .chain(self.watch_ignore)
+                .collect(),
+            watch_debounce_ms: self.watch_debounce_ms.or(other.watch_debounce_ms),
+            watch_rebuild: self.watch_rebuild.or(other.watch_rebuild),
+            editor: self.editor.or(other.editor),
+            pager: self.pager.or(other.pager),
+            color: self.color.or(other.color),
+            emoji: self.emoji.or(other.emoji),
+            lang: self.lang.or(other.lang),
+            clippy: self.clippy.or(other.clippy),
+            fmt: self.fmt.or(other.fmt),
+            timeout: self.timeout.or(other.timeout),
+            success_hints: self.success_hints.or(other.success_hints),
+            auto_hint_after: self.auto_hint_after.or(other.auto_hint_after),
+            notify: self.notify.or(other.notify),
+            git_autocommit: self.git_autocommit.or(other.git_autocommit),
+            backend: self.backend.or(other.backend),
+            progress_webhook: self.progress_webhook.or(other.progress_webhook),
+            sync_remote: self.sync_remote.or(other.sync_remote),
+            hooks: self.hooks.or(other.hooks),
+        }
+    }
+
+    // How long watch mode waits for further changes before checking the exercises
+    pub fn watch_debounce(&self) -> Duration {
+        Duration::from_millis(self.watch_debounce_ms.unwrap_or(1000))
+    }
+
+    // The patterns of the files that watch mode ignores, including the defaults.
+    // Invalid patterns are reported and skipped.
+    pub fn watch_ignore_patterns(&self) -> Vec<Pattern> {
+        DEFAULT_WATCH_IGNORE
+            .iter()
+            .copied()
+            .chain(self.watch_ignore.iter().map(String::as_str))
+            .filter_map(|pattern| {
+                Pattern::new(pattern)
+                    .map_err(|e| println!("Ignoring the watch pattern '{pattern}': {e}"))
+                    .ok()
+            })
+            .collect()
+    }
+
+    // The command to edit files with: the configured one, or else
+    // the one in $VISUAL or $EDITOR
+    pub fn editor(&self) -> Option<String> {
+        self.editor
+            .clone()
+            .or_else(|| env::var("VISUAL").ok())
+            .or_else(|| env::var("EDITOR").ok())
+            .filter(|editor| !editor.trim().is_empty())
+    }
+}
+
+// `$XDG_CONFIG_HOME/rustlings/config.toml`, which defaults to
+// `~/.config/rustlings/config.toml`
+fn user_config_path() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| home::home_dir().map(|home| home.join(".config")))
+        .map(|dir| dir.join("rustlings").join("config.toml"))
+}
+
+fn load_file(path: &Path) -> Config {
+    let Ok(contents) = fs::read_to_string(path) else {
+        return Config::default();
+    };
+    toml::from_str(&contents).unwrap_or_else(|e| {
+        println!(
+            "Ignoring {} since it couldn't be parsed: {e}",
+            path.display()
+        );
+        Config::default()
+    })
+}
+
+// Whether the file name of the path matches any of the patterns
+pub fn is_ignored(patterns: &[Pattern], path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    patterns.iter().any(|pattern| pattern.matches(name))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_editor_files_are_ignored() {
+        let config = Config {
+            watch_ignore: vec!["scratch*.rs".into()],
+            ..Config::default()
+        };
+        let patterns = config.watch_ignore_patterns();
+
+        for ignored in [
+            "exercises/intro/.intro1.rs.swp",
+            "exercises/intro/intro1.rs~",
+            "exercises/intro/#intro1.rs#",
+            "exercises/intro/.#intro1.rs",
+            "exercises/intro/scratch1.rs",
+        ] {
+            assert!(is_ignored(&patterns, Path::new(ignored)), "{ignored}");
+        }
+        assert!(!is_ignored(
+            &patterns,
+            Path::new("exercises/intro/intro1.rs")
+        ));
+    }
+
+    #[test]
+    fn test_workspace_config_takes_precedence() {
+        let workspace: Config = toml::from_str(
+            r#"
+                clippy = false
+                timeout = 60
+                watch_ignore = ["*.bak"]
+                watch_rebuild = "unsolved"
+            "#,
+        )
+        .unwrap();
+        let user: Config = toml::from_str(
+            r#"
+                clippy = true
+                editor = "vim"
+                watch_ignore = ["*.tmp"]
+            "#,
+        )
+        .unwrap();
+        let config = workspace.or(user);
+
+        assert_eq!(config.clippy, Some(false));
+        assert_eq!(config.timeout, Some(60));
+        assert_eq!(config.editor.as_deref(), Some("vim"));
+        assert_eq!(config.watch_ignore, ["*.tmp", "*.bak"]);
+        assert_eq!(config.watch_debounce(), Duration::from_secs(1));
+        assert_eq!(config.watch_rebuild, Some(RebuildPolicy::Unsolved));
+    }
+}
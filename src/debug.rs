@@ -0,0 +1,106 @@
+// Running an exercise in a debugger, to step through it and inspect its
+// variables. The exercise is built with debug info to `.rustlings/debug` and
+// handed to `rust-gdb` or `rust-lldb`, which come with rustup and print Rust
+// values in a readable way.
+use crate::diagnostics::tidy;
+use crate::exercise::{Exercise, Mode};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+const DEBUG_DIR: &str = ".rustlings/debug";
+
+// The debuggers to try, the one that's native to the platform first
+const DEBUGGERS: &[&str] = if cfg!(target_os = "macos") {
+    &["rust-lldb", "rust-gdb"]
+} else {
+    &["rust-gdb", "rust-lldb"]
+};
+
+pub fn debug(exercise: &Exercise) -> Result<(), ()> {
+    if let Mode::Wasm = exercise.mode {
+        warn!(
+            "{} is compiled to WebAssembly, which can't be debugged here",
+            exercise
+        );
+        return Err(());
+    }
+    let binary = Path::new(DEBUG_DIR).join(&exercise.name);
+    println!("Compiling {exercise} with debug info...");
+    exercise.compile_for_debugging(&binary).map_err(|output| {
+        warn!("Compiling of {} failed! Here's the output:", exercise);
+        println!("{}", tidy(&output.stderr));
+    })?;
+
+    let Some(debugger) = DEBUGGERS.iter().find(|debugger| installed(debugger)) else {
+        println!("Neither rust-gdb nor rust-lldb works here, since gdb or lldb isn't installed.");
+        println!("Once one of them is, debug the exercise with:");
+        println!("  {}", command_line(DEBUGGERS[0], &binary, exercise));
+        return Ok(());
+    };
+    println!("Starting {}", command_line(debugger, &binary, exercise));
+    let status = Command::new(debugger)
+        .args(debugger_args(debugger, &binary, exercise))
+        .status()
+        .map_err(|e| warn!("Failed to start the debugger: {}", e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(())
+    }
+}
+
+// Whether the debugger runs. The rust-gdb and rust-lldb wrappers are always
+// installed with rustup, but they need gdb or lldb to be installed as well.
+fn installed(debugger: &str) -> bool {
+    Command::new(debugger)
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+// The arguments that make the debugger run the binary. Test harnesses run
+// their tests on a single thread, which is easier to follow.
+fn debugger_args(debugger: &str, binary: &Path, exercise: &Exercise) -> Vec<PathBuf> {
+    let mut args = match debugger {
+        "rust-lldb" => vec![PathBuf::from("--")],
+        _ => vec![PathBuf::from("--args")],
+    };
+    args.push(binary.to_path_buf());
+    if let Mode::Test | Mode::Async = exercise.mode {
+        args.push(PathBuf::from("--test-threads=1"));
+    }
+    args
+}
+
+fn command_line(debugger: &str, binary: &Path, exercise: &Exercise) -> String {
+    let args: Vec<String> = debugger_args(debugger, binary, exercise)
+        .iter()
+        .map(|arg| arg.display().to_string())
+        .collect();
+    format!("{debugger} {}", args.join(" "))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_command_line() {
+        let mut exercise = Exercise {
+            mode: Mode::Test,
+            ..Exercise::new("tests1", "exercises/tests/tests1.rs")
+        };
+        let binary = Path::new(".rustlings/debug/tests1");
+        assert_eq!(
+            command_line("rust-gdb", binary, &exercise),
+            "rust-gdb --args .rustlings/debug/tests1 --test-threads=1"
+        );
+        exercise.mode = Mode::Compile;
+        assert_eq!(
+            command_line("rust-lldb", binary, &exercise),
+            "rust-lldb -- .rustlings/debug/tests1"
+        );
+    }
+}
@@ -0,0 +1,205 @@
+use crate::exercise::{Exercise, Mode};
+use crate::ui;
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+const COMPILE_TEMPLATE: &str = r#"fn main() {
+    todo!("Write the exercise");
+}
+"#;
+
+const TEST_TEMPLATE: &str = r#"fn main() {
+    // You can optionally experiment here.
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn it_works() {
+        todo!("Test the exercise");
+    }
+}
+"#;
+
+const ASYNC_TEMPLATE: &str = r#"fn main() {
+    // You can optionally experiment here.
+}
+
+#[cfg(test)]
+mod tests {
+    #[tokio::test]
+    async fn it_works() {
+        todo!("Test the exercise");
+    }
+}
+"#;
+
+// Check that every exercise in `info.toml` is complete: its file exists,
+// it has a hint, and its solution exists and compiles.
+pub fn check(exercises: &[Exercise]) -> Result<(), ()> {
+    let mut problems = Vec::new();
+    let mut names = HashSet::new();
+    let progress_bar = ui::spinner("Checking the exercises...".to_string());
+
+    for exercise in exercises {
+        progress_bar.set_message(format!("Checking {}...", exercise.name));
+        if !names.insert(&exercise.name) {
+            problems.push(format!(
+                "{}: the name is used more than once",
+                exercise.name
+            ));
+        }
+        if !exercise.path.exists() {
+            problems.push(format!(
+                "{}: there's no file at {}",
+                exercise.name,
+                exercise.path.display()
+            ));
+        }
+        if exercise.hints().is_empty() {
+            problems.push(format!("{}: there's no hint", exercise.name));
+        }
+
+        let solution = Exercise {
+            path: exercise.solution_path(),
+            ..exercise.clone()
+        };
+        if !solution.path.exists() {
+            problems.push(format!(
+                "{}: there's no solution at {}",
+                exercise.name,
+                solution.path.display()
+            ));
+            continue;
+        }
+        // Clippy exercises are compiled through a manifest that points to
+        // the exercise, so the solution is linted on its own instead
+        let compiled = match solution.mode {
+            Mode::Clippy => solution.lint(),
+            Mode::Compile | Mode::Test | Mode::Wasm | Mode::Async => solution.compile().map(|_| ()),
+        };
+        if compiled.is_err() {
+            problems.push(format!(
+                "{}: the solution at {} doesn't compile",
+                exercise.name,
+                solution.path.display()
+            ));
+        }
+    }
+    progress_bar.finish_and_clear();
+
+    if problems.is_empty() {
+        success!("All {} exercises are complete", exercises.len());
+        return Ok(());
+    }
+    for problem in &problems {
+        println!("{problem}");
+    }
+    println!();
+    warn!("Found {} problems", problems.len());
+    Err(())
+}
+
+// Scaffold a new exercise along with its solution and add it to `info.toml`.
+// Without a section, the exercise is put into the directory named like the
+// exercise without its number, e.g. `structs` for `structs4`.
+pub fn new(
+    name: &str,
+    mode: Mode,
+    section: Option<&str>,
+    exercises: &[Exercise],
+) -> Result<(), ()> {
+    if exercises.iter().any(|e| e.name == name) {
+        warn!("There already is an exercise called {}", name);
+        return Err(());
+    }
+    let section = section.unwrap_or_else(|| default_section(name));
+    let path = Path::new("exercises")
+        .join(section)
+        .join(format!("{name}.rs"));
+    let exercise = Exercise {
+        mode,
+        ..Exercise::new(name, path)
+    };
+    let solution_path = exercise.solution_path();
+    for path in [&exercise.path, &solution_path] {
+        if path.exists() {
+            warn!("Not overwriting the existing file {}", path.display());
+            return Err(());
+        }
+    }
+
+    let body = template(mode);
+    write_new_file(
+        &exercise.path,
+        &format!("{}\n// I AM NOT DONE\n\n{body}", header(name)),
+    )?;
+    write_new_file(&solution_path, &format!("{}\n{body}", header(name)))?;
+
+    let mode = match mode {
+        Mode::Compile => "compile",
+        Mode::Test => "test",
+        Mode::Clippy => "clippy",
+        Mode::Wasm => "wasm",
+        Mode::Async => "async",
+    };
+    // A placeholder hint, since `dev check` fails on exercises without one
+    let entry = format!(
+        "\n[[exercises]]\nname = \"{name}\"\npath = \"{}\"\nmode = \"{mode}\"\nhint = \"\"\"\n{}\"\"\"\n",
+        exercise.path.display(),
+        placeholder_hint(name)
+    );
+    OpenOptions::new()
+        .append(true)
+        .open("info.toml")
+        .and_then(|mut info| info.write_all(entry.as_bytes()))
+        .map_err(|e| println!("Failed to add {name} to info.toml: {e}"))?;
+
+    success!("Created {}", exercise);
+    println!("Its solution is at {}.", solution_path.display());
+    println!("Don't forget to replace its placeholder hint in info.toml!");
+    Ok(())
+}
+
+fn default_section(name: &str) -> &str {
+    name.trim_end_matches(|c: char| c.is_ascii_digit())
+}
+
+fn header(name: &str) -> String {
+    format!(
+        "// {name}.rs\n//\n// Execute `rustlings hint {name}` or use the `hint` watch subcommand for a\n// hint.\n"
+    )
+}
+
+fn placeholder_hint(name: &str) -> String {
+    format!("No hint for {name} has been written yet.")
+}
+
+fn template(mode: Mode) -> &'static str {
+    match mode {
+        Mode::Test => TEST_TEMPLATE,
+        Mode::Async => ASYNC_TEMPLATE,
+        Mode::Compile | Mode::Clippy | Mode::Wasm => COMPILE_TEMPLATE,
+    }
+}
+
+fn write_new_file(path: &Path, contents: &str) -> Result<(), ()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| println!("Failed to create {}: {e}", dir.display()))?;
+    }
+    fs::write(path, contents).map_err(|e| println!("Failed to write {}: {e}", path.display()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_default_section() {
+        assert_eq!(default_section("structs4"), "structs");
+        assert_eq!(default_section("quiz1"), "quiz");
+        assert_eq!(default_section("as_ref_mut"), "as_ref_mut");
+    }
+}
@@ -0,0 +1,207 @@
+// Tidying up the output of rustc and Clippy before it's shown to learners.
+// Their colors are kept, but the noise around the actual errors is dropped:
+// the "aborting due to previous errors" summaries and diagnostics that are
+// repeated word for word. The location of the first error is put on top.
+use console::{strip_ansi_codes, style};
+
+// Lines that only sum up the errors shown above them
+fn is_noise(line: &str) -> bool {
+    line.starts_with("error: aborting due to")
+        || line.starts_with("error: could not compile")
+        || line.starts_with("warning: build failed, waiting for other jobs")
+        || (line.starts_with("warning: `") && line.contains(" generated "))
+}
+
+// Whether the line starts a new diagnostic, like "error[E0308]: mismatched types"
+fn starts_diagnostic(line: &str) -> bool {
+    line.starts_with("error") || line.starts_with("warning")
+}
+
+pub fn tidy(output: &str) -> String {
+    // Every diagnostic is a block of lines, along with its text without colors
+    let mut blocks: Vec<(Vec<&str>, String)> = vec![(Vec::new(), String::new())];
+    for line in output.lines() {
+        let plain = strip_ansi_codes(line);
+        if is_noise(&plain) {
+            blocks.push((Vec::new(), String::new()));
+            continue;
+        }
+        if starts_diagnostic(&plain) {
+            blocks.push((Vec::new(), String::new()));
+        }
+        let (lines, text) = blocks.last_mut().unwrap();
+        lines.push(line);
+        text.push_str(plain.trim_end());
+        text.push('\n');
+    }
+
+    let mut seen = Vec::new();
+    let mut errors = 0;
+    let mut first_location = None;
+    let mut kept = Vec::new();
+    for (lines, text) in blocks {
+        if lines.is_empty() || (starts_diagnostic(&text) && seen.contains(&text)) {
+            continue;
+        }
+        if text.starts_with("error") {
+            errors += 1;
+            first_location = first_location.or_else(|| location(&text));
+        }
+        kept.extend(lines);
+        seen.push(text);
+    }
+
+    let mut out = String::new();
+    if let Some(location) = first_location {
+        out.push_str(&format!(
+            "{} {}",
+            style("First error at").red().bold(),
+            style(location).bold().underlined()
+        ));
+        if errors > 1 {
+            out.push_str(&format!(" ({errors} errors in total)"));
+        }
+        out.push_str("\n\n");
+    }
+    // Dropping lines can leave several blank lines in a row
+    let mut blank = true;
+    for line in kept {
+        let is_blank = strip_ansi_codes(line).trim().is_empty();
+        if !(is_blank && blank) {
+            out.push_str(line);
+            out.push('\n');
+        }
+        blank = is_blank;
+    }
+    out.trim_end().to_string()
+}
+
+// The location of a diagnostic, from its line like " --> src/main.rs:2:18"
+fn location(diagnostic: &str) -> Option<String> {
+    diagnostic
+        .lines()
+        .find_map(|line| line.trim_start().strip_prefix("--> "))
+        .map(|location| location.trim().to_string())
+}
+
+// Sum up why the tests in the output of a test harness failed, one line per
+// panic, like "tests::counts panicked at src/main.rs:9:9: assertion failed".
+// Panics of other threads, like the worker threads of an async runtime, are
+// told apart from the test itself, since the test only fails after them with
+// a less telling message.
+pub fn explain_panics(output: &str) -> Option<String> {
+    let output = strip_ansi_codes(output);
+    let mut test = None;
+    let mut lines = output.lines();
+    let mut explanations = Vec::new();
+    while let Some(line) = lines.next() {
+        if let Some(name) = line
+            .strip_prefix("---- ")
+            .and_then(|line| line.strip_suffix(" stdout ----"))
+        {
+            test = Some(name);
+            continue;
+        }
+        let Some((thread, location)) = line
+            .strip_prefix("thread '")
+            .and_then(|line| line.split_once("' panicked at "))
+        else {
+            continue;
+        };
+        let Some(test) = test else {
+            continue;
+        };
+        // The message is either on the same line or on the next one
+        let (location, message) = match location.trim_end().strip_suffix(':') {
+            Some(location) => (location, lines.next().unwrap_or_default()),
+            None => location.split_once(": ").unwrap_or((location, "")),
+        };
+        let message = message.trim();
+        explanations.push(if thread == test {
+            format!("{test} panicked at {location}: {message}")
+        } else {
+            format!("{test} failed, since its thread '{thread}' panicked at {location}: {message}")
+        });
+    }
+    if explanations.is_empty() {
+        None
+    } else {
+        Some(explanations.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const OUTPUT: &str = "error[E0308]: mismatched types
+ --> exercises/variables/variables1.rs:2:18
+  |
+2 |     let x: i32 = \"a\";
+  |                  ^^^ expected `i32`, found `&str`
+
+error[E0308]: mismatched types
+ --> exercises/variables/variables1.rs:2:18
+  |
+2 |     let x: i32 = \"a\";
+  |                  ^^^ expected `i32`, found `&str`
+
+error[E0425]: cannot find function `undefined` in this scope
+ --> exercises/variables/variables1.rs:4:5
+  |
+4 |     undefined();
+  |     ^^^^^^^^^ not found in this scope
+
+error: aborting due to 3 previous errors
+
+Some errors have detailed explanations: E0308, E0425.
+For more information about an error, try `rustc --explain E0308`.
+";
+
+    #[test]
+    fn test_tidy() {
+        let tidy = strip_ansi_codes(&tidy(OUTPUT)).to_string();
+        assert!(tidy.starts_with(
+            "First error at exercises/variables/variables1.rs:2:18 (2 errors in total)\n\n"
+        ));
+        assert_eq!(tidy.matches("mismatched types").count(), 1);
+        assert!(!tidy.contains("aborting"));
+        assert!(tidy.contains("not found in this scope\n\nSome errors"));
+        assert!(tidy.ends_with("try `rustc --explain E0308`."));
+    }
+
+    #[test]
+    fn test_tidy_without_errors() {
+        assert_eq!(tidy("thread 'main' panicked\n"), "thread 'main' panicked");
+    }
+
+    #[test]
+    fn test_explain_panics() {
+        let output = "running 2 tests
+test tests::sleeps ... FAILED
+test tests::spawns ... FAILED
+
+failures:
+
+---- tests::sleeps stdout ----
+
+thread 'tests::sleeps' panicked at exercises/async/async1.rs:12:9:
+assertion failed: done
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+---- tests::spawns stdout ----
+
+thread 'tokio-runtime-worker' panicked at exercises/async/async1.rs:20:13:
+not yet
+thread 'tests::spawns' panicked at exercises/async/async1.rs:22:29:
+called `Result::unwrap()` on an `Err` value: JoinError::Panic(Id(3), \"not yet\", ..)
+";
+        assert_eq!(
+            explain_panics(output).unwrap(),
+            "tests::sleeps panicked at exercises/async/async1.rs:12:9: assertion failed: done
+tests::spawns failed, since its thread 'tokio-runtime-worker' panicked at exercises/async/async1.rs:20:13: not yet
+tests::spawns panicked at exercises/async/async1.rs:22:29: called `Result::unwrap()` on an `Err` value: JoinError::Panic(Id(3), \"not yet\", ..)"
+        );
+        assert_eq!(explain_panics("test result: ok"), None);
+    }
+}
@@ -0,0 +1,259 @@
+// Diagnosing the setup Rustlings runs in. Most problems when starting out
+// aren't with the exercises, but with the environment: an outdated
+// toolchain, a course with missing files, a system that doesn't let
+// Rustlings watch the exercises or a disk that's full. Every check that
+// finds a problem tells how to fix it.
+use crate::exercise::{Exercise, ExerciseList};
+use crate::upgrade::is_newer;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc::channel;
+use std::time::Duration;
+
+// The oldest Rust that Rustlings and its exercises build with
+const MIN_RUST_VERSION: &str = env!("CARGO_PKG_RUST_VERSION");
+
+// Compiling the exercises needs room for their binaries, and the workspace
+// backend for a whole target directory
+const MIN_FREE_SPACE: u64 = 1024 * 1024 * 1024;
+
+// What a check found wrong, and what to do about it
+struct Problem {
+    message: String,
+    fix: String,
+}
+
+impl Problem {
+    fn new(message: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            fix: fix.into(),
+        }
+    }
+}
+
+// A check passes with a line telling what it found
+type Check = Result<String, Problem>;
+
+// Run all checks, failing if any of them found a problem
+pub fn doctor() -> Result<(), ()> {
+    let checks = [
+        check_toolchain("rustc"),
+        check_toolchain("cargo"),
+        check_exercises(),
+        check_watcher(),
+        check_disk_space(),
+    ];
+    let mut problems = 0;
+    for check in checks {
+        match check {
+            Ok(found) => success!("{}", found),
+            Err(problem) => {
+                warn!("{}", problem.message);
+                println!("  {}", problem.fix);
+                problems += 1;
+            }
+        }
+    }
+    println!();
+    if problems == 0 {
+        println!("Everything looks fine, happy coding!");
+        Ok(())
+    } else {
+        println!("Found {problems} problems, fix them and run `rustlings doctor` again.");
+        Err(())
+    }
+}
+
+fn check_toolchain(tool: &str) -> Check {
+    let version = Command::new(tool)
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string());
+    match version {
+        Some(version) => check_version(&version),
+        None => Err(Problem::new(
+            format!("Couldn't run `{tool} --version`"),
+            "Install Rust from https://rustup.rs, then open a new terminal so it's found.",
+        )),
+    }
+}
+
+// Check the line a tool prints for `--version`, like "rustc 1.70.0 (90c541806 2023-05-31)"
+fn check_version(line: &str) -> Check {
+    let version = line.split_whitespace().nth(1).unwrap_or_default();
+    if is_newer(MIN_RUST_VERSION, version) {
+        return Err(Problem::new(
+            format!("{line} is older than Rust {MIN_RUST_VERSION}, which Rustlings needs"),
+            "Run `rustup update` to get the latest Rust.",
+        ));
+    }
+    Ok(line.to_string())
+}
+
+// Check that the exercises in `info.toml` are all there, and that there are
+// no others, e.g. left over from an older version of the course
+fn check_exercises() -> Check {
+    let manifest = fs::read_to_string("info.toml").map_err(|_| {
+        Problem::new(
+            "There's no info.toml in this directory",
+            "Run `rustlings doctor` in the rustlings directory, or pass `--course` with the path to it.",
+        )
+    })?;
+    let exercises = toml::from_str::<ExerciseList>(&manifest)
+        .map_err(|e| {
+            Problem::new(
+                format!("info.toml is broken: {}", e.message()),
+                "Undo your changes to info.toml, e.g. with `git checkout info.toml`.",
+            )
+        })?
+        .exercises;
+
+    let missing = crate::repair::missing(&exercises);
+    if !missing.is_empty() {
+        let names: Vec<&str> = missing.iter().map(|e| e.name.as_str()).collect();
+        return Err(Problem::new(
+            format!(
+                "{} exercises are missing their file: {}",
+                missing.len(),
+                names.join(", ")
+            ),
+            "Run `rustlings repair` to restore them.",
+        ));
+    }
+
+    let files: Vec<PathBuf> = glob::glob("exercises/**/*.rs")
+        .map(|paths| paths.flatten().collect())
+        .unwrap_or_default();
+    let unknown = unknown_files(&files, &exercises);
+    if !unknown.is_empty() {
+        let paths: Vec<String> = unknown.iter().map(|p| p.display().to_string()).collect();
+        return Err(Problem::new(
+            format!(
+                "{} files in exercises/ aren't part of info.toml: {}",
+                unknown.len(),
+                paths.join(", ")
+            ),
+            "Your exercises may be from another version of Rustlings. Update the repository, or move the files elsewhere.",
+        ));
+    }
+    Ok(format!("All {} exercises are in place", exercises.len()))
+}
+
+// The source files that don't belong to any exercise
+fn unknown_files<'a>(files: &'a [PathBuf], exercises: &[Exercise]) -> Vec<&'a PathBuf> {
+    files
+        .iter()
+        .filter(|file| {
+            !exercises
+                .iter()
+                .any(|exercise| file.starts_with(&exercise.path))
+        })
+        .collect()
+}
+
+// Check that the exercises can be watched for changes the way watch mode does
+fn check_watcher() -> Check {
+    if !Path::new("exercises").is_dir() {
+        return Err(Problem::new(
+            "There's no exercises directory to watch",
+            "Run `rustlings doctor` in the rustlings directory, or pass `--course` with the path to it.",
+        ));
+    }
+    let (tx, _rx) = channel();
+    let watched = RecommendedWatcher::new(tx, Duration::from_secs(1))
+        .and_then(|mut watcher| watcher.watch(Path::new("./exercises"), RecursiveMode::Recursive));
+    match watched {
+        Ok(()) => Ok("Watch mode can watch the exercises for changes".to_string()),
+        Err(e) if is_watch_limit(&e) => Err(Problem::new(
+            "Your system doesn't allow watching any more files",
+            "Raise the limit with `sudo sysctl fs.inotify.max_user_watches=524288`, or run `rustlings watch --poll`.",
+        )),
+        Err(e) => Err(Problem::new(
+            format!("Watch mode can't watch the exercises for changes: {e:?}"),
+            "Run `rustlings watch --poll` to check the files for changes periodically instead.",
+        )),
+    }
+}
+
+// Whether watching failed because the limit of inotify watches was reached
+pub fn is_watch_limit(error: &notify::Error) -> bool {
+    match error {
+        notify::Error::Generic(message) => message.contains("inotify watches"),
+        _ => false,
+    }
+}
+
+fn check_disk_space() -> Check {
+    let Some(free) = free_space(Path::new(".")) else {
+        return Ok("Free disk space can't be checked on this system".to_string());
+    };
+    let gigabytes = free as f64 / (1024.0 * 1024.0 * 1024.0);
+    if free < MIN_FREE_SPACE {
+        return Err(Problem::new(
+            format!("Only {gigabytes:.1} GB of disk space are left"),
+            "Free up some space, e.g. with `cargo clean` in your other Rust projects.",
+        ));
+    }
+    Ok(format!("{gigabytes:.1} GB of disk space are free"))
+}
+
+// The space available to unprivileged users on the file system of the path
+#[cfg(unix)]
+fn free_space(path: &Path) -> Option<u64> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stat = std::mem::MaybeUninit::uninit();
+    // SAFETY: `path` is a valid C string and statvfs fills in `stat` if it succeeds
+    let stat: libc::statvfs = unsafe {
+        if libc::statvfs(path.as_ptr(), stat.as_mut_ptr()) != 0 {
+            return None;
+        }
+        stat.assume_init()
+    };
+    // The types of the fields differ between platforms
+    #[allow(clippy::unnecessary_cast)]
+    Some(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+#[cfg(not(unix))]
+fn free_space(_path: &Path) -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_check_version() {
+        assert!(check_version("rustc 1.95.0 (59807616e 2026-04-14)").is_ok());
+        assert!(check_version("cargo 1.83.0-nightly (ad074abe3 2024-10-04)").is_ok());
+        let problem = check_version("rustc 1.60.0 (7737e0b5c 2022-04-04)").unwrap_err();
+        assert!(problem.message.contains("older than Rust"));
+    }
+
+    #[test]
+    fn test_unknown_files() {
+        let exercise = |name: &str, path: &str| Exercise::new(name, path);
+        let exercises = [
+            exercise("intro1", "exercises/intro/intro1.rs"),
+            exercise("modules4", "exercises/modules/modules4"),
+        ];
+        let files = [
+            PathBuf::from("exercises/intro/intro1.rs"),
+            PathBuf::from("exercises/modules/modules4/helper.rs"),
+            PathBuf::from("exercises/intro/intro0.rs"),
+        ];
+        assert_eq!(
+            unknown_files(&files, &exercises),
+            [&PathBuf::from("exercises/intro/intro0.rs")]
+        );
+    }
+}
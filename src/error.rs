@@ -0,0 +1,22 @@
+use std::fmt::{self, Display, Formatter};
+
+// Why the library couldn't do what it was asked to
+#[derive(Debug)]
+pub enum Error {
+    // The info.toml of the course couldn't be read or is invalid
+    Course(String),
+    // The exercise doesn't compile, fails or is still marked as not done,
+    // along with the output that tells why
+    Exercise { name: String, output: String },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Course(e) => write!(f, "Couldn't read the exercises in info.toml: {e}"),
+            Error::Exercise { name, output } => write!(f, "{name} didn't pass:\n{output}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,307 @@
+// Reporting the checks of `run` and `verify` as JSON events for editor
+// plugins and other programs, one object per line on stdout.
+//
+// The compiler and Clippy are asked for JSON diagnostics, which are passed
+// on as they are. Everything else Rustlings would print is left out.
+use crate::exercise::{self, Exercise, ExerciseOutput, Mode, RunInput};
+use crate::hooks;
+use crate::state::ProgressState;
+use crate::ui;
+use crate::verify::{self, VerifyOptions};
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::Value;
+use std::path::Path;
+
+// How `run` and `verify` report their results
+#[derive(Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    // Messages for people
+    #[default]
+    Human,
+    // JSON events, one per line
+    Json,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum Event<'a> {
+    // Checking an exercise started
+    Started {
+        exercise: &'a str,
+        path: &'a Path,
+        mode: Mode,
+    },
+    // A diagnostic of the compiler or Clippy, in their JSON format
+    Diagnostic {
+        exercise: &'a str,
+        diagnostic: Value,
+    },
+    // What the exercise printed, or what a failed check printed
+    // that isn't a diagnostic, like the changes rustfmt would make
+    Output {
+        exercise: &'a str,
+        stdout: &'a str,
+        stderr: &'a str,
+    },
+    // The result of a single test of a test exercise
+    TestResult {
+        exercise: &'a str,
+        name: &'a str,
+        passed: bool,
+    },
+    // Checking an exercise finished, along with the stage that failed
+    Finished {
+        exercise: &'a str,
+        status: Status,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        stage: Option<Stage>,
+    },
+    // How many exercises are done after `verify`
+    Summary {
+        done: usize,
+        total: usize,
+    },
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum Status {
+    // The exercise passed all checks and is marked as done
+    Passed,
+    // The exercise passed all checks, but still contains `I AM NOT DONE`
+    Pending,
+    // One of the checks failed
+    Failed,
+}
+
+#[derive(Serialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+enum Stage {
+    Compile,
+    Lint,
+    Format,
+    Run,
+    Test,
+}
+
+// Ask for output that can be passed on in events
+fn start() {
+    exercise::enable_json_diagnostics();
+    ui::disable_color();
+}
+
+fn emit(event: &Event) {
+    let event = serde_json::to_string(event).expect("Failed to serialize event");
+    println!("{event}");
+}
+
+// Verify the exercises like `verify`, stopping at the first one that
+// isn't done, and report the progress as events
+pub fn verify(exercises: &[Exercise], options: &VerifyOptions) -> Result<(), ()> {
+    start();
+    let mut state = ProgressState::load();
+    let mut done = 0;
+    let mut result = Ok(());
+    for exercise in exercises {
+        let unchanged = options.is_cached(&state, exercise);
+        if unchanged || state.is_skipped(exercise) {
+            done += 1;
+            continue;
+        }
+        let status = check_and_record(exercise, options, &RunInput::default(), &mut state);
+        if status != Status::Passed {
+            result = Err(());
+            break;
+        }
+        done += 1;
+    }
+    let _ = state.save();
+    emit(&Event::Summary {
+        done,
+        total: exercises.len(),
+    });
+    result
+}
+
+// Run a single exercise like `run`, reporting the steps as events.
+// Fails only if one of the checks failed, not if the exercise isn't done yet.
+pub fn run(exercise: &Exercise, options: &VerifyOptions, input: &RunInput) -> Result<(), ()> {
+    start();
+    let mut state = ProgressState::load();
+    let status = check_and_record(exercise, options, input, &mut state);
+    let _ = state.save();
+    match status {
+        Status::Failed => Err(()),
+        Status::Passed | Status::Pending => Ok(()),
+    }
+}
+
+fn check_and_record(
+    exercise: &Exercise,
+    options: &VerifyOptions,
+    input: &RunInput,
+    state: &mut ProgressState,
+) -> Status {
+    emit(&Event::Started {
+        exercise: &exercise.name,
+        path: &exercise.path,
+        mode: exercise.mode,
+    });
+    let (status, stage) = match check(exercise, options, input) {
+        Ok(()) if exercise.looks_done() => (Status::Passed, None),
+        Ok(()) => (Status::Pending, None),
+        Err((stage, error)) => {
+            state.record_error(exercise, error);
+            hooks::fire(hooks::Event::Failed, Some(exercise));
+            (Status::Failed, Some(stage))
+        }
+    };
+    verify::record(state, exercise, status == Status::Passed, options);
+    emit(&Event::Finished {
+        exercise: &exercise.name,
+        status,
+        stage,
+    });
+    status
+}
+
+// Compile, check and run the exercise, returning the stage that failed
+// along with the line of its output that explains best what went wrong
+fn check(
+    exercise: &Exercise,
+    options: &VerifyOptions,
+    input: &RunInput,
+) -> Result<(), (Stage, Option<String>)> {
+    let compilation = exercise
+        .compile()
+        .map_err(|output| (Stage::Compile, report_failure(exercise, &output)))?;
+    if verify::needs_lint(exercise, options) {
+        exercise
+            .lint()
+            .map_err(|output| (Stage::Lint, report_failure(exercise, &output)))?;
+    }
+    if verify::needs_fmt_check(exercise, options) {
+        exercise
+            .check_formatting()
+            .map_err(|output| (Stage::Format, report_failure(exercise, &output)))?;
+    }
+    // Like in `verify`, Clippy exercises only have to compile without warnings
+    if let Mode::Clippy = exercise.mode {
+        return Ok(());
+    }
+
+    let result = compilation.run_with(input, options.timeout);
+    let output = result.as_ref().unwrap_or_else(|output| output);
+    emit(&Event::Output {
+        exercise: &exercise.name,
+        stdout: &output.stdout,
+        stderr: &output.stderr,
+    });
+    if let Mode::Test | Mode::Async = exercise.mode {
+        for (name, passed) in test_results(&output.stdout) {
+            emit(&Event::TestResult {
+                exercise: &exercise.name,
+                name,
+                passed,
+            });
+        }
+    }
+    result.map(|_| ()).map_err(|output| {
+        let stage = match exercise.mode {
+            Mode::Test | Mode::Async => Stage::Test,
+            _ => Stage::Run,
+        };
+        let error = verify::first_error_line(&(output.stdout + &output.stderr));
+        (stage, error)
+    })
+}
+
+// Report the diagnostics in the output of a failed check, or the output itself
+// if it has none, and pick the message that explains best what went wrong
+fn report_failure(exercise: &Exercise, output: &ExerciseOutput) -> Option<String> {
+    let diagnostics = diagnostics(&output.stdout)
+        .chain(diagnostics(&output.stderr))
+        .collect::<Vec<_>>();
+    if diagnostics.is_empty() {
+        emit(&Event::Output {
+            exercise: &exercise.name,
+            stdout: &output.stdout,
+            stderr: &output.stderr,
+        });
+        return verify::first_error_line(&format!("{}{}", output.stderr, output.stdout));
+    }
+    let error = diagnostics
+        .iter()
+        .find(|diagnostic| diagnostic["level"] == "error")
+        .and_then(|diagnostic| diagnostic["message"].as_str())
+        .map(|message| format!("error: {message}"));
+    for diagnostic in diagnostics {
+        emit(&Event::Diagnostic {
+            exercise: &exercise.name,
+            diagnostic,
+        });
+    }
+    error
+}
+
+// The JSON diagnostics in the output of rustc or `cargo clippy`. Cargo wraps
+// them in messages of its own, and also reports e.g. the artifacts it built.
+pub fn diagnostics(output: &str) -> impl Iterator<Item = Value> + '_ {
+    output
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter_map(|mut message| match message.get("reason") {
+            Some(reason) if reason == "compiler-message" => Some(message["message"].take()),
+            Some(_) => None,
+            None => Some(message),
+        })
+        .filter(|diagnostic| diagnostic.get("level").is_some())
+}
+
+// The names of the tests in the output of a test harness, and whether they passed
+fn test_results(output: &str) -> impl Iterator<Item = (&str, bool)> {
+    output.lines().filter_map(|line| {
+        let (name, result) = line.strip_prefix("test ")?.rsplit_once(" ... ")?;
+        match result.trim() {
+            "ok" => Some((name, true)),
+            "FAILED" => Some((name, false)),
+            _ => None,
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_diagnostics_from_rustc_and_cargo() {
+        let rustc =
+            r#"{"$message_type":"diagnostic","message":"mismatched types","level":"error"}"#;
+        let cargo = concat!(
+            r#"{"reason":"compiler-artifact","target":{}}"#,
+            "\n",
+            r#"{"reason":"compiler-message","message":{"message":"unused variable","level":"warning"}}"#,
+            "\n",
+            r#"{"reason":"build-finished","success":false}"#,
+        );
+        let messages = |output| {
+            diagnostics(output)
+                .map(|diagnostic| diagnostic["message"].as_str().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(messages(rustc), ["mismatched types"]);
+        assert_eq!(messages(cargo), ["unused variable"]);
+        assert_eq!(messages("error: could not compile"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_test_results() {
+        let output = "\nrunning 3 tests\ntest tests::a ... ok\ntest tests::b ... FAILED\ntest tests::c ... ignored\n\ntest result: FAILED. 1 passed; 1 failed";
+        assert_eq!(
+            test_results(output).collect::<Vec<_>>(),
+            [("tests::a", true), ("tests::b", false)]
+        );
+    }
+}
@@ -1,17 +1,67 @@
+use crate::cache;
+use crate::error::Error;
+use crate::run;
+use crate::ui;
+use crate::workspace;
+use clap::ValueEnum;
 use regex::Regex;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::env;
 use std::fmt::{self, Display, Formatter};
 use std::fs::{self, remove_file, File};
-use std::io::Read;
-use std::path::PathBuf;
-use std::process::{self, Command};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Output, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
 
-const RUSTC_COLOR_ARGS: &[&str] = &["--color", "always"];
 const RUSTC_EDITION_ARGS: &[&str] = &["--edition", "2021"];
 const I_AM_DONE_REGEX: &str = r"(?m)^\s*///?\s*I\s+AM\s+NOT\s+DONE";
 const CONTEXT: usize = 2;
 const CLIPPY_CARGO_TOML_PATH: &str = "./exercises/clippy/Cargo.toml";
+const LINT_DIR: &str = ".rustlings/clippy";
+const DEPENDENCIES_DIR: &str = ".rustlings/dependencies";
+pub const WASM_TARGET: &str = "wasm32-unknown-unknown";
+// The runtime async exercises get, unless they depend on another version of it
+const ASYNC_RUNTIME: &str =
+    r#"tokio = { version = "1", features = ["macros", "rt", "rt-multi-thread", "time", "sync"] }"#;
+
+// Colored compiler output, unless colors are turned off
+fn color_args() -> [&'static str; 2] {
+    if ui::wants_color() {
+        ["--color", "always"]
+    } else {
+        ["--color", "never"]
+    }
+}
+
+// Whether the compiler and Clippy report their diagnostics as JSON
+static JSON_DIAGNOSTICS: AtomicBool = AtomicBool::new(false);
+
+// Have the compiler and Clippy report their diagnostics as JSON from now on,
+// one object per line, for programs consuming the output of Rustlings
+pub fn enable_json_diagnostics() {
+    JSON_DIAGNOSTICS.store(true, Ordering::Relaxed);
+}
+
+// How rustc should report diagnostics
+fn rustc_diagnostic_args() -> Vec<&'static str> {
+    let mut args = color_args().to_vec();
+    if JSON_DIAGNOSTICS.load(Ordering::Relaxed) {
+        args.push("--error-format=json");
+    }
+    args
+}
+
+// How Cargo should report the diagnostics of Clippy
+fn cargo_diagnostic_args() -> Vec<&'static str> {
+    let mut args = color_args().to_vec();
+    if JSON_DIAGNOSTICS.load(Ordering::Relaxed) {
+        args.push("--message-format=json");
+    }
+    args
+}
 
 // Get a temporary file name that is hopefully unique
 #[inline]
@@ -25,7 +75,7 @@ fn temp_file() -> String {
 }
 
 // The mode of the exercise.
-#[derive(Deserialize, Copy, Clone, Debug)]
+#[derive(Deserialize, Serialize, ValueEnum, Copy, Clone, Debug)]
 #[serde(rename_all = "lowercase")]
 pub enum Mode {
     // Indicates that the exercise should be compiled as a binary
@@ -34,6 +84,43 @@ pub enum Mode {
     Test,
     // Indicates that the exercise should be linted with clippy
     Clippy,
+    // Indicates that the exercise should be compiled to WebAssembly,
+    // and run with wasmtime if it's installed
+    Wasm,
+    // Indicates that the exercise should be compiled as a test harness
+    // with an async runtime, for tests like `#[tokio::test]`
+    Async,
+}
+
+impl Display for Mode {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Mode::Compile => "compile",
+            Mode::Test => "test",
+            Mode::Clippy => "clippy",
+            Mode::Wasm => "wasm",
+            Mode::Async => "async",
+        })
+    }
+}
+
+// How hard an exercise is, for learners who want to skip ahead
+#[derive(Deserialize, Serialize, ValueEnum, Copy, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+        })
+    }
 }
 
 #[derive(Deserialize)]
@@ -41,9 +128,20 @@ pub struct ExerciseList {
     pub exercises: Vec<Exercise>,
 }
 
+impl ExerciseList {
+    // The exercises of the course in the current directory, read from its
+    // info.toml and in the order they're meant to be done in
+    pub fn load() -> Result<Vec<Exercise>, Error> {
+        let toml_str = fs::read_to_string("info.toml").map_err(|e| Error::Course(e.to_string()))?;
+        let list = toml::from_str::<ExerciseList>(&toml_str)
+            .map_err(|e| Error::Course(e.message().to_string()))?;
+        crate::prerequisites::order(list.exercises).map_err(Error::Course)
+    }
+}
+
 // A representation of a rustlings exercise.
 // This is deserialized from the accompanying info.toml file
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Clone, Debug)]
 pub struct Exercise {
     // Name of the exercise
     pub name: String,
@@ -52,7 +150,52 @@ pub struct Exercise {
     // The mode of the exercise (Test, Compile, or Clippy)
     pub mode: Mode,
     // The hint text associated with the exercise
+    #[serde(default)]
     pub hint: String,
+    // Progressive hints which are revealed one at a time.
+    // If present, these are used instead of `hint`
+    #[serde(default)]
+    pub hints: Vec<String>,
+    // The concepts the exercise is about, e.g. "ownership"
+    #[serde(default)]
+    pub topics: Vec<String>,
+    // Whether the exercise must be formatted with rustfmt to pass
+    #[serde(default)]
+    pub check_fmt: bool,
+    // The names of the exercises that have to be done before this one
+    #[serde(default)]
+    pub requires: Vec<String>,
+    // How hard the exercise is, if the course says so
+    #[serde(default)]
+    pub difficulty: Option<Difficulty>,
+    // How many minutes solving the exercise takes, if the course says so
+    #[serde(default)]
+    pub estimated_minutes: Option<u32>,
+    // Bonus exercises that don't have to be done to complete the course
+    #[serde(default)]
+    pub optional: bool,
+    // Crates the exercise uses, as lines of a Cargo.toml, e.g. `rand = "0.8"`.
+    // Exercises with dependencies are built through Cargo instead of rustc.
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+}
+
+// The configuration of an exercise as Rustlings resolved it,
+// used to inspect an exercise without building it
+#[derive(Serialize, Debug)]
+pub struct ExerciseMetadata<'a> {
+    pub name: &'a str,
+    pub path: &'a Path,
+    pub mode: Mode,
+    pub edition: &'a str,
+    // The number of hints available for the exercise
+    pub hints: usize,
+    // The directory the exercise lives in, if it's part of a section
+    pub section: Option<String>,
+    pub topics: &'a [String],
+    pub difficulty: Option<Difficulty>,
+    // How many minutes solving the exercise likely takes
+    pub estimated_minutes: u32,
 }
 
 // An enum to track of the state of an Exercise.
@@ -83,12 +226,32 @@ pub struct CompiledExercise<'a> {
 }
 
 impl<'a> CompiledExercise<'a> {
-    // Run the compiled exercise
-    pub fn run(&self) -> Result<ExerciseOutput, ExerciseOutput> {
-        self.exercise.run()
+    // Run the compiled exercise, killing it if it takes longer than the timeout
+    pub fn run(&self, timeout: Duration) -> Result<ExerciseOutput, ExerciseOutput> {
+        self.exercise.run(&RunInput::default(), timeout)
+    }
+
+    // Run the compiled exercise with the given arguments and standard input
+    pub fn run_with(
+        &self,
+        input: &RunInput,
+        timeout: Duration,
+    ) -> Result<ExerciseOutput, ExerciseOutput> {
+        self.exercise.run(input, timeout)
     }
 }
 
+// Input for a compiled exercise, for exercises that read arguments or stdin
+#[derive(Default, Clone, Copy)]
+pub struct RunInput<'a> {
+    // Arguments passed on to the binary
+    pub args: &'a [String],
+    // A file whose contents are piped into the standard input of the binary
+    pub stdin: Option<&'a Path>,
+    // Have test harnesses print the output of the tests as they run
+    pub nocapture: bool,
+}
+
 // A representation of an already executed binary
 #[derive(Debug)]
 pub struct ExerciseOutput {
@@ -107,18 +270,77 @@ impl Drop for FileHandle {
 }
 
 impl Exercise {
-    pub fn compile(&self) -> Result<CompiledExercise, ExerciseOutput> {
+    // A compile mode exercise with nothing but a name and a path, to be
+    // adjusted with struct update syntax
+    pub fn new(name: &str, path: impl Into<PathBuf>) -> Exercise {
+        Exercise {
+            name: name.to_string(),
+            path: path.into(),
+            mode: Mode::Compile,
+            hint: String::new(),
+            hints: Vec::new(),
+            topics: Vec::new(),
+            check_fmt: false,
+            requires: Vec::new(),
+            difficulty: None,
+            estimated_minutes: None,
+            optional: false,
+            dependencies: Vec::new(),
+        }
+    }
+
+    pub fn compile(&self) -> Result<CompiledExercise<'_>, ExerciseOutput> {
+        self.compile_for(None)
+    }
+
+    // Compile the exercise, optionally for a target triple other than the host
+    pub fn compile_for(
+        &self,
+        target: Option<&str>,
+    ) -> Result<CompiledExercise<'_>, ExerciseOutput> {
+        let target_args: Vec<&str> = match target {
+            Some(target) => vec!["--target", target],
+            None => Vec::new(),
+        };
+        if let Some((manifest, target_dir)) = self.cargo_dirs()? {
+            return self
+                .compile_with_cargo(&manifest, &target_dir, &target_args)
+                .map_err(|output| self.relative_to_course(output));
+        }
         let cmd = match self.mode {
             Mode::Compile => Command::new("rustc")
-                .args([self.path.to_str().unwrap(), "-o", &temp_file()])
-                .args(RUSTC_COLOR_ARGS)
+                .arg(self.main_file())
+                .args(["-o", &temp_file()])
+                .args(rustc_diagnostic_args())
                 .args(RUSTC_EDITION_ARGS)
+                .args(&target_args)
                 .output(),
-            Mode::Test => Command::new("rustc")
-                .args(["--test", self.path.to_str().unwrap(), "-o", &temp_file()])
-                .args(RUSTC_COLOR_ARGS)
+            Mode::Test | Mode::Async => Command::new("rustc")
+                .arg("--test")
+                .arg(self.main_file())
+                .args(["-o", &temp_file()])
+                .args(rustc_diagnostic_args())
                 .args(RUSTC_EDITION_ARGS)
+                .args(&target_args)
                 .output(),
+            Mode::Wasm => {
+                if run::target_installed(WASM_TARGET) == Some(false) {
+                    return Err(ExerciseOutput {
+                        stdout: String::new(),
+                        stderr: format!(
+                            "error: the target `{WASM_TARGET}` isn't installed\n\
+                             Try running `rustup target add {WASM_TARGET}` first.\n"
+                        ),
+                    });
+                }
+                Command::new("rustc")
+                    .arg(self.main_file())
+                    .args(["-o", &temp_file()])
+                    .args(["--target", WASM_TARGET])
+                    .args(rustc_diagnostic_args())
+                    .args(RUSTC_EDITION_ARGS)
+                    .output()
+            }
             Mode::Clippy => {
                 let cargo_toml = format!(
                     r#"[package]
@@ -141,9 +363,11 @@ path = "{}.rs""#,
                 // compilation failure, this would silently fail. But we expect
                 // clippy to reflect the same failure while compiling later.
                 Command::new("rustc")
-                    .args([self.path.to_str().unwrap(), "-o", &temp_file()])
-                    .args(RUSTC_COLOR_ARGS)
+                    .arg(self.main_file())
+                    .args(["-o", &temp_file()])
+                    .args(rustc_diagnostic_args())
                     .args(RUSTC_EDITION_ARGS)
+                    .args(&target_args)
                     .output()
                     .expect("Failed to compile!");
                 // Due to an issue with Clippy, a cargo clean is required to catch all lints.
@@ -152,12 +376,13 @@ path = "{}.rs""#,
                 // https://github.com/rust-lang/rust-clippy/issues/3837
                 Command::new("cargo")
                     .args(["clean", "--manifest-path", CLIPPY_CARGO_TOML_PATH])
-                    .args(RUSTC_COLOR_ARGS)
+                    .args(color_args())
                     .output()
                     .expect("Failed to run 'cargo clean'");
                 Command::new("cargo")
                     .args(["clippy", "--manifest-path", CLIPPY_CARGO_TOML_PATH])
-                    .args(RUSTC_COLOR_ARGS)
+                    .args(cargo_diagnostic_args())
+                    .args(&target_args)
                     .args(["--", "-D", "warnings", "-D", "clippy::float_cmp"])
                     .output()
             }
@@ -178,20 +403,410 @@ path = "{}.rs""#,
         }
     }
 
-    fn run(&self) -> Result<ExerciseOutput, ExerciseOutput> {
-        let arg = match self.mode {
-            Mode::Test => "--show-output",
-            _ => "",
+    // Type-check the exercise without generating code, which shows most
+    // errors in a fraction of the time a full build takes. Clippy exercises
+    // are linted anyway, and WebAssembly ones can only be checked for their
+    // target, so those pass without being checked here.
+    pub fn check(&self) -> Result<(), ExerciseOutput> {
+        if matches!(self.mode, Mode::Clippy | Mode::Wasm) {
+            return Ok(());
+        }
+        let tests = matches!(self.mode, Mode::Test | Mode::Async);
+        let cmd = match self.cargo_dirs()? {
+            Some((manifest, target_dir)) => {
+                let mut command = Command::new("cargo");
+                command.arg("check");
+                if tests {
+                    command.args(["--profile", "test"]);
+                }
+                command
+                    .args(["--quiet", "--bin", &self.name, "--manifest-path"])
+                    .arg(manifest)
+                    .arg("--target-dir")
+                    .arg(target_dir)
+                    .args(cargo_diagnostic_args())
+                    .output()
+            }
+            None => {
+                let mut command = Command::new("rustc");
+                if tests {
+                    command.arg("--test");
+                }
+                command
+                    .arg(self.main_file())
+                    .args(["--emit=metadata", "-o", &temp_file()])
+                    .args(rustc_diagnostic_args())
+                    .args(RUSTC_EDITION_ARGS)
+                    .output()
+            }
+        }
+        .expect("Failed to run 'check' command.");
+        clean();
+
+        if cmd.status.success() {
+            Ok(())
+        } else {
+            Err(self.relative_to_course(ExerciseOutput {
+                stdout: String::from_utf8_lossy(&cmd.stdout).to_string(),
+                stderr: String::from_utf8_lossy(&cmd.stderr).to_string(),
+            }))
+        }
+    }
+
+    // Whether `compile` builds the exercise through Cargo instead of rustc.
+    // That's the case with the workspace backend and for exercises that use
+    // crates, which async exercises always do.
+    fn builds_with_cargo(&self) -> bool {
+        let needs_cargo = workspace::is_enabled()
+            || !self.dependencies.is_empty()
+            || matches!(self.mode, Mode::Async);
+        needs_cargo && !matches!(self.mode, Mode::Clippy | Mode::Wasm)
+    }
+
+    // The manifest and the target directory to build the exercise with, if
+    // it's built with Cargo. Outside of the workspace backend, the manifest
+    // with the crates of the exercise is written here.
+    fn cargo_dirs(&self) -> Result<Option<(PathBuf, PathBuf)>, ExerciseOutput> {
+        if !self.builds_with_cargo() {
+            return Ok(None);
+        }
+        if workspace::is_enabled() {
+            return Ok(Some((workspace::manifest_path(), cache::target_dir())));
+        }
+        let manifest = Path::new(DEPENDENCIES_DIR)
+            .join(&self.name)
+            .join("Cargo.toml");
+        let written = self.cargo_toml().and_then(|cargo_toml| {
+            fs::create_dir_all(manifest.parent().unwrap())
+                .and_then(|_| fs::write(&manifest, cargo_toml))
+                .map_err(|e| format!("Failed to write {}: {e}", manifest.display()))
+        });
+        if let Err(e) = written {
+            return Err(ExerciseOutput {
+                stdout: String::new(),
+                stderr: format!("error: {e}\n"),
+            });
+        }
+        Ok(Some((manifest, cache::target_dir())))
+    }
+
+    // Cargo shows the files relative to the workspace manifest, but learners
+    // know them relative to the course
+    fn relative_to_course(&self, output: ExerciseOutput) -> ExerciseOutput {
+        if !workspace::is_enabled() {
+            return output;
+        }
+        let path = self.path.display().to_string();
+        let relative = Path::new("../..").join(&self.path).display().to_string();
+        ExerciseOutput {
+            stdout: output.stdout.replace(&relative, &path),
+            stderr: output.stderr.replace(&relative, &path),
+        }
+    }
+
+    // Build the binary of the exercise in the given manifest with Cargo, for
+    // exercises that use crates or with the workspace backend. The binary
+    // Cargo produced is then copied to where `run` expects it.
+    fn compile_with_cargo(
+        &self,
+        manifest: &Path,
+        target_dir: &Path,
+        target_args: &[&str],
+    ) -> Result<CompiledExercise<'_>, ExerciseOutput> {
+        let error = |message: String| ExerciseOutput {
+            stdout: String::new(),
+            stderr: format!("error: {message}\n"),
+        };
+        // Cargo reports what it built as JSON on stdout, and renders the
+        // diagnostics to stderr unless they're wanted as JSON as well
+        let json = JSON_DIAGNOSTICS.load(Ordering::Relaxed);
+        let mut command = Command::new("cargo");
+        match self.mode {
+            Mode::Test | Mode::Async => command.args(["test", "--no-run"]),
+            _ => command.arg("build"),
+        };
+        let cmd = command
+            .args(["--quiet", "--bin", &self.name, "--manifest-path"])
+            .arg(manifest)
+            .arg("--target-dir")
+            .arg(target_dir)
+            .arg(if json {
+                "--message-format=json"
+            } else {
+                "--message-format=json-render-diagnostics"
+            })
+            .args(color_args())
+            .args(target_args)
+            .output()
+            .expect("Failed to run 'cargo'");
+        let stdout = String::from_utf8_lossy(&cmd.stdout);
+        if !cmd.status.success() {
+            return Err(ExerciseOutput {
+                stdout: if json {
+                    stdout.to_string()
+                } else {
+                    String::new()
+                },
+                stderr: String::from_utf8_lossy(&cmd.stderr).to_string(),
+            });
+        }
+
+        let executable = stdout
+            .lines()
+            .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+            .filter(|message| message["target"]["name"] == self.name.as_str())
+            .find_map(|message| message["executable"].as_str().map(PathBuf::from))
+            .ok_or_else(|| error(format!("Cargo didn't build a binary for {self}")))?;
+        fs::copy(&executable, temp_file()).map_err(|e| {
+            error(format!(
+                "Failed to copy {}: {e}",
+                executable.to_string_lossy()
+            ))
+        })?;
+        Ok(CompiledExercise {
+            exercise: self,
+            _handle: FileHandle,
+        })
+    }
+
+    // The Cargo.toml that builds the exercise as a binary, or as a test
+    // harness for test exercises, along with its dependencies
+    fn cargo_toml(&self) -> Result<String, String> {
+        let path =
+            fs::canonicalize(self.main_file()).expect("We were unable to find the exercise file!");
+        Ok(format!(
+            r#"[package]
+name = "{}"
+version = "0.0.1"
+edition = "{}"
+
+[workspace]
+
+[[bin]]
+name = "{}"
+path = '{}'
+
+{}"#,
+            self.name,
+            RUSTC_EDITION_ARGS[1],
+            self.name,
+            path.display(),
+            self.dependencies_toml()?
+        ))
+    }
+
+    // The dependencies of the exercise as the `[dependencies]` table of a
+    // Cargo.toml
+    fn dependencies_toml(&self) -> Result<String, String> {
+        let mut manifest = toml::Table::new();
+        manifest.insert(
+            "dependencies".into(),
+            toml::Value::Table(self.dependency_table()?),
+        );
+        toml::to_string(&manifest).map_err(|e| e.to_string())
+    }
+
+    // The crates the exercise depends on by name. The paths of path
+    // dependencies are relative to the course, so they're made absolute for
+    // the generated manifests. Async exercises depend on Tokio by default.
+    pub fn dependency_table(&self) -> Result<toml::Table, String> {
+        let mut dependencies = toml::Table::new();
+        for line in &self.dependencies {
+            let mut table: toml::Table = line.parse().map_err(|e| {
+                format!(
+                    "The dependency `{line}` of {} isn't valid TOML: {e}",
+                    self.name
+                )
+            })?;
+            for (_, spec) in table.iter_mut() {
+                if let Some(toml::Value::String(path)) =
+                    spec.as_table_mut().and_then(|spec| spec.get_mut("path"))
+                {
+                    if let Ok(absolute) = fs::canonicalize(&*path) {
+                        *path = absolute.to_string_lossy().to_string();
+                    }
+                }
+            }
+            dependencies.extend(table);
+        }
+        if matches!(self.mode, Mode::Async) && !dependencies.contains_key("tokio") {
+            dependencies.extend(ASYNC_RUNTIME.parse::<toml::Table>().unwrap());
+        }
+        Ok(dependencies)
+    }
+
+    // Lint the exercise with Clippy, failing on any warning.
+    // Every exercise gets its own generated Cargo.toml, while the build
+    // cache is shared so dependencies of the lints only build once.
+    pub fn lint(&self) -> Result<(), ExerciseOutput> {
+        let dir = Path::new(LINT_DIR).join(&self.name);
+        let path =
+            fs::canonicalize(self.main_file()).expect("We were unable to find the exercise file!");
+        // Test exercises usually have no `main` function, so they're linted as a library
+        let (target, target_args) = match self.mode {
+            Mode::Test | Mode::Async => ("[lib]".to_string(), &["--tests"][..]),
+            _ => (format!("[[bin]]\nname = \"{}\"", self.name), &[][..]),
         };
-        let cmd = Command::new(temp_file())
-            .arg(arg)
+        let cargo_toml = format!(
+            r#"[package]
+name = "{}"
+version = "0.0.1"
+edition = "{}"
+
+[workspace]
+
+{target}
+path = '{}'
+
+{}"#,
+            self.name,
+            RUSTC_EDITION_ARGS[1],
+            path.display(),
+            self.dependencies_toml().map_err(|e| ExerciseOutput {
+                stdout: String::new(),
+                stderr: format!("error: {e}\n"),
+            })?
+        );
+        fs::create_dir_all(&dir)
+            .and_then(|_| fs::write(dir.join("Cargo.toml"), cargo_toml))
+            .expect("Failed to write the Cargo.toml file for Clippy.");
+
+        let cmd = Command::new("cargo")
+            .arg("clippy")
+            .arg("--manifest-path")
+            .arg(dir.join("Cargo.toml"))
+            .arg("--target-dir")
+            .arg(cache::target_dir())
+            .args(cargo_diagnostic_args())
+            .args(target_args)
+            .args(["--", "-D", "warnings"])
+            .output()
+            .expect("Failed to run 'cargo clippy'");
+
+        if cmd.status.success() {
+            Ok(())
+        } else {
+            Err(ExerciseOutput {
+                stdout: String::from_utf8_lossy(&cmd.stdout).to_string(),
+                stderr: String::from_utf8_lossy(&cmd.stderr).to_string(),
+            })
+        }
+    }
+
+    // Build the exercise with debug info to the given path, for running it
+    // in a debugger. Exercises that are built through Cargo already come
+    // with debug info, so their binary is only copied there.
+    pub fn compile_for_debugging(&self, output: &Path) -> Result<(), ExerciseOutput> {
+        if let Some(dir) = output.parent() {
+            fs::create_dir_all(dir).expect("Failed to create the directory for the debug build.");
+        }
+        if self.builds_with_cargo() {
+            let compiled = self.compile()?;
+            fs::copy(temp_file(), output).map_err(|e| ExerciseOutput {
+                stdout: String::new(),
+                stderr: format!("error: Failed to copy the binary of {self}: {e}\n"),
+            })?;
+            drop(compiled);
+            return Ok(());
+        }
+        let mut command = Command::new("rustc");
+        if matches!(self.mode, Mode::Test) {
+            command.arg("--test");
+        }
+        let cmd = command
+            .arg(self.main_file())
+            .arg("-o")
+            .arg(output)
+            .args(["-g", "-C", "opt-level=0"])
+            .args(rustc_diagnostic_args())
+            .args(RUSTC_EDITION_ARGS)
+            .output()
+            .expect("Failed to run 'rustc'");
+        if cmd.status.success() {
+            Ok(())
+        } else {
+            Err(ExerciseOutput {
+                stdout: String::from_utf8_lossy(&cmd.stdout).to_string(),
+                stderr: String::from_utf8_lossy(&cmd.stderr).to_string(),
+            })
+        }
+    }
+
+    // Check with rustfmt that the exercise is formatted.
+    // On failure, the output holds the diff that rustfmt would apply.
+    pub fn check_formatting(&self) -> Result<(), ExerciseOutput> {
+        let cmd = Command::new("rustfmt")
+            .arg("--check")
+            .arg(self.main_file())
+            .args(color_args())
+            .args(RUSTC_EDITION_ARGS)
             .output()
-            .expect("Failed to run 'run' command");
+            .expect("Failed to run 'rustfmt'");
+
+        if cmd.status.success() {
+            Ok(())
+        } else {
+            Err(ExerciseOutput {
+                stdout: String::from_utf8_lossy(&cmd.stdout).to_string(),
+                stderr: String::from_utf8_lossy(&cmd.stderr).to_string(),
+            })
+        }
+    }
 
-        let output = ExerciseOutput {
+    fn run(&self, input: &RunInput, timeout: Duration) -> Result<ExerciseOutput, ExerciseOutput> {
+        let mut command = match self.mode {
+            // Without a runtime, building the module is all there is to it
+            Mode::Wasm if !wasm_runtime_installed() => {
+                return Ok(ExerciseOutput {
+                    stdout: String::new(),
+                    stderr: String::new(),
+                })
+            }
+            // The module has no WASI entry point, so its `main` is invoked
+            // with no command line arguments
+            Mode::Wasm => {
+                let mut command = Command::new("wasmtime");
+                command
+                    .args(["run", "--invoke", "main"])
+                    .arg(temp_file())
+                    .args(["0", "0"]);
+                command
+            }
+            _ => Command::new(temp_file()),
+        };
+        if input.nocapture && matches!(self.mode, Mode::Test | Mode::Async) {
+            command.arg("--nocapture");
+        }
+        // Arguments like a test name filter come on top of showing the output
+        if matches!(self.mode, Mode::Test | Mode::Async) {
+            command.arg("--show-output");
+        }
+        if !matches!(self.mode, Mode::Wasm) {
+            command.args(input.args);
+        }
+        if let Some(stdin) = input.stdin {
+            let file = File::open(stdin).map_err(|e| ExerciseOutput {
+                stdout: String::new(),
+                stderr: format!("Failed to open {}: {e}", stdin.display()),
+            })?;
+            command.stdin(file);
+        } else {
+            command.stdin(Stdio::null());
+        }
+        let (cmd, timed_out) =
+            output_with_timeout(&mut command, timeout).expect("Failed to run 'run' command");
+
+        let mut output = ExerciseOutput {
             stdout: String::from_utf8_lossy(&cmd.stdout).to_string(),
             stderr: String::from_utf8_lossy(&cmd.stderr).to_string(),
         };
+        if timed_out {
+            output.stderr.push_str(&format!(
+                "error: {self} timed out after {}s, do you have an infinite loop?\n",
+                timeout.as_secs()
+            ));
+            return Err(output);
+        }
 
         if cmd.status.success() {
             Ok(output)
@@ -201,28 +816,16 @@ path = "{}.rs""#,
     }
 
     pub fn state(&self) -> State {
-        let mut source_file =
-            File::open(&self.path).expect("We were unable to open the exercise file!");
-
-        let source = {
-            let mut s = String::new();
-            source_file
-                .read_to_string(&mut s)
-                .expect("We were unable to read the exercise file!");
-            s
-        };
-
         let re = Regex::new(I_AM_DONE_REGEX).unwrap();
-
-        if !re.is_match(&source) {
+        // The comment can be in any file of an exercise made of several files
+        let Some((source, matched_line_index)) = self.source_files().iter().find_map(|path| {
+            let source =
+                fs::read_to_string(path).expect("We were unable to read the exercise file!");
+            let index = source.lines().position(|line| re.is_match(line))?;
+            Some((source, index))
+        }) else {
             return State::Done;
-        }
-
-        let matched_line_index = source
-            .lines()
-            .enumerate()
-            .find_map(|(i, line)| if re.is_match(line) { Some(i) } else { None })
-            .expect("This should not happen at all");
+        };
 
         let min_line = ((matched_line_index as i32) - (CONTEXT as i32)).max(0) as usize;
         let max_line = matched_line_index + CONTEXT;
@@ -241,6 +844,91 @@ path = "{}.rs""#,
         State::Pending(context)
     }
 
+    pub fn metadata(&self) -> ExerciseMetadata<'_> {
+        let section = self
+            .path
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty() && !dir.ends_with("exercises"))
+            .and_then(|dir| dir.file_name())
+            .map(|dir| dir.to_string_lossy().to_string());
+
+        ExerciseMetadata {
+            name: &self.name,
+            path: &self.path,
+            mode: self.mode,
+            edition: RUSTC_EDITION_ARGS[1],
+            hints: self.hints().len(),
+            section,
+            topics: &self.topics,
+            difficulty: self.difficulty,
+            estimated_minutes: self.estimated_minutes(),
+        }
+    }
+
+    // How many minutes solving the exercise likely takes: the estimate of the
+    // course, or else a guess from its difficulty
+    pub fn estimated_minutes(&self) -> u32 {
+        self.estimated_minutes.unwrap_or(match self.difficulty {
+            Some(Difficulty::Easy) => 5,
+            Some(Difficulty::Medium) | None => 10,
+            Some(Difficulty::Hard) => 20,
+        })
+    }
+
+    // The file that's passed to rustc: the exercise itself, or the `main.rs`
+    // of an exercise made of several files in a directory
+    pub fn main_file(&self) -> PathBuf {
+        if self.path.is_dir() {
+            self.path.join("main.rs")
+        } else {
+            self.path.clone()
+        }
+    }
+
+    // All source files of the exercise, starting with the main file
+    pub fn source_files(&self) -> Vec<PathBuf> {
+        let main = self.main_file();
+        let mut files = if self.path.is_dir() {
+            rust_files(&self.path)
+        } else {
+            Vec::new()
+        };
+        files.retain(|file| *file != main);
+        files.insert(0, main);
+        files
+    }
+
+    // Whether the file is the exercise or one of its files
+    pub fn contains_file(&self, file: &Path) -> bool {
+        file.ancestors().any(|path| path.ends_with(&self.path))
+    }
+
+    // Whether the exercise is tagged with the given topic, ignoring case
+    pub fn has_topic(&self, topic: &str) -> bool {
+        self.topics.iter().any(|t| t.eq_ignore_ascii_case(topic))
+    }
+
+    // All hint levels of the exercise, from the gentlest to the most revealing
+    pub fn hints(&self) -> Vec<&str> {
+        if self.hints.is_empty() {
+            Some(self.hint.as_str())
+                .filter(|hint| !hint.trim().is_empty())
+                .into_iter()
+                .collect()
+        } else {
+            self.hints.iter().map(String::as_str).collect()
+        }
+    }
+
+    // The path of the reference solution for this exercise.
+    // Solutions mirror the layout of the `exercises` directory inside `solutions`.
+    pub fn solution_path(&self) -> PathBuf {
+        match self.path.strip_prefix("exercises") {
+            Ok(relative) => PathBuf::from("solutions").join(relative),
+            Err(_) => PathBuf::from("solutions").join(&self.path),
+        }
+    }
+
     // Check that the exercise looks to be solved using self.state()
     // This is not the best way to check since
     // the user can just remove the "I AM NOT DONE" string from the file
@@ -252,12 +940,75 @@ path = "{}.rs""#,
     }
 }
 
+// Whether wasmtime is installed to run WebAssembly exercises with
+pub fn wasm_runtime_installed() -> bool {
+    Command::new("wasmtime")
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+// The Rust files in the directory and its subdirectories, sorted by path
+pub fn rust_files(dir: &Path) -> Vec<PathBuf> {
+    let pattern = dir.join("**").join("*.rs");
+    let mut files: Vec<PathBuf> = glob::glob(&pattern.to_string_lossy())
+        .map(|paths| paths.filter_map(Result::ok).collect())
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
 impl Display for Exercise {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         write!(f, "{}", self.path.to_str().unwrap())
     }
 }
 
+// Run the command like `Command::output`, but kill it once it runs longer than
+// the timeout. Returns the output along with whether the command was killed.
+fn output_with_timeout(command: &mut Command, timeout: Duration) -> io::Result<(Output, bool)> {
+    let mut child = command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // Read the pipes while waiting, so the child doesn't block on a full pipe
+    fn read_to_end(pipe: Option<impl Read + Send + 'static>) -> thread::JoinHandle<Vec<u8>> {
+        thread::spawn(move || {
+            let mut contents = Vec::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut contents);
+            }
+            contents
+        })
+    }
+    let stdout = read_to_end(child.stdout.take());
+    let stderr = read_to_end(child.stderr.take());
+
+    let deadline = Instant::now() + timeout;
+    let mut timed_out = false;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            timed_out = true;
+            let _ = child.kill();
+            break child.wait()?;
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+
+    let output = Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    };
+    Ok((output, timed_out))
+}
+
 #[inline]
 fn clean() {
     let _ignored = remove_file(temp_file());
@@ -266,17 +1017,11 @@ fn clean() {
 #[cfg(test)]
 mod test {
     use super::*;
-    use std::path::Path;
 
     #[test]
     fn test_clean() {
         File::create(temp_file()).unwrap();
-        let exercise = Exercise {
-            name: String::from("example"),
-            path: PathBuf::from("tests/fixture/state/pending_exercise.rs"),
-            mode: Mode::Compile,
-            hint: String::from(""),
-        };
+        let exercise = Exercise::new("example", "tests/fixture/state/pending_exercise.rs");
         let compiled = exercise.compile().unwrap();
         drop(compiled);
         assert!(!Path::new(&temp_file()).exists());
@@ -284,12 +1029,10 @@ mod test {
 
     #[test]
     fn test_pending_state() {
-        let exercise = Exercise {
-            name: "pending_exercise".into(),
-            path: PathBuf::from("tests/fixture/state/pending_exercise.rs"),
-            mode: Mode::Compile,
-            hint: String::new(),
-        };
+        let exercise = Exercise::new(
+            "pending_exercise",
+            "tests/fixture/state/pending_exercise.rs",
+        );
 
         let state = exercise.state();
         let expected = vec![
@@ -325,25 +1068,164 @@ mod test {
 
     #[test]
     fn test_finished_exercise() {
+        let exercise = Exercise::new(
+            "finished_exercise",
+            "tests/fixture/state/finished_exercise.rs",
+        );
+
+        assert_eq!(exercise.state(), State::Done);
+    }
+
+    #[test]
+    fn test_solution_path() {
+        let exercise = Exercise::new("intro1", "exercises/intro/intro1.rs");
+
+        assert_eq!(
+            exercise.solution_path(),
+            PathBuf::from("solutions/intro/intro1.rs")
+        );
+    }
+
+    #[test]
+    fn test_metadata_section() {
         let exercise = Exercise {
-            name: "finished_exercise".into(),
-            path: PathBuf::from("tests/fixture/state/finished_exercise.rs"),
-            mode: Mode::Compile,
-            hint: String::new(),
+            hint: String::from("Remove the comment"),
+            ..Exercise::new("intro1", "exercises/intro/intro1.rs")
+        };
+        let quiz = Exercise {
+            mode: Mode::Test,
+            ..Exercise::new("quiz1", "exercises/quiz1.rs")
         };
 
-        assert_eq!(exercise.state(), State::Done);
+        assert_eq!(exercise.metadata().section.as_deref(), Some("intro"));
+        assert_eq!(exercise.metadata().hints, 1);
+        assert_eq!(quiz.metadata().section, None);
+        assert_eq!(quiz.metadata().hints, 0);
+    }
+
+    #[test]
+    fn test_hint_levels() {
+        let mut exercise = Exercise {
+            hint: String::from("The only hint"),
+            ..Exercise::new("intro1", "exercises/intro/intro1.rs")
+        };
+        assert_eq!(exercise.hints(), vec!["The only hint"]);
+
+        exercise.hints = vec!["First".into(), "Second".into()];
+        assert_eq!(exercise.hints(), vec!["First", "Second"]);
+        assert_eq!(exercise.metadata().hints, 2);
     }
 
     #[test]
     fn test_exercise_with_output() {
         let exercise = Exercise {
-            name: "exercise_with_output".into(),
-            path: PathBuf::from("tests/fixture/success/testSuccess.rs"),
             mode: Mode::Test,
-            hint: String::new(),
+            ..Exercise::new(
+                "exercise_with_output",
+                "tests/fixture/success/testSuccess.rs",
+            )
         };
-        let out = exercise.compile().unwrap().run().unwrap();
+        let out = exercise
+            .compile()
+            .unwrap()
+            .run(Duration::from_secs(30))
+            .unwrap();
         assert!(out.stdout.contains("THIS TEST TOO SHALL PASS"));
     }
+
+    #[test]
+    fn test_check() {
+        let exercise = |path: &str, mode| Exercise {
+            mode,
+            ..Exercise::new("check", path)
+        };
+        assert!(exercise("tests/fixture/success/testSuccess.rs", Mode::Test)
+            .check()
+            .is_ok());
+        let error = exercise("tests/fixture/failure/compFailure.rs", Mode::Compile)
+            .check()
+            .unwrap_err();
+        assert!(error.stderr.contains("expected pattern"));
+    }
+
+    #[test]
+    fn test_exercise_of_several_files() {
+        let exercise = Exercise::new("multiFile", "tests/fixture/success/multiFile");
+        assert_eq!(
+            exercise.source_files(),
+            [
+                PathBuf::from("tests/fixture/success/multiFile/main.rs"),
+                PathBuf::from("tests/fixture/success/multiFile/greeting.rs"),
+            ]
+        );
+        assert!(exercise.contains_file(Path::new(
+            "/home/ferris/rustlings/tests/fixture/success/multiFile/greeting.rs"
+        )));
+        assert!(!exercise.contains_file(Path::new("tests/fixture/success/compSuccess.rs")));
+        let out = exercise
+            .compile()
+            .unwrap()
+            .run(Duration::from_secs(30))
+            .unwrap();
+        assert!(out.stdout.contains("Hello from another file!"));
+    }
+
+    #[test]
+    fn test_exercise_with_dependencies() {
+        let mut exercise = Exercise {
+            dependencies: vec![
+                r#"greeter = { path = "tests/fixture/dependencies/greeter" }"#.into(),
+            ],
+            ..Exercise::new("useGreeter", "tests/fixture/dependencies/useGreeter.rs")
+        };
+        let out = exercise
+            .compile()
+            .unwrap()
+            .run(Duration::from_secs(30))
+            .unwrap();
+        assert!(out.stdout.contains("Hello from a dependency!"));
+
+        exercise.mode = Mode::Test;
+        let out = exercise
+            .compile()
+            .unwrap()
+            .run(Duration::from_secs(30))
+            .unwrap();
+        assert!(out.stdout.contains("test greets ... ok"));
+
+        exercise.dependencies = vec!["greeter = ".into()];
+        let out = exercise.compile().err().unwrap();
+        assert!(out.stderr.contains("isn't valid TOML"));
+    }
+
+    #[test]
+    fn test_wasm_exercise() {
+        let exercise = Exercise {
+            mode: Mode::Wasm,
+            ..Exercise::new("compSuccess", "tests/fixture/success/compSuccess.rs")
+        };
+        match run::target_installed(WASM_TARGET) {
+            Some(false) => {
+                let out = exercise.compile().err().unwrap();
+                assert!(out
+                    .stderr
+                    .contains("rustup target add wasm32-unknown-unknown"));
+            }
+            _ => assert!(exercise.compile().is_ok()),
+        }
+    }
+
+    #[test]
+    fn test_async_exercise_depends_on_tokio() {
+        let mut exercise = Exercise {
+            mode: Mode::Async,
+            ..Exercise::new("async1", "exercises/async/async1.rs")
+        };
+        let dependencies = exercise.dependency_table().unwrap();
+        assert_eq!(dependencies["tokio"]["version"].as_str(), Some("1"));
+
+        exercise.dependencies = vec![r#"tokio = { version = "1.30", features = ["full"] }"#.into()];
+        let dependencies = exercise.dependency_table().unwrap();
+        assert_eq!(dependencies["tokio"]["version"].as_str(), Some("1.30"));
+    }
 }
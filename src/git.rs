@@ -0,0 +1,79 @@
+// Committing exercises once they're solved, which gives learners a history
+// of their solutions and instructors a timeline they can verify.
+use crate::exercise::Exercise;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// Commit the exercise to the git repository it's in, with a message like
+// "solve intro2". Problems are reported, but don't stop Rustlings.
+pub fn commit_solution(exercise: &Exercise) {
+    let path = exercise.path.to_string_lossy();
+    let message = format!("solve {}", exercise.name);
+    let result = git(&["add", "--", &path]).and_then(|_| {
+        // Nothing to commit if the solution is already in the repository
+        if git(&["diff", "--cached", "--quiet", "--", &path]).is_ok() {
+            return Ok(false);
+        }
+        git(&["commit", "--quiet", "-m", &message, "--", &path]).map(|_| true)
+    });
+    match result {
+        Ok(true) => println!("Committed {exercise} to git: \"{message}\""),
+        Ok(false) => {}
+        Err(e) => {
+            warn!("Couldn't commit {} to git", exercise);
+            println!("{e}");
+        }
+    }
+}
+
+// The file as it was last committed, which is what `git stash` brings back
+pub fn committed_version(path: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .arg("show")
+        .arg(format!(
+            "HEAD:./{}",
+            path.to_str()?.trim_start_matches("./")
+        ))
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
+
+// The files at the path as they were last committed, along with their
+// contents. For an exercise made of several files, those are all its files.
+pub fn committed_files(path: &Path) -> Vec<(PathBuf, String)> {
+    let Ok(output) = Command::new("git")
+        .args(["ls-tree", "-r", "--name-only", "HEAD", "--"])
+        .arg(path)
+        .output()
+    else {
+        return Vec::new();
+    };
+    if !output.status.success() {
+        return Vec::new();
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|file| {
+            let file = PathBuf::from(file);
+            let contents = committed_version(&file)?;
+            Some((file, contents))
+        })
+        .collect()
+}
+
+// Run git, returning what it printed to stderr if it failed
+fn git(args: &[&str]) -> Result<(), String> {
+    let output = Command::new("git")
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run git: {e}"))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
@@ -0,0 +1,136 @@
+// Shell commands that run on events, as configured in the `[hooks]` table of
+// `rustlings.toml`. Learners use them to play a sound when an exercise is
+// solved, or to log their progress to their own tools. The commands learn
+// about the event from the environment variables `RUSTLINGS_EVENT`,
+// `RUSTLINGS_EXERCISE` and `RUSTLINGS_EXERCISE_PATH`.
+use crate::exercise::Exercise;
+use serde::Deserialize;
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+use std::thread;
+
+#[derive(Deserialize, Default, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Hooks {
+    // When an exercise is solved for the first time since it last failed
+    pub solved: Option<String>,
+    // When an exercise doesn't compile or its tests fail
+    pub failed: Option<String>,
+    // When all exercises of the course are done
+    pub all_done: Option<String>,
+    // When watch mode starts
+    pub watch_started: Option<String>,
+}
+
+impl Hooks {
+    // Use the hooks of `self`, falling back to those of `other`
+    pub fn or(self, other: Hooks) -> Hooks {
+        Hooks {
+            solved: self.solved.or(other.solved),
+            failed: self.failed.or(other.failed),
+            all_done: self.all_done.or(other.all_done),
+            watch_started: self.watch_started.or(other.watch_started),
+        }
+    }
+
+    fn command(&self, event: Event) -> Option<&str> {
+        match event {
+            Event::Solved => self.solved.as_deref(),
+            Event::Failed => self.failed.as_deref(),
+            Event::AllDone => self.all_done.as_deref(),
+            Event::WatchStarted => self.watch_started.as_deref(),
+        }
+        .filter(|command| !command.trim().is_empty())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Event {
+    Solved,
+    Failed,
+    AllDone,
+    WatchStarted,
+}
+
+impl Event {
+    // The name of the event, as in the config
+    fn name(self) -> &'static str {
+        match self {
+            Event::Solved => "solved",
+            Event::Failed => "failed",
+            Event::AllDone => "all_done",
+            Event::WatchStarted => "watch_started",
+        }
+    }
+}
+
+static HOOKS: OnceLock<Hooks> = OnceLock::new();
+
+// Run the given hooks on events from now on
+pub fn enable(hooks: Hooks) {
+    let _ = HOOKS.set(hooks);
+}
+
+// Run the hook of the event, if there is one. It runs in the background, so
+// a slow hook doesn't hold up checking the exercises, and one that fails is
+// only reported.
+pub fn fire(event: Event, exercise: Option<&Exercise>) {
+    let Some(command) = HOOKS.get().and_then(|hooks| hooks.command(event)) else {
+        return;
+    };
+    let mut shell = shell(command);
+    shell.env("RUSTLINGS_EVENT", event.name());
+    if let Some(exercise) = exercise {
+        shell
+            .env("RUSTLINGS_EXERCISE", &exercise.name)
+            .env("RUSTLINGS_EXERCISE_PATH", &exercise.path);
+    }
+    let command = command.to_string();
+    match shell.stdin(Stdio::null()).spawn() {
+        Ok(mut child) => {
+            thread::spawn(move || match child.wait() {
+                Ok(status) if !status.success() => {
+                    println!("The {} hook `{command}` failed with {status}", event.name());
+                }
+                _ => {}
+            });
+        }
+        Err(e) => println!("Couldn't run the {} hook `{command}`: {e}", event.name()),
+    }
+}
+
+fn shell(command: &str) -> Command {
+    if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.args(["/C", command]);
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.args(["-c", command]);
+        shell
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_hooks() {
+        let local = Hooks {
+            solved: Some("paplay done.oga".to_string()),
+            failed: Some(" ".to_string()),
+            ..Hooks::default()
+        };
+        let user = Hooks {
+            solved: Some("echo solved".to_string()),
+            all_done: Some("echo done".to_string()),
+            ..Hooks::default()
+        };
+        let hooks = local.or(user);
+        assert_eq!(hooks.command(Event::Solved), Some("paplay done.oga"));
+        assert_eq!(hooks.command(Event::Failed), None);
+        assert_eq!(hooks.command(Event::AllDone), Some("echo done"));
+        assert_eq!(hooks.command(Event::WatchStarted), None);
+    }
+}
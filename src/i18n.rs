@@ -0,0 +1,130 @@
+// The messages of Rustlings in the languages it has been translated to.
+//
+// Every language has a catalog in `src/locales` that maps message ids to
+// their text. Messages missing from a translation are shown in English.
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+// The language codes and catalogs of the available languages
+const CATALOGS: &[(&str, &str)] = &[
+    ("en", include_str!("locales/en.toml")),
+    ("de", include_str!("locales/de.toml")),
+];
+
+type Catalog = HashMap<String, String>;
+
+static ENGLISH: OnceLock<Catalog> = OnceLock::new();
+static SELECTED: OnceLock<Catalog> = OnceLock::new();
+
+// Show messages in the given language from now on, e.g. `de` or `de_DE.UTF-8`.
+// Returns false if there's no translation into that language.
+pub fn set_language(lang: &str) -> bool {
+    let lang = normalize(lang);
+    let Some((_, catalog)) = CATALOGS.iter().find(|(code, _)| *code == lang) else {
+        return false;
+    };
+    let _ = SELECTED.set(parse(catalog));
+    true
+}
+
+// The codes of the languages Rustlings has been translated to
+pub fn languages() -> impl Iterator<Item = &'static str> {
+    CATALOGS.iter().map(|(code, _)| *code)
+}
+
+// The text of a message in the selected language
+pub fn message(id: &str) -> &'static str {
+    SELECTED
+        .get()
+        .and_then(|catalog| catalog.get(id))
+        .or_else(|| english().get(id))
+        .map(String::as_str)
+        .unwrap_or_else(|| panic!("There's no message with the id '{id}'"))
+}
+
+// The text of a message with its placeholders, like `{exercise}`, filled in
+pub fn format_message(id: &str, args: &[(&str, &dyn Display)]) -> String {
+    fill_in(message(id), args)
+}
+
+fn fill_in(text: &str, args: &[(&str, &dyn Display)]) -> String {
+    args.iter().fold(text.to_string(), |text, (name, value)| {
+        text.replace(&format!("{{{name}}}"), &value.to_string())
+    })
+}
+
+fn english() -> &'static Catalog {
+    ENGLISH.get_or_init(|| parse(CATALOGS[0].1))
+}
+
+fn parse(catalog: &str) -> Catalog {
+    toml::from_str(catalog).expect("Message catalogs should be valid")
+}
+
+// The language part of a locale, e.g. `pt` for `pt_BR.UTF-8`
+fn normalize(lang: &str) -> String {
+    lang.split(['_', '-', '.'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+// The text of a message in the selected language. Placeholders are filled in
+// from the given arguments, e.g. `tr!("watch-skipped", exercise = name)`.
+macro_rules! tr {
+    ($id:literal) => {
+        $crate::i18n::message($id)
+    };
+    ($id:literal, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::format_message(
+            $id,
+            &[$((stringify!($name), &$value as &dyn std::fmt::Display)),+],
+        )
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use regex::Regex;
+
+    fn placeholders(text: &str) -> Vec<&str> {
+        let placeholder = Regex::new(r"\{[a-z_]+\}").unwrap();
+        let mut found: Vec<&str> = placeholder.find_iter(text).map(|m| m.as_str()).collect();
+        found.sort_unstable();
+        found
+    }
+
+    #[test]
+    fn test_translations_match_english() {
+        let english = english();
+        for (code, catalog) in &CATALOGS[1..] {
+            for (id, text) in parse(catalog) {
+                let original = english
+                    .get(&id)
+                    .unwrap_or_else(|| panic!("{code}: '{id}' isn't an English message"));
+                assert_eq!(
+                    placeholders(&text),
+                    placeholders(original),
+                    "{code}: '{id}' has different placeholders"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_fill_in_placeholders() {
+        assert_eq!(
+            fill_in("Hint {level} of {count}:", &[("level", &1), ("count", &3)]),
+            "Hint 1 of 3:"
+        );
+    }
+
+    #[test]
+    fn test_normalize_language() {
+        assert_eq!(normalize("de"), "de");
+        assert_eq!(normalize("pt_BR.UTF-8"), "pt");
+        assert_eq!(normalize("zh-Hans"), "zh");
+    }
+}
@@ -0,0 +1,116 @@
+// Everything Rustlings knows about a single exercise, in one place
+use crate::chapter::chapter_of;
+use crate::exercise::Exercise;
+use crate::notes::{notes_path, read_notes};
+use crate::plan::format_minutes;
+use crate::state::ProgressState;
+use console::style;
+
+pub fn info(exercise: &Exercise, exercises: &[Exercise]) {
+    let state = ProgressState::load();
+    let fields = fields(exercise, exercises, &state);
+    let width = fields.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    for (name, value) in fields {
+        println!("{}  {value}", style(format!("{name:<width$}")).bold());
+    }
+}
+
+// The name and value of every line that's printed about the exercise
+fn fields(
+    exercise: &Exercise,
+    exercises: &[Exercise],
+    state: &ProgressState,
+) -> Vec<(&'static str, String)> {
+    let progress = state.exercises.get(&exercise.name);
+    let status = state.status(exercise);
+    let or_none = |values: &[String]| {
+        if values.is_empty() {
+            "none".to_string()
+        } else {
+            values.join(", ")
+        }
+    };
+    let hints = exercise.hints().len();
+    let solution = exercise.solution_path();
+
+    let mut fields = vec![
+        ("Name", exercise.name.clone()),
+        ("Path", exercise.path.display().to_string()),
+        ("Mode", exercise.mode.to_string()),
+        (
+            "Chapter",
+            chapter_of(exercise, exercises).map_or("none".to_string(), |chapter| {
+                format!("{} ({})", chapter.title(), chapter.number)
+            }),
+        ),
+        ("Topics", or_none(&exercise.topics)),
+        (
+            "Difficulty",
+            exercise
+                .difficulty
+                .map_or("unknown".to_string(), |difficulty| difficulty.to_string()),
+        ),
+        ("Estimate", format_minutes(exercise.estimated_minutes())),
+        ("Requires", or_none(&exercise.requires)),
+        ("Status", status.to_string()),
+        (
+            "Attempts",
+            progress.map_or(0, |progress| progress.attempts).to_string(),
+        ),
+        (
+            "Hints",
+            format!(
+                "{hints} ({} shown)",
+                progress.map_or(0, |progress| progress.hints_shown)
+            ),
+        ),
+        (
+            "Solution",
+            if solution.exists() {
+                solution.display().to_string()
+            } else {
+                "none".to_string()
+            },
+        ),
+    ];
+    if read_notes(exercise).is_some() {
+        fields.push(("Notes", notes_path(exercise).display().to_string()));
+    }
+    if let Some(error) = state.last_error(exercise) {
+        fields.push(("Last error", error.to_string()));
+    }
+    fields
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::exercise::{Difficulty, Mode};
+
+    #[test]
+    fn test_fields() {
+        let exercise = Exercise {
+            mode: Mode::Test,
+            hint: "Look at the fields".to_string(),
+            topics: vec!["structs".to_string()],
+            difficulty: Some(Difficulty::Medium),
+            ..Exercise::new("structs3", "exercises/structs/structs3.rs")
+        };
+        let exercises = [exercise.clone()];
+        let fields = fields(&exercise, &exercises, &ProgressState::default());
+        let field = |name| {
+            fields
+                .iter()
+                .find(|(field, _)| *field == name)
+                .map(|(_, value)| value.as_str())
+        };
+        assert_eq!(field("Mode"), Some("test"));
+        assert_eq!(field("Chapter"), Some("Structs (1)"));
+        assert_eq!(field("Difficulty"), Some("medium"));
+        assert_eq!(field("Estimate"), Some("10m"));
+        assert_eq!(field("Requires"), Some("none"));
+        assert_eq!(field("Status"), Some("Pending"));
+        assert_eq!(field("Hints"), Some("1 (0 shown)"));
+        assert_eq!(field("Last error"), None);
+    }
+}
@@ -0,0 +1,237 @@
+use crate::exercise::ExerciseList;
+use crate::prerequisites::order;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+// The files of a VS Code workspace for a course, relative to its directory
+const VSCODE_FILES: &[(&str, &str)] = &[
+    (
+        ".vscode/extensions.json",
+        r#"{
+    "recommendations": [
+        "rust-lang.rust-analyzer"
+    ]
+}
+"#,
+    ),
+    (
+        ".vscode/settings.json",
+        r#"{
+    "rust-analyzer.check.command": "clippy",
+    "files.exclude": {
+        ".rustlings": true,
+        ".rustlings-state.json": true
+    }
+}
+"#,
+    ),
+    (
+        ".vscode/tasks.json",
+        r#"{
+    "version": "2.0.0",
+    "tasks": [
+        {
+            "label": "Run current exercise",
+            "type": "shell",
+            "command": "rustlings run ${fileBasenameNoExtension}",
+            "problemMatcher": "$rustc",
+            "group": {
+                "kind": "test",
+                "isDefault": true
+            }
+        },
+        {
+            "label": "Show hint for current exercise",
+            "type": "shell",
+            "command": "rustlings hint ${fileBasenameNoExtension}",
+            "problemMatcher": []
+        },
+        {
+            "label": "Watch exercises",
+            "type": "shell",
+            "command": "rustlings watch",
+            "isBackground": true,
+            "problemMatcher": []
+        }
+    ]
+}
+"#,
+    ),
+];
+
+// How a course is set up by `init_from_git`
+#[derive(Default)]
+pub struct InitOptions {
+    // The directory to set the course up in, named after the repository if unset
+    pub dir: Option<PathBuf>,
+    // Replace an existing copy of a course in that directory
+    pub force: bool,
+    // Never wait for input, e.g. when git would ask for credentials
+    pub non_interactive: bool,
+}
+
+// Clone a course from a git repository and check that it can be used with Rustlings
+pub fn init_from_git(url: &str, options: InitOptions) -> Result<PathBuf, ()> {
+    let dir = options.dir.unwrap_or_else(|| default_dir(url));
+    if dir.exists() {
+        if !options.force {
+            warn!("{} already exists, not cloning into it", dir.display());
+            println!("Pass `--force` to replace the course that's in it.");
+            return Err(());
+        }
+        // Only copies of a course are replaced, not any directory that
+        // happens to be in the way
+        if !is_course_checkout(&dir) {
+            warn!(
+                "{} doesn't contain a course, so it isn't replaced",
+                dir.display()
+            );
+            return Err(());
+        }
+        if let Err(e) = fs::remove_dir_all(&dir) {
+            warn!("Couldn't remove {}", dir.display());
+            println!("{e}");
+            return Err(());
+        }
+    }
+
+    println!("Cloning {url} into {}...", dir.display());
+    let mut clone = Command::new("git");
+    clone.args(["clone", "--depth", "1", url]).arg(&dir);
+    if options.non_interactive {
+        clone
+            .arg("--quiet")
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .stdin(Stdio::null());
+    }
+    let status = clone.status();
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(_) => {
+            warn!("Failed to clone {}", url);
+            return Err(());
+        }
+        Err(e) => {
+            warn!("Failed to run git: {}", e);
+            return Err(());
+        }
+    }
+
+    if let Err(problem) = validate_course(&dir) {
+        warn!("{} doesn't look like a Rustlings course", url);
+        println!("{problem}");
+        let _ = fs::remove_dir_all(&dir);
+        return Err(());
+    }
+    Ok(dir)
+}
+
+// Set up VS Code for the course: recommend rust-analyzer and add tasks to run
+// the open exercise and show its hint. Files the course already comes with are
+// kept. Returns the files that were written.
+pub fn write_vscode_workspace(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir.join(".vscode"))?;
+    let mut written = Vec::new();
+    for (file, contents) in VSCODE_FILES {
+        let path = dir.join(file);
+        if !path.exists() {
+            fs::write(&path, contents)?;
+            written.push(path);
+        }
+    }
+    Ok(written)
+}
+
+// Whether the directory holds a copy of a course, even a broken one: a git
+// checkout or a directory with an `info.toml`. Empty directories count too.
+fn is_course_checkout(dir: &Path) -> bool {
+    dir.join(".git").exists()
+        || dir.join("info.toml").exists()
+        || fs::read_dir(dir).is_ok_and(|mut entries| entries.next().is_none())
+}
+
+// The directory to clone into, named after the repository
+fn default_dir(url: &str) -> PathBuf {
+    let name = url
+        .trim_end_matches('/')
+        .rsplit(['/', ':'])
+        .next()
+        .unwrap_or(url)
+        .trim_end_matches(".git");
+    PathBuf::from(if name.is_empty() { "rustlings" } else { name })
+}
+
+// Check that the course has a valid `info.toml` and all exercises it lists
+fn validate_course(dir: &Path) -> Result<(), String> {
+    let info = fs::read_to_string(dir.join("info.toml"))
+        .map_err(|_| "The course has no info.toml file.".to_string())?;
+    let list = toml::from_str::<ExerciseList>(&info)
+        .map_err(|e| format!("The info.toml file is invalid: {e}"))?;
+    if list.exercises.is_empty() {
+        return Err("The info.toml file doesn't contain any exercises.".to_string());
+    }
+    let missing: Vec<String> = list
+        .exercises
+        .iter()
+        .filter(|e| !dir.join(&e.path).exists())
+        .map(|e| format!("  {}: {}", e.name, e.path.display()))
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!(
+            "These exercises are missing their file:\n{}",
+            missing.join("\n")
+        ));
+    }
+    order(list.exercises).map(|_| ())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_default_dir() {
+        assert_eq!(
+            default_dir("https://github.com/someone/rust-course.git"),
+            PathBuf::from("rust-course")
+        );
+        assert_eq!(
+            default_dir("git@github.com:someone/course"),
+            PathBuf::from("course")
+        );
+    }
+
+    #[test]
+    fn test_write_vscode_workspace() {
+        let dir = std::env::temp_dir().join(format!("rustlings-vscode-{}", std::process::id()));
+        let settings = dir.join(".vscode/settings.json");
+        fs::create_dir_all(dir.join(".vscode")).unwrap();
+        fs::write(&settings, "{}").unwrap();
+
+        let written = write_vscode_workspace(&dir).unwrap();
+        let tasks = fs::read_to_string(dir.join(".vscode/tasks.json")).unwrap();
+        let kept = fs::read_to_string(&settings).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(written.len(), 2);
+        assert!(tasks.contains("rustlings run ${fileBasenameNoExtension}"));
+        assert_eq!(kept, "{}");
+        for (_, contents) in VSCODE_FILES {
+            serde_json::from_str::<serde_json::Value>(contents).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_validate_course() {
+        assert!(validate_course(Path::new("tests/fixture/success")).is_ok());
+        assert!(validate_course(Path::new("tests/fixture")).is_err());
+    }
+
+    #[test]
+    fn test_is_course_checkout() {
+        assert!(is_course_checkout(Path::new("tests/fixture/success")));
+        assert!(!is_course_checkout(Path::new("tests")));
+    }
+}
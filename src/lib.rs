@@ -0,0 +1,103 @@
+// Rustlings as a library, for editor plugins and course platforms that work
+// with the exercises directly instead of running `rustlings` and reading what
+// it prints. The exercises of a course, verifying and running them, and the
+// progress of the learner are the public API:
+//
+//     let exercises = rustlings::ExerciseList::load()?;
+//     let state = rustlings::ProgressState::load();
+//     let next = exercises.iter().find(|e| !state.is_done(e));
+//     rustlings::verify(next.unwrap(), &options)?;
+//
+// Unlike the `rustlings` binary, the library doesn't print anything, it
+// returns what the learner would be shown. It does work on the course in the
+// current directory, since the paths in its info.toml are relative to it.
+
+#[macro_use]
+mod ui;
+#[macro_use]
+mod i18n;
+
+mod backup;
+mod cache;
+mod chapter;
+mod ci;
+mod classroom;
+mod config;
+mod debug;
+mod dev;
+mod diagnostics;
+mod doctor;
+mod error;
+mod events;
+mod exercise;
+mod git;
+mod hooks;
+mod info;
+mod init;
+mod list;
+mod lsp;
+mod markdown;
+mod notes;
+mod notification;
+mod pager;
+mod pick;
+mod plan;
+mod prerequisites;
+mod progress;
+mod project;
+mod repair;
+mod report;
+mod review;
+mod run;
+mod serve;
+mod state;
+mod stats;
+mod sync;
+mod terminal;
+#[cfg(unix)]
+mod tui;
+mod upgrade;
+mod util;
+mod verify;
+mod webhook;
+mod workspace;
+
+// The `rustlings` command, which is made of all the modules above and may
+// change at any time
+#[doc(hidden)]
+pub mod cli;
+
+pub use error::Error;
+pub use exercise::{
+    CompiledExercise, Difficulty, Exercise, ExerciseList, ExerciseOutput, Mode, RunInput,
+};
+pub use state::{ExerciseProgress, ProgressState, Status};
+pub use verify::VerifyOptions;
+
+// Check the exercise like `rustlings verify` does, without recording whether
+// it's done
+pub fn verify(exercise: &Exercise, options: &VerifyOptions) -> Result<(), Error> {
+    verify::check_quietly(exercise, options)
+        .0
+        .map_err(|output| Error::Exercise {
+            name: exercise.name.clone(),
+            output,
+        })
+}
+
+// Compile and run the exercise like `rustlings run` does, returning what it
+// printed
+pub fn run(
+    exercise: &Exercise,
+    options: &VerifyOptions,
+    input: &RunInput,
+) -> Result<ExerciseOutput, Error> {
+    let failed = |output: String| Error::Exercise {
+        name: exercise.name.clone(),
+        output,
+    };
+    let compiled = exercise.compile().map_err(|output| failed(output.stderr))?;
+    compiled
+        .run_with(input, options.timeout)
+        .map_err(|output| failed(output.stdout + &output.stderr))
+}
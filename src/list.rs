@@ -0,0 +1,458 @@
+use crate::chapter::chapters;
+use crate::exercise::{Difficulty, Exercise};
+use crate::notes::read_notes;
+use crate::pager;
+use crate::plan::format_minutes;
+use crate::state::{ProgressState, Status};
+use crate::stats::{format_duration, Pace};
+use crate::ui::text_bar;
+use clap::ValueEnum;
+use console::style;
+use regex::Regex;
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::fs;
+use std::io::{self, prelude::*};
+use std::time::{Duration, SystemTime};
+
+// The ways in which the exercise list can be narrowed down or displayed
+#[derive(Default)]
+pub struct ListOptions {
+    // Show only the paths of the exercises
+    pub paths: bool,
+    // Show only the names of the exercises
+    pub names: bool,
+    // Comma separated patterns to match exercise names and paths against
+    pub filter: Option<String>,
+    // Show only exercises not yet solved
+    pub unsolved: bool,
+    // Show only exercises that have been solved
+    pub solved: bool,
+    // Show only exercises about this topic
+    pub topic: Option<String>,
+    // Show only exercises of this difficulty
+    pub difficulty: Option<Difficulty>,
+    // How to print the exercises
+    pub format: ListFormat,
+    // Show only exercises with notes, along with the notes
+    pub with_notes: bool,
+    // The order to print the exercises in
+    pub sort: ListSort,
+    // Print only the progress of the course and its chapters
+    pub summary_only: bool,
+    // Show only exercises whose name, path, hints or files match, along
+    // with the lines that match
+    pub search: Option<Regex>,
+    // Estimate how long the exercises that aren't done take from how long
+    // the solved ones took
+    pub estimate_time: bool,
+}
+
+// The orders the exercises can be listed in
+#[derive(ValueEnum, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum ListSort {
+    // The order of the course
+    #[default]
+    Course,
+    // Alphabetically by name
+    Name,
+    // Alphabetically by path
+    Path,
+    // Pending exercises first, then skipped ones, then those that are done
+    Status,
+    // The exercises that took the most failed attempts first
+    Attempts,
+    // The exercises that were changed most recently first
+    Recent,
+}
+
+// The ways the exercise list can be printed
+#[derive(ValueEnum, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum ListFormat {
+    // Aligned columns for reading, followed by the overall progress
+    #[default]
+    Table,
+    // A JSON array of the exercises
+    Json,
+    // Comma separated values with a header line
+    Csv,
+}
+
+// An exercise as printed by `list --format json` or `list --format csv`
+#[derive(Serialize)]
+struct ExerciseRecord<'a> {
+    name: &'a str,
+    path: String,
+    status: Status,
+    topics: &'a [String],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    difficulty: Option<Difficulty>,
+    has_hint: bool,
+    // How many times the exercise was checked without being solved
+    attempts: u32,
+    estimated_minutes: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    notes: Option<String>,
+    // The lines of the hints and files that match the search
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    matches: Vec<String>,
+    // The directory of the chapter the exercise is in
+    #[serde(skip)]
+    section: Option<String>,
+    // When any file of the exercise was last changed
+    #[serde(skip)]
+    modified: Option<SystemTime>,
+    // How long the exercise will likely take at the learner's pace
+    #[serde(skip)]
+    at_pace: Option<Duration>,
+}
+
+// Print the exercises along with their status, followed by the overall progress
+pub fn list(exercises: &[Exercise], options: &ListOptions) {
+    let ListOptions {
+        paths,
+        names,
+        filter,
+        unsolved,
+        solved,
+        topic,
+        difficulty,
+        format,
+        with_notes,
+        sort,
+        summary_only,
+        search,
+        estimate_time,
+    } = options;
+
+    let state = ProgressState::load();
+    if *summary_only {
+        pager::page(&summary(exercises, &state));
+        return;
+    }
+
+    let pace = estimate_time.then(|| Pace::new(exercises, &state));
+    let mut records = Vec::new();
+    exercises.iter().for_each(|e| {
+        let fname = format!("{}", e.path.display());
+        let filter_cond = filter
+            .as_ref()
+            .is_none_or(|filter| matches_filter(e, filter));
+        let status = state.status(e);
+        let done = status == Status::Done;
+        let solve_cond = (done && *solved) || (!done && *unsolved) || (!solved && !unsolved);
+        let topic_cond = topic.as_ref().is_none_or(|topic| e.has_topic(topic));
+        let difficulty_cond = difficulty.is_none() || e.difficulty == *difficulty;
+        let notes = if *with_notes { read_notes(e) } else { None };
+        let notes_cond = !with_notes || notes.is_some();
+        let matches = search.as_ref().map(|regex| search_exercise(e, regex));
+        let search_cond = matches.as_ref().is_none_or(|matches| matches.is_some());
+        if solve_cond && topic_cond && difficulty_cond && notes_cond && search_cond && filter_cond {
+            records.push(ExerciseRecord {
+                name: &e.name,
+                path: fname,
+                status,
+                topics: &e.topics,
+                difficulty: e.difficulty,
+                has_hint: !e.hints().is_empty(),
+                attempts: state
+                    .exercises
+                    .get(&e.name)
+                    .map_or(0, |progress| progress.attempts),
+                estimated_minutes: e.estimated_minutes(),
+                notes,
+                matches: matches.flatten().unwrap_or_default(),
+                section: e.metadata().section,
+                modified: e
+                    .source_files()
+                    .iter()
+                    .filter_map(|file| fs::metadata(file).and_then(|m| m.modified()).ok())
+                    .max(),
+                at_pace: pace.as_ref().and_then(|pace| pace.estimate(e)),
+            });
+        }
+    });
+    sort_records(&mut records, *sort);
+
+    match format {
+        ListFormat::Json => {
+            let json =
+                serde_json::to_string_pretty(&records).expect("Failed to serialize exercises");
+            write_line(&format!("{json}\n"));
+            return;
+        }
+        ListFormat::Csv => {
+            let notes_column = if *with_notes { ",notes" } else { "" };
+            write_line(&format!("name,path,status,topics,has_hint{notes_column}\n"));
+            for record in &records {
+                let notes = match &record.notes {
+                    Some(notes) => format!(",{}", csv_field(notes.trim_end())),
+                    None => String::new(),
+                };
+                write_line(&format!(
+                    "{},{},{},{},{}{notes}\n",
+                    csv_field(record.name),
+                    csv_field(&record.path),
+                    record.status,
+                    csv_field(&record.topics.join(";")),
+                    record.has_hint
+                ));
+            }
+            return;
+        }
+        ListFormat::Table => {}
+    }
+
+    let mut out = String::new();
+    if !paths && !names {
+        let pace_column = if pace.is_some() { "At your pace\t" } else { "" };
+        out.push_str(&format!(
+            "{:<17}\t{:<46}\t{:<7}\t{:<8}\t{:<8}\t{pace_column}Difficulty\n",
+            "Name", "Path", "Status", "Attempts", "Estimate"
+        ));
+    }
+    // The time the exercises that aren't done take at the learner's pace
+    let mut left_at_pace = Some(Duration::ZERO);
+    let chapters = chapters(exercises);
+    // The time the exercises that aren't done take, up to the current one
+    let mut estimate = 0;
+    for (i, record) in records.iter().enumerate() {
+        let line = if *paths {
+            format!("{}\n", record.path)
+        } else if *names {
+            format!("{}\n", record.name)
+        } else {
+            let difficulty = record
+                .difficulty
+                .map(|difficulty| format!("\t{difficulty}"))
+                .unwrap_or_default();
+            let cumulative = if record.status == Status::Done {
+                "—".to_string()
+            } else {
+                estimate += record.estimated_minutes;
+                format_minutes(estimate)
+            };
+            let at_pace = if pace.is_none() {
+                String::new()
+            } else if record.status == Status::Done {
+                format!("\t{:<12}", "—")
+            } else {
+                left_at_pace = left_at_pace
+                    .zip(record.at_pace)
+                    .map(|(left, estimate)| left + estimate);
+                let estimate = record.at_pace.map_or("—".to_string(), format_duration);
+                format!("\t{estimate:<12}")
+            };
+            format!(
+                "{:<17}\t{:<46}\t{:<7}\t{:<8}\t{cumulative:<8}{at_pace}{difficulty}\n",
+                record.name, record.path, record.status, record.attempts
+            )
+        };
+        out.push_str(&line);
+        if let Some(notes) = &record.notes {
+            for note in notes.trim_end().lines() {
+                out.push_str(&format!("    {note}\n"));
+            }
+        }
+        if !paths && !names {
+            for line in &record.matches {
+                out.push_str(&format!("    {line}\n"));
+            }
+        }
+        // Close every chapter with its progress, unless only paths or names
+        // are asked for, or the chapters are mixed up by sorting
+        let chapter_ends = records.get(i + 1).map(|next| &next.section) != Some(&record.section);
+        if !paths && !names && *sort == ListSort::Course && chapter_ends {
+            if let Some(chapter) = chapters
+                .iter()
+                .find(|chapter| Some(&chapter.section) == record.section.as_ref())
+            {
+                out.push_str(&format!("-- {}\n", chapter.summary(&state)));
+            }
+        }
+    }
+    out.push('\n');
+    if pace.is_some() {
+        out.push_str(&format!(
+            "Left at your pace: {}\n",
+            left_at_pace.map_or("—".to_string(), format_duration)
+        ));
+    }
+    out.push_str(&summary(exercises, &state));
+    pager::page(&out);
+}
+
+// Whether the exercise matches a filter like that of `list --filter`: comma
+// separated patterns to look for in the name and path of the exercise.
+// Patterns with wildcards, like `errors*`, have to match the whole name or path.
+pub fn matches_filter(exercise: &Exercise, filter: &str) -> bool {
+    let name = exercise.name.to_lowercase();
+    let path = exercise.path.to_string_lossy().to_lowercase();
+    filter
+        .to_lowercase()
+        .split(',')
+        .map(str::trim)
+        .filter(|f| !f.is_empty())
+        .any(|f| match glob::Pattern::new(f) {
+            Ok(pattern) if f.contains(['*', '?', '[']) => {
+                pattern.matches(&name) || pattern.matches(&path)
+            }
+            _ => name.contains(f) || path.contains(f),
+        })
+}
+
+// A bar for the progress of the whole course, followed by how many
+// exercises of every chapter are done
+fn summary(exercises: &[Exercise], state: &ProgressState) -> String {
+    let mut out = String::new();
+    let done = exercises.iter().filter(|e| state.is_done(e)).count();
+    let total = exercises.len();
+    let percentage = done as f32 / total.max(1) as f32 * 100.0;
+    out.push_str(&format!(
+        "Progress: {} {done}/{total} exercises done ({percentage:.1} %)\n",
+        text_bar(done, total, 40)
+    ));
+    let left: u32 = exercises
+        .iter()
+        .filter(|e| !state.is_done(e))
+        .map(Exercise::estimated_minutes)
+        .sum();
+    if left > 0 {
+        out.push_str(&format!(
+            "About {} left for the exercises that aren't done\n",
+            format_minutes(left)
+        ));
+    }
+    for chapter in chapters(exercises) {
+        let summary = chapter.summary(state);
+        if chapter.exercises.iter().all(|e| state.is_done(e)) {
+            out.push_str(&format!("  {}\n", style(summary).green()));
+        } else {
+            out.push_str(&format!("  {summary}\n"));
+        }
+    }
+    out
+}
+
+// Search the exercise for the regex. If it matches, returns the lines of the
+// hints and files it matches, like "hint: Look at `Rc`" or
+// "exercises/smart_pointers/rc1.rs:12: let a = Rc::new(1);". The list of lines
+// is empty if only the name or path of the exercise matches.
+fn search_exercise(exercise: &Exercise, regex: &Regex) -> Option<Vec<String>> {
+    let mut matches = Vec::new();
+    for hint in exercise.hints() {
+        for line in hint.lines().filter(|line| regex.is_match(line)) {
+            matches.push(format!("hint: {}", line.trim()));
+        }
+    }
+    for file in exercise.source_files() {
+        let Ok(contents) = fs::read_to_string(&file) else {
+            continue;
+        };
+        for (number, line) in contents.lines().enumerate() {
+            if regex.is_match(line) {
+                matches.push(format!(
+                    "{}:{}: {}",
+                    file.display(),
+                    number + 1,
+                    line.trim()
+                ));
+            }
+        }
+    }
+    let named = regex.is_match(&exercise.name) || regex.is_match(&exercise.path.to_string_lossy());
+    (named || !matches.is_empty()).then_some(matches)
+}
+
+// Sort the records, keeping the order of the course among equal ones
+fn sort_records(records: &mut [ExerciseRecord], sort: ListSort) {
+    match sort {
+        ListSort::Course => {}
+        ListSort::Name => records.sort_by(|a, b| a.name.cmp(b.name)),
+        ListSort::Path => records.sort_by(|a, b| a.path.cmp(&b.path)),
+        ListSort::Status => records.sort_by_key(|record| record.status),
+        ListSort::Attempts => records.sort_by_key(|record| Reverse(record.attempts)),
+        ListSort::Recent => records.sort_by_key(|record| Reverse(record.modified)),
+    }
+}
+
+// Quote a CSV field if it contains a separator, a quote or a line break
+pub fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+// Somehow using println! leads to the binary panicking
+// when its output is piped.
+// So, we're handling a Broken Pipe error and exiting with 0 anyway
+fn write_line(line: &str) {
+    let stdout = io::stdout();
+    let mut handle = stdout.lock();
+    handle.write_all(line.as_bytes()).unwrap_or_else(|e| {
+        match e.kind() {
+            io::ErrorKind::BrokenPipe => std::process::exit(0),
+            _ => std::process::exit(1),
+        };
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_csv_field() {
+        assert_eq!(csv_field("intro1"), "intro1");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn test_matches_filter() {
+        let exercise = Exercise::new("errors3", "exercises/error_handling/errors3.rs");
+        assert!(matches_filter(&exercise, "errors*"));
+        assert!(matches_filter(&exercise, "vecs, error_handling"));
+        assert!(matches_filter(&exercise, "Errors"));
+        assert!(!matches_filter(&exercise, "rors*"));
+        assert!(!matches_filter(&exercise, ","));
+    }
+
+    #[test]
+    fn test_sort_records() {
+        let record = |name, status, modified| ExerciseRecord {
+            name,
+            path: format!("exercises/{name}.rs"),
+            status,
+            topics: &[],
+            difficulty: None,
+            has_hint: false,
+            attempts: 0,
+            estimated_minutes: 10,
+            notes: None,
+            matches: Vec::new(),
+            section: None,
+            modified: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(modified)),
+            at_pace: None,
+        };
+        let mut records = [
+            record("b", Status::Done, 3),
+            record("c", Status::Pending, 2),
+            record("a", Status::Skipped, 1),
+        ];
+        let names = |records: &[ExerciseRecord]| -> Vec<String> {
+            records
+                .iter()
+                .map(|record| record.name.to_string())
+                .collect()
+        };
+        sort_records(&mut records, ListSort::Status);
+        assert_eq!(names(&records), ["c", "a", "b"]);
+        sort_records(&mut records, ListSort::Recent);
+        assert_eq!(names(&records), ["b", "c", "a"]);
+        sort_records(&mut records, ListSort::Path);
+        assert_eq!(names(&records), ["a", "b", "c"]);
+    }
+}
@@ -0,0 +1,340 @@
+// A small language server for the exercises, speaking the Language Server
+// Protocol on stdin and stdout.
+//
+// Whenever an exercise is opened or saved, it's compiled and the diagnostics
+// of the compiler and Clippy are published for it, so they show up in the
+// editor right where they belong. Editors can ask for the next hint of an
+// exercise with the `rustlings.hint` command.
+use crate::events;
+use crate::exercise::{self, Exercise, State};
+use crate::state::{ProgressState, Status};
+use crate::ui;
+use crate::verify::{self, VerifyOptions};
+use serde_json::{json, Value};
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+
+// The command that shows the next hint of an exercise. Its only argument is
+// the URI of the exercise, the next unsolved exercise is used without it.
+const HINT_COMMAND: &str = "rustlings.hint";
+
+// The JSON-RPC error codes for unknown methods and invalid arguments
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+type Response = Result<Value, (i64, String)>;
+
+// Serve the exercises until the editor asks the server to exit
+pub fn serve(exercises: &[Exercise], options: &VerifyOptions) -> io::Result<()> {
+    exercise::enable_json_diagnostics();
+    ui::disable_color();
+    let mut input = io::stdin().lock();
+    let mut output = io::stdout().lock();
+    while let Some(message) = read_message(&mut input)? {
+        let params = &message["params"];
+        let response = match message["method"].as_str().unwrap_or_default() {
+            "initialize" => Ok(capabilities()),
+            "shutdown" => Ok(Value::Null),
+            "exit" => return Ok(()),
+            "textDocument/didOpen" | "textDocument/didSave" => {
+                let uri = &params["textDocument"]["uri"];
+                let file = uri.as_str().and_then(uri_to_path);
+                if let Some((file, exercise)) = file.and_then(|file| find(&file, exercises)) {
+                    let diagnostics = check(exercise, &file, options);
+                    let params = json!({ "uri": uri, "diagnostics": diagnostics });
+                    write_notification(&mut output, "textDocument/publishDiagnostics", params)?;
+                }
+                Ok(Value::Null)
+            }
+            "workspace/executeCommand" if params["command"] == HINT_COMMAND => {
+                let hint = next_hint(&params["arguments"][0], exercises);
+                if let Ok(hint) = &hint {
+                    // Editors that don't know the command can at least show the hint
+                    let message = json!({ "type": 3, "message": hint["hint"] });
+                    write_notification(&mut output, "window/showMessage", message)?;
+                }
+                hint
+            }
+            method => Err((METHOD_NOT_FOUND, format!("Unknown method '{method}'"))),
+        };
+        // Only requests have an id, notifications don't get a response
+        if let Some(id) = message.get("id") {
+            let response = match response {
+                Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+                Err((code, message)) => json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "error": { "code": code, "message": message },
+                }),
+            };
+            write_message(&mut output, &response)?;
+        }
+    }
+    Ok(())
+}
+
+fn capabilities() -> Value {
+    json!({
+        "capabilities": {
+            "textDocumentSync": { "openClose": true, "change": 0, "save": true },
+            "executeCommandProvider": { "commands": [HINT_COMMAND] },
+        },
+        "serverInfo": { "name": "rustlings", "version": env!("CARGO_PKG_VERSION") },
+    })
+}
+
+// Read the next message, or None once the input is closed
+fn read_message(input: &mut impl BufRead) -> io::Result<Option<Value>> {
+    let mut length = None;
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+    let Some(length) = length else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Message without a Content-Length",
+        ));
+    };
+    let mut content = vec![0; length];
+    input.read_exact(&mut content)?;
+    // Messages that aren't JSON are treated like unknown notifications
+    Ok(Some(serde_json::from_slice(&content).unwrap_or_default()))
+}
+
+fn write_message(output: &mut impl Write, message: &Value) -> io::Result<()> {
+    let content = message.to_string();
+    write!(output, "Content-Length: {}\r\n\r\n{content}", content.len())?;
+    output.flush()
+}
+
+fn write_notification(output: &mut impl Write, method: &str, params: Value) -> io::Result<()> {
+    let notification = json!({ "jsonrpc": "2.0", "method": method, "params": params });
+    write_message(output, &notification)
+}
+
+// The path of a `file://` URI
+fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let path = percent_decode(uri.strip_prefix("file://")?);
+    // Windows paths look like `file:///C:/...`
+    #[cfg(windows)]
+    let path = path.strip_prefix('/').unwrap_or(&path).to_string();
+    Some(PathBuf::from(path))
+}
+
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| text.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+// The exercise the file belongs to, along with the file's canonical path
+fn find<'a>(file: &Path, exercises: &'a [Exercise]) -> Option<(PathBuf, &'a Exercise)> {
+    let file = fs::canonicalize(file).ok()?;
+    let exercise = exercises
+        .iter()
+        .find(|exercise| exercise.contains_file(&file))?;
+    Some((file, exercise))
+}
+
+// Compile and lint the exercise like `verify`, returning the diagnostics of
+// one of its files for the editor. Once it compiles, the `I AM NOT DONE`
+// comment is pointed out.
+fn check(exercise: &Exercise, file: &Path, options: &VerifyOptions) -> Vec<Value> {
+    let failure = match exercise.compile() {
+        Err(output) => Some(output),
+        Ok(_) if verify::needs_lint(exercise, options) => exercise.lint().err(),
+        Ok(_) => None,
+    };
+    match failure {
+        Some(output) => events::diagnostics(&output.stdout)
+            .chain(events::diagnostics(&output.stderr))
+            .filter_map(|diagnostic| to_lsp_diagnostic(&diagnostic, file))
+            .collect(),
+        None => not_done_diagnostic(exercise, file).into_iter().collect(),
+    }
+}
+
+fn range(line: u64, start: u64, end_line: u64, end: u64) -> Value {
+    json!({
+        "start": { "line": line, "character": start },
+        "end": { "line": end_line, "character": end },
+    })
+}
+
+// Turn a diagnostic of rustc into one of the Language Server Protocol,
+// if it points into the given file
+fn to_lsp_diagnostic(diagnostic: &Value, path: &Path) -> Option<Value> {
+    let span = diagnostic["spans"].as_array()?.iter().find(|span| {
+        span["is_primary"] == true
+            && span["file_name"]
+                .as_str()
+                .map(Path::new)
+                .and_then(Path::file_name)
+                == path.file_name()
+    })?;
+    // rustc counts lines and columns from 1, the protocol from 0
+    let position = |name: &str| span[name].as_u64().unwrap_or(1).saturating_sub(1);
+    let severity = match diagnostic["level"].as_str()? {
+        "warning" => 2,
+        "note" => 3,
+        "help" => 4,
+        _ => 1,
+    };
+    let mut message = diagnostic["message"].as_str()?.to_string();
+    for child in diagnostic["children"].as_array().into_iter().flatten() {
+        if let (Some(level), Some(text)) = (child["level"].as_str(), child["message"].as_str()) {
+            message.push_str(&format!("\n{level}: {text}"));
+        }
+    }
+    let mut lsp_diagnostic = json!({
+        "range": range(
+            position("line_start"),
+            position("column_start"),
+            position("line_end"),
+            position("column_end"),
+        ),
+        "severity": severity,
+        "source": "rustlings",
+        "message": message,
+    });
+    if let Some(code) = diagnostic["code"]["code"].as_str() {
+        lsp_diagnostic["code"] = json!(code);
+    }
+    Some(lsp_diagnostic)
+}
+
+// The `I AM NOT DONE` comment of the exercise, if it's in the given file
+fn not_done_diagnostic(exercise: &Exercise, file: &Path) -> Option<Value> {
+    // A file of an exercise made of several is looked at on its own
+    let file = Exercise {
+        path: file.to_path_buf(),
+        ..exercise.clone()
+    };
+    let State::Pending(context) = file.state() else {
+        return None;
+    };
+    let line = context.iter().find(|line| line.important)?;
+    let number = line.number as u64 - 1;
+    Some(json!({
+        "range": range(number, 0, number, line.line.chars().count() as u64),
+        "severity": 3,
+        "source": "rustlings",
+        "message": "The exercise compiles! Remove this comment once you're done to move on.",
+    }))
+}
+
+// The next hint of the exercise with the given URI, or of the next unsolved
+// exercise. Like `rustlings hint`, every call reveals one more hint.
+fn next_hint(uri: &Value, exercises: &[Exercise]) -> Response {
+    let mut state = ProgressState::load();
+    let exercise = match uri.as_str() {
+        Some(uri) => uri_to_path(uri)
+            .and_then(|file| find(&file, exercises))
+            .map(|(_, exercise)| exercise),
+        None => exercises
+            .iter()
+            .find(|exercise| state.status(exercise) == Status::Pending),
+    }
+    .ok_or((
+        INVALID_PARAMS,
+        "There's no exercise to show a hint for".to_string(),
+    ))?;
+    let hints = exercise.hints();
+    let Some(level) = state.next_hint(exercise) else {
+        return Err((
+            INVALID_PARAMS,
+            tr!("hint-missing", exercise = exercise.name),
+        ));
+    };
+    let _ = state.save();
+    Ok(json!({
+        "exercise": exercise.name,
+        "level": level + 1,
+        "count": hints.len(),
+        "hint": hints[level],
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_read_and_write_messages() {
+        let mut written = Vec::new();
+        write_message(&mut written, &json!({ "id": 1, "method": "shutdown" })).unwrap();
+        write_message(&mut written, &json!({ "method": "exit" })).unwrap();
+        let mut input = Cursor::new(written);
+        assert_eq!(read_message(&mut input).unwrap().unwrap()["id"], 1);
+        assert_eq!(read_message(&mut input).unwrap().unwrap()["method"], "exit");
+        assert!(read_message(&mut input).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_uri_to_path() {
+        assert_eq!(
+            uri_to_path("file:///home/me/my%20rustlings/intro1.rs"),
+            Some(PathBuf::from("/home/me/my rustlings/intro1.rs"))
+        );
+        assert_eq!(uri_to_path("untitled:Untitled-1"), None);
+    }
+
+    #[test]
+    fn test_to_lsp_diagnostic() {
+        let diagnostic = json!({
+            "message": "mismatched types",
+            "code": { "code": "E0308" },
+            "level": "error",
+            "spans": [
+                { "file_name": "other.rs", "is_primary": true, "line_start": 1, "line_end": 1, "column_start": 1, "column_end": 2 },
+                { "file_name": "exercises/intro/intro2.rs", "is_primary": true, "line_start": 3, "line_end": 3, "column_start": 9, "column_end": 14 },
+            ],
+            "children": [{ "message": "try using a conversion method", "level": "help" }],
+        });
+        assert_eq!(
+            to_lsp_diagnostic(&diagnostic, Path::new("exercises/intro/intro2.rs")),
+            Some(json!({
+                "range": range(2, 8, 2, 13),
+                "severity": 1,
+                "source": "rustlings",
+                "message": "mismatched types\nhelp: try using a conversion method",
+                "code": "E0308",
+            }))
+        );
+        assert_eq!(
+            to_lsp_diagnostic(&diagnostic, Path::new("exercises/intro/intro1.rs")),
+            None
+        );
+    }
+}
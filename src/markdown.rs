@@ -0,0 +1,279 @@
+// Rendering the Markdown of hints and chapter READMEs for the terminal.
+//
+// Only what they actually use is supported: headings, code blocks, inline
+// code, emphasis, strong emphasis and links. Without colors, the Markdown is kept
+// as it is, except that code blocks are indented and links show their target.
+use console::Style;
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "false",
+    "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "type", "unsafe",
+    "use", "where", "while",
+];
+
+// Render Markdown for printing, styled if colors are enabled
+pub fn render(markdown: &str) -> String {
+    render_with(markdown, console::colors_enabled())
+}
+
+fn render_with(markdown: &str, styled: bool) -> String {
+    let mut lines = Vec::new();
+    // Whether the lines are inside a code block, and if so, whether it's Rust code
+    let mut code_block: Option<bool> = None;
+    for line in markdown.lines() {
+        if let Some(lang) = line.trim_start().strip_prefix("```") {
+            code_block = match code_block {
+                Some(_) => None,
+                None => Some(matches!(lang.trim(), "" | "rust" | "rs")),
+            };
+            continue;
+        }
+        lines.push(match code_block {
+            Some(true) => format!("    {}", highlight(line, styled)),
+            Some(false) => format!("    {line}"),
+            None => match line.trim_start_matches('#').strip_prefix(' ') {
+                Some(heading) if styled && line.starts_with('#') => {
+                    paint(&inline(heading, styled), Style::new().bold(), styled)
+                }
+                _ => inline(line, styled),
+            },
+        });
+    }
+    lines.join("\n")
+}
+
+fn paint(text: &str, style: Style, styled: bool) -> String {
+    style.force_styling(styled).apply_to(text).to_string()
+}
+
+// The text between `delimiter` at the start of `text` and the next one,
+// along with what follows. Like in Markdown, the text between them can't
+// start or end with whitespace.
+fn delimited<'a>(text: &'a str, delimiter: &str) -> Option<(&'a str, &'a str)> {
+    let rest = text.strip_prefix(delimiter)?;
+    let end = rest.find(delimiter)?;
+    let inner = &rest[..end];
+    if inner.is_empty()
+        || inner.starts_with(char::is_whitespace)
+        || inner.ends_with(char::is_whitespace)
+    {
+        return None;
+    }
+    Some((inner, &rest[end + delimiter.len()..]))
+}
+
+// A link, which is clickable in terminals that support OSC 8 hyperlinks
+fn link(text: &str, url: &str, styled: bool) -> String {
+    if styled {
+        let text = paint(text, Style::new().underlined(), styled);
+        format!("\x1b]8;;{url}\x1b\\{text}\x1b]8;;\x1b\\")
+    } else if text == url {
+        url.to_string()
+    } else {
+        format!("{text} ({url})")
+    }
+}
+
+// The length of a URL at the start of `text`, without trailing punctuation
+fn url_len(text: &str) -> Option<usize> {
+    if !text.starts_with("https://") && !text.starts_with("http://") {
+        return None;
+    }
+    let end = text.find(char::is_whitespace).unwrap_or(text.len());
+    Some(
+        text[..end]
+            .trim_end_matches(['.', ',', ';', ':', '!', '?', ')'])
+            .len(),
+    )
+}
+
+fn inline(line: &str, styled: bool) -> String {
+    let mut out = String::new();
+    let mut rest = line;
+    let mut previous: Option<char> = None;
+    while let Some(c) = rest.chars().next() {
+        let after_word = previous.is_some_and(char::is_alphanumeric);
+        let (rendered, next) = if let Some((code, next)) = delimited(rest, "`") {
+            (paint(code, Style::new().cyan(), styled), next)
+        } else if let Some((text, next)) = delimited(rest, "**") {
+            (paint(text, Style::new().bold(), styled), next)
+        } else if let Some((text, next)) = delimited(rest, "*").filter(|_| !after_word) {
+            (paint(text, Style::new().italic(), styled), next)
+        } else if let Some((text, next)) = delimited(rest, "_").filter(|_| !after_word) {
+            (paint(text, Style::new().italic(), styled), next)
+        } else if let Some((text, url, next)) = markdown_link(rest) {
+            (link(text, url, styled), next)
+        } else if let Some(url) = rest
+            .strip_prefix("<")
+            .and_then(|r| Some(&r[..r.find('>')?]))
+        {
+            match url_len(url) {
+                Some(len) if len == url.len() => (link(url, url, styled), &rest[url.len() + 2..]),
+                _ => (c.to_string(), &rest[c.len_utf8()..]),
+            }
+        } else if let Some(len) = url_len(rest).filter(|_| !after_word) {
+            (link(&rest[..len], &rest[..len], styled), &rest[len..])
+        } else {
+            (c.to_string(), &rest[c.len_utf8()..])
+        };
+        // Without styles, the Markdown of code and emphasis stays as it is
+        let consumed = &rest[..rest.len() - next.len()];
+        if styled || consumed.starts_with('[') || consumed.starts_with('<') {
+            out.push_str(&rendered);
+        } else {
+            out.push_str(consumed);
+        }
+        previous = consumed.chars().last();
+        rest = next;
+    }
+    out
+}
+
+// `[text](url)` at the start of `text`, along with what follows
+fn markdown_link(text: &str) -> Option<(&str, &str, &str)> {
+    let rest = text.strip_prefix('[')?;
+    let (label, rest) = rest.split_once("](")?;
+    let (url, rest) = rest.split_once(')')?;
+    (!label.contains(']') && !url.contains(char::is_whitespace)).then_some((label, url, rest))
+}
+
+// Highlight a line of Rust code for printing, if colors are enabled
+pub fn highlight_line(code: &str) -> String {
+    highlight(code, console::colors_enabled())
+}
+
+// Highlight a line of Rust code
+fn highlight(code: &str, styled: bool) -> String {
+    let mut out = String::new();
+    let mut rest = code;
+    while let Some(c) = rest.chars().next() {
+        let len = if rest.starts_with("//") {
+            out.push_str(&paint(rest, Style::new().dim(), styled));
+            break;
+        } else if c == '"' {
+            let len = string_len(rest);
+            out.push_str(&paint(&rest[..len], Style::new().green(), styled));
+            len
+        } else if c.is_alphabetic() || c == '_' {
+            let len = rest
+                .find(|c: char| !c.is_alphanumeric() && c != '_')
+                .unwrap_or(rest.len());
+            let word = &rest[..len];
+            if RUST_KEYWORDS.contains(&word) {
+                out.push_str(&paint(word, Style::new().magenta().bold(), styled));
+            } else {
+                out.push_str(word);
+            }
+            len
+        } else if c.is_ascii_digit() {
+            let len = rest
+                .find(|c: char| !c.is_alphanumeric() && c != '_' && c != '.')
+                .unwrap_or(rest.len());
+            out.push_str(&paint(&rest[..len], Style::new().yellow(), styled));
+            len
+        } else {
+            out.push(c);
+            c.len_utf8()
+        };
+        rest = &rest[len..];
+    }
+    out
+}
+
+// The length of the string literal at the start of `code`, including its quotes
+fn string_len(code: &str) -> usize {
+    let mut escaped = false;
+    for (i, c) in code.char_indices().skip(1) {
+        match c {
+            '\\' if !escaped => escaped = true,
+            '"' if !escaped => return i + 1,
+            _ => escaped = false,
+        }
+    }
+    code.len()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_plain_markdown_stays_readable() {
+        let hint = "Use `vec![]` or *another* way.\n```rust\nlet x = 5;\n```\nSee [the book](https://doc.rust-lang.org/book).";
+        assert_eq!(
+            render_with(hint, false),
+            "Use `vec![]` or *another* way.\n    let x = 5;\nSee the book (https://doc.rust-lang.org/book)."
+        );
+    }
+
+    #[test]
+    fn test_styled_markdown() {
+        let rendered = render_with("Call `len` on **it**", true);
+        assert_eq!(
+            rendered,
+            format!(
+                "Call {} on {}",
+                Style::new().cyan().force_styling(true).apply_to("len"),
+                Style::new().bold().force_styling(true).apply_to("it")
+            )
+        );
+    }
+
+    #[test]
+    fn test_headings() {
+        let bold = Style::new().bold().force_styling(true);
+        assert_eq!(
+            render_with("## Further information\n#hashtag", true),
+            format!("{}\n#hashtag", bold.apply_to("Further information"))
+        );
+        assert_eq!(
+            render_with("## Further information", false),
+            "## Further information"
+        );
+    }
+
+    #[test]
+    fn test_snake_case_isnt_emphasized() {
+        assert_eq!(
+            render_with("Call string_slice_to_owned", true),
+            "Call string_slice_to_owned"
+        );
+    }
+
+    #[test]
+    fn test_links_are_clickable() {
+        let url = "https://doc.rust-lang.org/book/ch04-03-slices.html";
+        let link = |text: &str| {
+            format!(
+                "\x1b]8;;{url}\x1b\\{}\x1b]8;;\x1b\\",
+                Style::new().underlined().force_styling(true).apply_to(text)
+            )
+        };
+        assert_eq!(
+            render_with(&format!("Read {url}."), true),
+            format!("Read {}.", link(url))
+        );
+        assert_eq!(
+            render_with(&format!("Read <{url}>!"), true),
+            format!("Read {}!", link(url))
+        );
+        assert_eq!(render_with(&format!("<{url}>"), false), url);
+    }
+
+    #[test]
+    fn test_highlight_rust() {
+        let keyword = Style::new().magenta().bold().force_styling(true);
+        let string = Style::new().green().force_styling(true);
+        let comment = Style::new().dim().force_styling(true);
+        assert_eq!(
+            highlight(r#"let s = "a \"b\""; // c"#, true),
+            format!(
+                "{} s = {}; {}",
+                keyword.apply_to("let"),
+                string.apply_to(r#""a \"b\"""#),
+                comment.apply_to("// c")
+            )
+        );
+    }
+}
@@ -0,0 +1,48 @@
+use crate::exercise::Exercise;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+const NOTES_DIR: &str = ".rustlings/notes";
+
+// The file holding the user's notes about the exercise
+pub fn notes_path(exercise: &Exercise) -> PathBuf {
+    PathBuf::from(NOTES_DIR).join(format!("{}.md", exercise.name))
+}
+
+// The notes about the exercise, unless there aren't any. A notes file that
+// was opened but left as it was created doesn't count.
+pub fn read_notes(exercise: &Exercise) -> Option<String> {
+    fs::read_to_string(notes_path(exercise))
+        .ok()
+        .filter(|notes| !notes.trim().is_empty() && notes.trim() != template(exercise).trim())
+}
+
+// Add a note to the end of the notes about the exercise
+pub fn add_note(exercise: &Exercise, note: &str) -> io::Result<()> {
+    let path = create_notes(exercise)?;
+    let mut file = OpenOptions::new().append(true).open(path)?;
+    writeln!(file, "{}", note.trim_end())
+}
+
+// Replace the notes about the exercise
+pub fn write_notes(exercise: &Exercise, notes: &str) -> io::Result<()> {
+    fs::create_dir_all(NOTES_DIR)?;
+    fs::write(notes_path(exercise), notes)
+}
+
+// Create the notes file of the exercise unless it already exists,
+// so it can be opened in an editor
+pub fn create_notes(exercise: &Exercise) -> io::Result<PathBuf> {
+    let path = notes_path(exercise);
+    if !path.exists() {
+        fs::create_dir_all(NOTES_DIR)?;
+        fs::write(&path, template(exercise))?;
+    }
+    Ok(path)
+}
+
+// What a new notes file starts with
+fn template(exercise: &Exercise) -> String {
+    format!("# Notes on {}\n\n", exercise.name)
+}
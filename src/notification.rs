@@ -0,0 +1,41 @@
+use std::process::{Command, Stdio};
+
+// Show a desktop notification using the tools the platform ships with.
+// Notifications are a nicety, so failing to show one is silently ignored.
+pub fn notify(title: &str, body: &str) {
+    let mut command = if cfg!(target_os = "macos") {
+        let script = format!(
+            "display notification \"{}\" with title \"{}\"",
+            escape_applescript(body),
+            escape_applescript(title)
+        );
+        let mut command = Command::new("osascript");
+        command.args(["-e", &script]);
+        command
+    } else if cfg!(windows) {
+        let script = format!(
+            "[reflection.assembly]::loadwithpartialname('System.Windows.Forms') | Out-Null; \
+             $n = New-Object System.Windows.Forms.NotifyIcon; \
+             $n.Icon = [System.Drawing.SystemIcons]::Information; $n.Visible = $true; \
+             $n.ShowBalloonTip(5000, '{}', '{}', 'None'); Start-Sleep -Seconds 5; $n.Dispose()",
+            escape_powershell(title),
+            escape_powershell(body)
+        );
+        let mut command = Command::new("powershell");
+        command.args(["-NoProfile", "-Command", &script]);
+        command
+    } else {
+        let mut command = Command::new("notify-send");
+        command.args([title, body]);
+        command
+    };
+    let _ = command.stdout(Stdio::null()).stderr(Stdio::null()).spawn();
+}
+
+fn escape_applescript(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_powershell(text: &str) -> String {
+    text.replace('\'', "''")
+}
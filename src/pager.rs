@@ -0,0 +1,94 @@
+// Showing long output, like verbose hints, the whole list of exercises or a
+// solution, through a pager, so it doesn't scroll off the top of the screen.
+// Like git, Rustlings uses the `pager` setting, then the `PAGER` environment
+// variable, then `less`, and `pager = ""` turns paging off. Output that fits
+// on the screen, or that doesn't go to a terminal, is printed as usual.
+use crate::terminal;
+use std::env;
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+
+const DEFAULT_PAGER: &str = "less";
+
+static PAGER: OnceLock<Option<String>> = OnceLock::new();
+
+// Use the pager from the config from now on, if it sets one
+pub fn configure(pager: Option<String>) {
+    let _ = PAGER.set(pager);
+}
+
+// The command of the pager, or None if paging is turned off
+fn command() -> Option<String> {
+    let pager = PAGER
+        .get()
+        .cloned()
+        .flatten()
+        .or_else(|| env::var("PAGER").ok())
+        .unwrap_or_else(|| DEFAULT_PAGER.to_string());
+    (!pager.trim().is_empty()).then_some(pager)
+}
+
+// Show the text, through the pager if it doesn't fit on the screen
+pub fn page(text: &str) {
+    let term = console::Term::stdout();
+    let rows = term.size_checked().map(|(rows, _)| usize::from(rows));
+    if term.is_term() && !fits(text, rows) {
+        if let Some(pager) = command() {
+            // Watch mode reads single keys, which the pager has to read itself
+            if terminal::with_line_input(|| run(&pager, text)).is_ok() {
+                return;
+            }
+        }
+    }
+    print(text);
+}
+
+// Whether the text fits on a screen with the given rows, leaving one for the
+// prompt. Without knowing the rows, it's assumed to fit.
+fn fits(text: &str, rows: Option<usize>) -> bool {
+    rows.is_none_or(|rows| text.lines().count() < rows)
+}
+
+fn run(pager: &str, text: &str) -> io::Result<()> {
+    let mut parts = pager.split_whitespace();
+    let program = parts.next().unwrap_or(DEFAULT_PAGER);
+    let mut command = Command::new(program);
+    command.args(parts).stdin(Stdio::piped());
+    // Like git, have less keep the colors, quit if the text fits after all
+    // and leave the text on the screen
+    if env::var_os("LESS").is_none() {
+        command.env("LESS", "FRX");
+    }
+    let mut child = command.spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // Quitting the pager before reading everything closes the pipe,
+        // which is fine
+        let _ = stdin.write_all(text.as_bytes());
+    }
+    child.wait().map(|_| ())
+}
+
+// Print the text, exiting quietly if stdout was closed, e.g. by `head`
+fn print(text: &str) {
+    io::stdout()
+        .lock()
+        .write_all(text.as_bytes())
+        .unwrap_or_else(|e| match e.kind() {
+            io::ErrorKind::BrokenPipe => std::process::exit(0),
+            _ => std::process::exit(1),
+        });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_fits() {
+        let text = "one\ntwo\nthree\n";
+        assert!(fits(text, Some(4)));
+        assert!(!fits(text, Some(3)));
+        assert!(fits(text, None));
+    }
+}
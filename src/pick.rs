@@ -0,0 +1,229 @@
+// Picking an exercise by typing parts of its name, instead of copying names
+// between commands. The exercises are filtered as the learner types, the
+// arrow keys move the selection and Enter picks the selected exercise.
+// Misspelled names given on the command line are matched up with the
+// exercises here as well.
+use crate::exercise::Exercise;
+use crate::state::{ProgressState, Status};
+use crate::terminal::{self, KeyInput};
+use crate::ui;
+use console::style;
+
+// The rows taken up by the search line, the hint and the line after the list
+const RESERVED_ROWS: usize = 3;
+
+// Let the learner pick an exercise. Returns None if they cancelled, or if the
+// input isn't a terminal to read keys from.
+pub fn pick<'a>(exercises: &'a [Exercise], state: &ProgressState) -> Option<&'a Exercise> {
+    let _key_input = KeyInput::enable()?;
+    let mut query = String::new();
+    let mut selected = 0;
+    loop {
+        let matches = matching(exercises, &query);
+        selected = selected.min(matches.len().saturating_sub(1));
+        draw(&query, &matches, selected, state);
+
+        match terminal::read_key().ok()?? {
+            '\r' | '\n' => {
+                ui::clear_screen();
+                return matches.get(selected).copied();
+            }
+            // Ctrl-D
+            '\x04' => {
+                ui::clear_screen();
+                return None;
+            }
+            // Backspace
+            '\x7f' | '\x08' => {
+                query.pop();
+            }
+            // Ctrl-P and Ctrl-N
+            '\x10' => selected = selected.saturating_sub(1),
+            '\x0e' => selected += 1,
+            // The arrow keys, like "\x1B[A" for up
+            '\x1B' if terminal::read_key().ok()?? == '[' => match terminal::read_key().ok()?? {
+                'A' => selected = selected.saturating_sub(1),
+                'B' => selected += 1,
+                _ => {}
+            },
+            c if !c.is_control() => {
+                query.push(c);
+                selected = 0;
+            }
+            _ => {}
+        }
+    }
+}
+
+fn draw(query: &str, matches: &[&Exercise], selected: usize, state: &ProgressState) {
+    ui::clear_screen();
+    let (rows, _) = console::Term::stdout().size();
+    let shown = (rows as usize).saturating_sub(RESERVED_ROWS).max(1);
+    // Scroll the list so the selected exercise stays in view
+    let first = (selected + 1).saturating_sub(shown);
+    println!(
+        "{}",
+        style("Type to search, ↑/↓ to select, Enter to pick, Ctrl-D to cancel").dim()
+    );
+    for (i, exercise) in matches.iter().enumerate().skip(first).take(shown) {
+        let status = state.status(exercise);
+        let text = format!("{status:<7}");
+        let status = match status {
+            Status::Done => style(text).green(),
+            Status::Skipped => style(text).yellow(),
+            Status::Pending => style(text).red(),
+        };
+        let line = format!("{:<24} {status}", exercise.name);
+        if i == selected {
+            println!("{} {}", style(">").bold(), style(line).reverse());
+        } else {
+            println!("  {line}");
+        }
+    }
+    if matches.is_empty() {
+        println!("  No exercise matches");
+    }
+    print!("> {query}");
+    let _ = std::io::Write::flush(&mut std::io::stdout());
+}
+
+// The exercises matching the query, the best matches first
+fn matching<'a>(exercises: &'a [Exercise], query: &str) -> Vec<&'a Exercise> {
+    let mut scored: Vec<((usize, usize), &Exercise)> = exercises
+        .iter()
+        .filter_map(|exercise| {
+            // Every exercise is in there, so it would only match everything
+            let path = exercise
+                .path
+                .strip_prefix("exercises")
+                .unwrap_or(&exercise.path);
+            let path = path.to_string_lossy();
+            let score = match (
+                fuzzy_score(query, &exercise.name),
+                fuzzy_score(query, &path),
+            ) {
+                (Some(name), Some(path)) => name.min(path),
+                (score, None) | (None, score) => score?,
+            };
+            Some((score, exercise))
+        })
+        .collect();
+    // Sorting is stable, so equally good matches stay in the order of the course
+    scored.sort_by_key(|(score, _)| *score);
+    scored.into_iter().map(|(_, exercise)| exercise).collect()
+}
+
+// Whether the characters of the query appear in the text in the same order,
+// ignoring case. The score is the number of characters skipped between the
+// first and the last matched one, then where the first one is, so lower is
+// better and (0, 0) means the text starts with the query.
+fn fuzzy_score(query: &str, text: &str) -> Option<(usize, usize)> {
+    let text = text.to_lowercase();
+    let mut chars = text.char_indices();
+    let mut first = None;
+    let mut last = 0;
+    let mut matched = 0;
+    for q in query.to_lowercase().chars() {
+        let (i, _) = chars.find(|(_, c)| *c == q)?;
+        first.get_or_insert(i);
+        last = i;
+        matched += 1;
+    }
+    match first {
+        Some(first) => Some((last + 1 - first - matched, first)),
+        None => Some((0, 0)),
+    }
+}
+
+// The names of the exercises closest to a misspelled one, the closest first.
+// Only names that are a few typos away count, so unrelated exercises aren't
+// suggested.
+pub fn similar_names<'a>(name: &str, exercises: &'a [Exercise]) -> Vec<&'a str> {
+    let name = name.to_lowercase();
+    let max_distance = (name.chars().count() / 3).max(2);
+    let mut similar: Vec<(usize, &str)> = exercises
+        .iter()
+        .map(|exercise| {
+            let distance = edit_distance(&name, &exercise.name.to_lowercase());
+            (distance, exercise.name.as_str())
+        })
+        .filter(|(distance, _)| *distance <= max_distance)
+        .collect();
+    similar.sort_by_key(|(distance, _)| *distance);
+    similar.into_iter().take(3).map(|(_, name)| name).collect()
+}
+
+// The number of characters to insert, remove or replace to turn one text
+// into the other, also known as the Levenshtein distance
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    // The distances from the start of `a` read so far to every start of `b`
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            let replace = previous[j] + usize::from(a != *b);
+            current.push(replace.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert_eq!(fuzzy_score("", "intro1"), Some((0, 0)));
+        assert_eq!(fuzzy_score("intro", "intro1"), Some((0, 0)));
+        assert_eq!(fuzzy_score("ms2", "move_semantics2"), Some((12, 0)));
+        assert_eq!(fuzzy_score("RC", "arc1"), Some((0, 1)));
+        assert_eq!(fuzzy_score("xyz", "intro1"), None);
+        assert_eq!(fuzzy_score("1intro", "intro1"), None);
+    }
+
+    fn exercise(name: &str, path: &str) -> Exercise {
+        Exercise::new(name, path)
+    }
+
+    #[test]
+    fn test_matching() {
+        let exercises = [
+            exercise("arc1", "exercises/smart_pointers/arc1.rs"),
+            exercise("rc1", "exercises/smart_pointers/rc1.rs"),
+            exercise("intro1", "exercises/intro/intro1.rs"),
+        ];
+        let names = |query| -> Vec<String> {
+            matching(&exercises, query)
+                .iter()
+                .map(|exercise| exercise.name.clone())
+                .collect()
+        };
+        assert_eq!(names("rc"), ["rc1", "arc1"]);
+        assert!(names("xyz").is_empty());
+        assert_eq!(names("smartpoint"), ["arc1", "rc1"]);
+        assert_eq!(names("intr")[0], "intro1");
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("vecs1", "vecs1"), 0);
+        assert_eq!(edit_distance("vec1", "vecs1"), 1);
+        assert_eq!(edit_distance("itnro1", "intro1"), 2);
+        assert_eq!(edit_distance("", "rc1"), 3);
+    }
+
+    #[test]
+    fn test_similar_names() {
+        let exercises = [
+            exercise("vecs1", "exercises/vecs/vecs1.rs"),
+            exercise("vecs2", "exercises/vecs/vecs2.rs"),
+            exercise("intro1", "exercises/intro/intro1.rs"),
+        ];
+        assert_eq!(similar_names("vec1", &exercises), ["vecs1", "vecs2"]);
+        assert_eq!(similar_names("Intro1", &exercises), ["intro1"]);
+        assert!(similar_names("iterators5", &exercises).is_empty());
+    }
+}
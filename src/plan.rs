@@ -0,0 +1,117 @@
+// Planning a session of the course: which of the next exercises can be done
+// in the time the learner has, e.g. on a lunch break, going by how long
+// each of them is estimated to take.
+use crate::exercise::Exercise;
+use crate::state::{ProgressState, Status};
+
+// Like "45m" or "1h 5m"
+pub fn format_minutes(minutes: u32) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{minutes}m"),
+        (hours, minutes) => format!("{hours}h {minutes}m"),
+    }
+}
+
+// Propose the next exercises that fit into the given minutes. Optional
+// exercises are left out, like on the way through the course in `watch`.
+pub fn plan(exercises: &[Exercise], minutes: u32) -> Result<(), ()> {
+    let state = ProgressState::load();
+    let pending: Vec<&Exercise> = exercises
+        .iter()
+        .filter(|e| !e.optional && state.status(e) == Status::Pending)
+        .collect();
+    let Some(first) = pending.first() else {
+        println!("There are no exercises left to do, well done!");
+        return Ok(());
+    };
+    let planned = fitting(&pending, minutes);
+    if planned.is_empty() {
+        warn!(
+            "The next exercise, {}, likely takes {}, more than you have",
+            first.name,
+            format_minutes(first.estimated_minutes())
+        );
+        println!(
+            "Give yourself some more time, or start it anyway with `rustlings run {}`.",
+            first.name
+        );
+        return Err(());
+    }
+
+    println!(
+        "In {}, you can likely do these exercises:",
+        format_minutes(minutes)
+    );
+    let width = planned.iter().map(|e| e.name.len()).max().unwrap_or(0);
+    for exercise in &planned {
+        println!(
+            "  {:<width$}  {:>7}  {}",
+            exercise.name,
+            format_minutes(exercise.estimated_minutes()),
+            exercise.path.display()
+        );
+    }
+    let total: u32 = planned.iter().map(|e| e.estimated_minutes()).sum();
+    println!();
+    println!(
+        "That's about {}. Start with `rustlings watch`, and happy coding!",
+        format_minutes(total)
+    );
+    Ok(())
+}
+
+// The exercises from the start of the pending ones that fit into the budget.
+// The order of the course is kept, so a long exercise ends the plan even if
+// shorter ones after it would still fit.
+fn fitting<'a>(pending: &[&'a Exercise], minutes: u32) -> Vec<&'a Exercise> {
+    let mut left = minutes;
+    pending
+        .iter()
+        .map_while(|exercise| {
+            left = left.checked_sub(exercise.estimated_minutes())?;
+            Some(*exercise)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::exercise::Difficulty;
+
+    fn exercise(name: &str, estimated_minutes: Option<u32>) -> Exercise {
+        Exercise {
+            difficulty: Some(Difficulty::Hard),
+            estimated_minutes,
+            ..Exercise::new(name, format!("exercises/intro/{name}.rs"))
+        }
+    }
+
+    #[test]
+    fn test_format_minutes() {
+        assert_eq!(format_minutes(45), "45m");
+        assert_eq!(format_minutes(60), "1h 0m");
+        assert_eq!(format_minutes(125), "2h 5m");
+    }
+
+    #[test]
+    fn test_fitting() {
+        let exercises = [
+            exercise("a", Some(10)),
+            exercise("b", None),
+            exercise("c", Some(30)),
+            exercise("d", Some(1)),
+        ];
+        let pending: Vec<&Exercise> = exercises.iter().collect();
+        let names = |minutes| -> Vec<String> {
+            fitting(&pending, minutes)
+                .iter()
+                .map(|e| e.name.clone())
+                .collect()
+        };
+        assert_eq!(names(45), ["a", "b"]);
+        assert_eq!(names(30), ["a", "b"]);
+        assert_eq!(names(61), ["a", "b", "c", "d"]);
+        assert!(names(5).is_empty());
+    }
+}
@@ -0,0 +1,174 @@
+// Exercises can require others to be done first, with e.g.
+// `requires = ["move_semantics5"]` in `info.toml`. Custom courses use this
+// to order their exercises other than by their position in the file.
+use crate::exercise::Exercise;
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mark {
+    Unvisited,
+    Visiting,
+    Visited,
+}
+
+// Order the exercises so that every exercise comes after the ones it
+// requires. Otherwise, the exercises stay in the order of `info.toml`.
+pub fn order(exercises: Vec<Exercise>) -> Result<Vec<Exercise>, String> {
+    let indices: HashMap<&str, usize> = exercises
+        .iter()
+        .enumerate()
+        .map(|(i, exercise)| (exercise.name.as_str(), i))
+        .collect();
+    let mut marks = vec![Mark::Unvisited; exercises.len()];
+    let mut ordered = Vec::with_capacity(exercises.len());
+    let mut chain = Vec::new();
+    for i in 0..exercises.len() {
+        visit(
+            i,
+            &exercises,
+            &indices,
+            &mut marks,
+            &mut chain,
+            &mut ordered,
+        )?;
+    }
+
+    let mut exercises: Vec<Option<Exercise>> = exercises.into_iter().map(Some).collect();
+    Ok(ordered
+        .into_iter()
+        .filter_map(|i| exercises[i].take())
+        .collect())
+}
+
+// Add the exercise to the ordered ones after everything it requires. The
+// chain holds the exercises that are being visited, to report cycles.
+fn visit<'a>(
+    i: usize,
+    exercises: &'a [Exercise],
+    indices: &HashMap<&str, usize>,
+    marks: &mut [Mark],
+    chain: &mut Vec<&'a str>,
+    ordered: &mut Vec<usize>,
+) -> Result<(), String> {
+    let exercise = &exercises[i];
+    match marks[i] {
+        Mark::Visited => return Ok(()),
+        Mark::Visiting => {
+            let start = chain
+                .iter()
+                .position(|name| *name == exercise.name)
+                .unwrap_or(0);
+            let mut cycle = chain[start..].to_vec();
+            cycle.push(&exercise.name);
+            return Err(format!(
+                "The exercises require each other in a cycle: {}",
+                cycle.join(" -> ")
+            ));
+        }
+        Mark::Unvisited => {}
+    }
+    marks[i] = Mark::Visiting;
+    chain.push(&exercise.name);
+    for name in &exercise.requires {
+        let Some(&required) = indices.get(name.as_str()) else {
+            return Err(format!(
+                "{} requires {name}, but there's no exercise with that name",
+                exercise.name
+            ));
+        };
+        visit(required, exercises, indices, marks, chain, ordered)?;
+    }
+    chain.pop();
+    marks[i] = Mark::Visited;
+    ordered.push(i);
+    Ok(())
+}
+
+// All exercises that have to be done before the given one, directly or
+// through others, in the order they're done in
+pub fn prerequisites<'a>(exercise: &Exercise, exercises: &'a [Exercise]) -> Vec<&'a Exercise> {
+    let mut required: Vec<&str> = exercise.requires.iter().map(String::as_str).collect();
+    let mut next = 0;
+    while let Some(name) = required.get(next) {
+        next += 1;
+        let Some(found) = exercises.iter().find(|e| e.name == *name) else {
+            continue;
+        };
+        for name in &found.requires {
+            if !required.contains(&name.as_str()) {
+                required.push(name);
+            }
+        }
+    }
+    exercises
+        .iter()
+        .filter(|e| required.contains(&e.name.as_str()))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn exercise(name: &str, requires: &[&str]) -> Exercise {
+        Exercise {
+            requires: requires.iter().map(|name| name.to_string()).collect(),
+            ..Exercise::new(name, format!("exercises/{name}.rs"))
+        }
+    }
+
+    fn names(exercises: &[Exercise]) -> Vec<&str> {
+        exercises.iter().map(|e| e.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_order_keeps_file_order() {
+        let exercises = vec![
+            exercise("a", &[]),
+            exercise("b", &["a"]),
+            exercise("c", &[]),
+        ];
+        assert_eq!(names(&order(exercises).unwrap()), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_order_moves_prerequisites_first() {
+        let exercises = vec![
+            exercise("a", &["c"]),
+            exercise("b", &[]),
+            exercise("c", &["b"]),
+            exercise("d", &[]),
+        ];
+        assert_eq!(names(&order(exercises).unwrap()), ["b", "c", "a", "d"]);
+    }
+
+    #[test]
+    fn test_order_rejects_cycles_and_unknown_names() {
+        let cycle = vec![exercise("a", &["b"]), exercise("b", &["a"])];
+        assert_eq!(
+            order(cycle).err().unwrap(),
+            "The exercises require each other in a cycle: a -> b -> a"
+        );
+        let unknown = vec![exercise("a", &["z"])];
+        assert_eq!(
+            order(unknown).err().unwrap(),
+            "a requires z, but there's no exercise with that name"
+        );
+    }
+
+    #[test]
+    fn test_prerequisites() {
+        let exercises = order(vec![
+            exercise("a", &[]),
+            exercise("b", &["a"]),
+            exercise("c", &[]),
+            exercise("d", &["b"]),
+        ])
+        .unwrap();
+        let chain = prerequisites(&exercises[3], &exercises);
+        assert_eq!(
+            chain.iter().map(|e| e.name.as_str()).collect::<Vec<_>>(),
+            ["a", "b"]
+        );
+    }
+}
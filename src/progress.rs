@@ -0,0 +1,231 @@
+// Moving the progress to another machine.
+//
+// An export is a single JSON file with the state file, the sources of the
+// exercises that were worked on and the notes about them. Importing it into a
+// fresh copy of the course on the other machine picks up where the export
+// left off.
+use crate::backup::backup;
+use crate::exercise::Exercise;
+use crate::notes::{read_notes, write_notes};
+use crate::state::ProgressState;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+// The version of the export format, raised on incompatible changes. Version 2
+// added exercises made of several files.
+const VERSION: u32 = 2;
+
+// The source of an exercise
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug)]
+#[serde(untagged)]
+enum Source {
+    File(String),
+    // The files of an exercise made of several, by their path inside its directory
+    Files(BTreeMap<PathBuf, String>),
+}
+
+impl Source {
+    fn read(exercise: &Exercise) -> Option<Source> {
+        if !exercise.path.is_dir() {
+            return fs::read_to_string(&exercise.path).ok().map(Source::File);
+        }
+        exercise
+            .source_files()
+            .into_iter()
+            .map(|file| {
+                let contents = fs::read_to_string(&file).ok()?;
+                Some((
+                    file.strip_prefix(&exercise.path).ok()?.to_path_buf(),
+                    contents,
+                ))
+            })
+            .collect::<Option<_>>()
+            .map(Source::Files)
+    }
+
+    // Write the source over the exercise. Files of an exercise made of
+    // several can't be outside its directory.
+    fn write(&self, exercise: &Exercise) -> io::Result<()> {
+        match self {
+            Source::File(source) => fs::write(&exercise.path, source),
+            Source::Files(files) => {
+                for (file, source) in files {
+                    if !file
+                        .components()
+                        .all(|component| matches!(component, Component::Normal(_)))
+                    {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!("{} isn't inside the exercise", file.display()),
+                        ));
+                    }
+                    let path = exercise.path.join(file);
+                    path.parent().map_or(Ok(()), fs::create_dir_all)?;
+                    fs::write(path, source)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ProgressExport {
+    version: u32,
+    pub state: ProgressState,
+    // The sources of the exercises that were worked on, by exercise name
+    sources: BTreeMap<String, Source>,
+    // The notes about the exercises, by exercise name
+    #[serde(default)]
+    notes: BTreeMap<String, String>,
+}
+
+impl ProgressExport {
+    // The progress on the exercises. With `sources`, the sources of the
+    // exercises Rustlings has checked are included, those are the ones that
+    // were worked on.
+    pub fn collect(exercises: &[Exercise], sources: bool) -> ProgressExport {
+        let state = ProgressState::load();
+        let sources = exercises
+            .iter()
+            .filter(|exercise| sources && state.exercises.contains_key(&exercise.name))
+            .filter_map(|exercise| Some((exercise.name.clone(), Source::read(exercise)?)))
+            .collect();
+        let notes = exercises
+            .iter()
+            .filter_map(|exercise| Some((exercise.name.clone(), read_notes(exercise)?)))
+            .collect();
+        ProgressExport {
+            version: VERSION,
+            state,
+            sources,
+            notes,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.state.exercises.is_empty() && self.sources.is_empty() && self.notes.is_empty()
+    }
+}
+
+// Write the progress on the exercises to the given file
+pub fn export(exercises: &[Exercise], file: &Path) -> Result<(), ()> {
+    let export = ProgressExport::collect(exercises, true);
+    let count = export.sources.len();
+    let contents = serde_json::to_string_pretty(&export).expect("Failed to serialize progress");
+    fs::write(file, contents).map_err(|e| {
+        warn!("Couldn't write the progress to {}", file.display());
+        println!("{e}");
+    })?;
+    success!("Exported the progress on {} exercises", count);
+    println!(
+        "Run `rustlings progress import {}` on the other machine to continue there.",
+        file.display()
+    );
+    Ok(())
+}
+
+// Read a file written by `export`
+pub fn read_export(file: &Path) -> Result<ProgressExport, String> {
+    let contents = fs::read_to_string(file).map_err(|e| e.to_string())?;
+    parse_export(&contents)
+}
+
+// Read the contents of a file written by `export`
+pub fn parse_export(contents: &str) -> Result<ProgressExport, String> {
+    let export: ProgressExport = serde_json::from_str(contents).map_err(|e| e.to_string())?;
+    if export.version > VERSION {
+        return Err(
+            "It was exported by a newer version of Rustlings, please update Rustlings first."
+                .to_string(),
+        );
+    }
+    Ok(export)
+}
+
+// Restore the progress from a file written by `export`
+pub fn import(exercises: &[Exercise], file: &Path) -> Result<(), ()> {
+    let export = read_export(file).map_err(|e| {
+        warn!("Couldn't read the progress from {}", file.display());
+        println!("{e}");
+    })?;
+    let restored = apply(exercises, &export)?;
+    success!("Imported the progress on {} exercises", restored);
+    Ok(())
+}
+
+// Take over the progress of the export, returning on how many exercises.
+// Exercises that are overwritten are backed up first, so `rustlings unreset`
+// brings them back.
+pub fn apply(exercises: &[Exercise], export: &ProgressExport) -> Result<usize, ()> {
+    let mut state = ProgressState::load();
+    let mut restored = 0;
+    for exercise in exercises {
+        if let Some(source) = export.sources.get(&exercise.name) {
+            let current = Source::read(exercise);
+            if current.as_ref() != Some(source) {
+                if exercise.path.exists() && backup(exercise).is_err() {
+                    warn!("Not restoring {}, since it couldn't be backed up", exercise);
+                    continue;
+                }
+                if let Err(e) = source.write(exercise) {
+                    warn!("Couldn't restore {}", exercise);
+                    println!("{e}");
+                    continue;
+                }
+            }
+            restored += 1;
+        }
+        if let Some(progress) = export.state.exercises.get(&exercise.name) {
+            state
+                .exercises
+                .insert(exercise.name.clone(), progress.clone());
+        }
+        // Notes taken on this machine aren't replaced
+        if let Some(notes) = export.notes.get(&exercise.name) {
+            if read_notes(exercise).is_none() {
+                let _ = write_notes(exercise, notes);
+            }
+        }
+    }
+    state.save().map_err(|e| {
+        warn!("Couldn't save the imported progress: {}", e);
+    })?;
+    Ok(restored)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sources_of_version_1_are_read() {
+        let export = parse_export(
+            r#"{ "version": 1, "state": {}, "sources": { "intro1": "fn main() {}\n" } }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            export.sources["intro1"],
+            Source::File("fn main() {}\n".to_string())
+        );
+    }
+
+    #[test]
+    fn test_files_outside_the_exercise_are_refused() {
+        let dir = std::env::temp_dir().join(format!("rustlings-source-{}", std::process::id()));
+        let exercise = Exercise {
+            path: dir.join("exercise"),
+            ..Exercise::new("exercise", "")
+        };
+        let source = Source::Files(BTreeMap::from([(
+            PathBuf::from("../outside.rs"),
+            String::new(),
+        )]));
+        let written = source.write(&exercise);
+        assert!(written.is_err());
+        assert!(!dir.join("outside.rs").exists());
+    }
+}
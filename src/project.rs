@@ -7,7 +7,7 @@ use std::process::Command;
 
 /// Contains the structure of resulting rust-project.json file
 /// and functions to build the data required to create the file
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Default)]
 pub struct RustAnalyzerProject {
     sysroot_src: String,
     pub crates: Vec<Crate>,
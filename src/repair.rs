@@ -0,0 +1,96 @@
+// Restoring exercises whose files went missing, e.g. after a chapter was
+// deleted by accident. Only missing files are restored from git, exercises
+// that are still there are left alone, whatever was changed in them.
+use crate::exercise::Exercise;
+use crate::git;
+use std::fs;
+use std::path::PathBuf;
+
+// Whether a file of the exercise doesn't exist, like the main file of an
+// exercise made of several files
+pub fn is_missing(exercise: &Exercise) -> bool {
+    exercise.source_files().iter().any(|file| !file.exists())
+}
+
+// The exercises with a file that doesn't exist
+pub fn missing(exercises: &[Exercise]) -> Vec<&Exercise> {
+    exercises.iter().filter(|e| is_missing(e)).collect()
+}
+
+// Write the committed version of every file of the exercise that doesn't
+// exist, returning how many were restored
+fn restore(exercise: &Exercise) -> Result<usize, String> {
+    let files = git::committed_files(&exercise.path);
+    if files.is_empty() {
+        return Err(format!(
+            "Couldn't find the original version of {exercise} in git"
+        ));
+    }
+    let mut restored = 0;
+    for (file, original) in files.into_iter().filter(|(file, _)| !file.exists()) {
+        file.parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&file, original))
+            .map_err(|e| format!("Couldn't restore {}: {e}", file.display()))?;
+        restored += 1;
+    }
+    Ok(restored)
+}
+
+// Check that no exercise is missing its file, before that leads to confusing
+// errors halfway through the course
+pub fn ensure_complete(exercises: &[Exercise]) -> Result<(), ()> {
+    let missing = missing(exercises);
+    if missing.is_empty() {
+        return Ok(());
+    }
+    warn!("{} exercises are missing their file:", missing.len());
+    for exercise in missing {
+        println!("  {exercise}");
+    }
+    println!("Run `rustlings repair` to restore them.");
+    Err(())
+}
+
+// Write the committed version of every exercise that's missing its file
+pub fn repair(exercises: &[Exercise]) -> Result<(), ()> {
+    let missing = missing(exercises);
+    if missing.is_empty() {
+        success!(
+            "All {} exercises are in place, nothing to repair",
+            exercises.len()
+        );
+        return Ok(());
+    }
+
+    let mut failed = 0;
+    for exercise in &missing {
+        match restore(exercise) {
+            Ok(_) => println!("Restored {exercise}"),
+            Err(e) => {
+                warn!("{}", e);
+                failed += 1;
+            }
+        }
+    }
+    // The introductions of the chapters go missing along with their exercises
+    let mut readmes: Vec<PathBuf> = missing
+        .iter()
+        .filter_map(|exercise| Some(exercise.path.parent()?.join("README.md")))
+        .filter(|readme| !readme.exists())
+        .collect();
+    readmes.dedup();
+    for readme in readmes {
+        if let Some(original) = git::committed_version(&readme) {
+            if fs::write(&readme, original).is_ok() {
+                println!("Restored {}", readme.display());
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(());
+    }
+    success!("Restored {} exercises", missing.len());
+    Ok(())
+}
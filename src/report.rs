@@ -0,0 +1,320 @@
+// A report of the progress to share with others, e.g. as proof of progress
+// for a course. It's written as Markdown or HTML, depending on the file name.
+use crate::chapter::chapters;
+use crate::exercise::Exercise;
+use crate::state::{ProgressState, Status};
+use crate::stats::format_duration;
+use crate::util::escape_html;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum ReportFormat {
+    Markdown,
+    Html,
+}
+
+impl ReportFormat {
+    fn from_path(path: &Path) -> Option<ReportFormat> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "md" | "markdown" => Some(ReportFormat::Markdown),
+            "html" | "htm" => Some(ReportFormat::Html),
+            _ => None,
+        }
+    }
+}
+
+// The progress on an exercise as it appears in the report
+struct ExerciseReport<'a> {
+    name: &'a str,
+    // The title of the chapter, empty for exercises outside of the chapters
+    chapter: String,
+    status: Status,
+    attempts: u32,
+    // When the exercise was solved, in seconds since the Unix epoch
+    solved_at: Option<u64>,
+    time_to_solve: Option<Duration>,
+}
+
+// How many exercises of a chapter are done
+struct ChapterReport {
+    name: String,
+    done: usize,
+    total: usize,
+}
+
+// Write a report of the progress on the exercises to the given file
+pub fn report(exercises: &[Exercise], output: &Path) -> Result<(), ()> {
+    let Some(format) = ReportFormat::from_path(output) else {
+        warn!(
+            "Don't know how to write {}, the report can be written to .md or .html files",
+            output.display()
+        );
+        return Err(());
+    };
+
+    let state = ProgressState::load();
+    let chapters = chapters(exercises);
+    let chapter_reports: Vec<ChapterReport> = chapters
+        .iter()
+        .map(|chapter| ChapterReport {
+            name: chapter.title(),
+            done: chapter
+                .exercises
+                .iter()
+                .filter(|exercise| state.is_done(exercise))
+                .count(),
+            total: chapter.exercises.len(),
+        })
+        .collect();
+    let entries: Vec<ExerciseReport> = exercises
+        .iter()
+        .map(|exercise| {
+            let progress = state.exercises.get(&exercise.name);
+            let chapter = chapters
+                .iter()
+                .find(|chapter| chapter.exercises.iter().any(|e| e.name == exercise.name));
+            ExerciseReport {
+                name: &exercise.name,
+                chapter: chapter.map(|chapter| chapter.title()).unwrap_or_default(),
+                status: state.status(exercise),
+                attempts: progress.map_or(0, |progress| progress.attempts),
+                solved_at: progress.and_then(|progress| progress.solved_at),
+                time_to_solve: progress.and_then(|progress| progress.time_to_solve()),
+            }
+        })
+        .collect();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_secs());
+
+    let contents = match format {
+        ReportFormat::Markdown => markdown(&entries, &chapter_reports, now),
+        ReportFormat::Html => html(&entries, &chapter_reports, now),
+    };
+    fs::write(output, contents).map_err(|e| {
+        warn!("Couldn't write the report to {}", output.display());
+        println!("{e}");
+    })
+}
+
+fn percentage(done: usize, total: usize) -> String {
+    format!("{:.1} %", done as f32 / total.max(1) as f32 * 100.0)
+}
+
+// The row of an exercise in the tables of the report
+fn exercise_cells(entry: &ExerciseReport) -> [String; 6] {
+    [
+        entry.name.to_string(),
+        entry.chapter.clone(),
+        entry.status.to_string(),
+        entry.attempts.to_string(),
+        entry.solved_at.map(format_timestamp).unwrap_or_default(),
+        entry.time_to_solve.map(format_duration).unwrap_or_default(),
+    ]
+}
+
+const EXERCISE_HEADER: [&str; 6] = [
+    "Exercise",
+    "Chapter",
+    "Status",
+    "Failed attempts",
+    "Solved on",
+    "Time to solve",
+];
+
+fn markdown(entries: &[ExerciseReport], chapters: &[ChapterReport], now: u64) -> String {
+    let done = entries.iter().filter(|e| e.status == Status::Done).count();
+    let mut out = String::new();
+    let _ = writeln!(out, "# Rustlings progress\n");
+    let _ = writeln!(out, "Generated on {}.\n", format_timestamp(now));
+    let _ = writeln!(
+        out,
+        "Completed **{done} / {} exercises** ({}).\n",
+        entries.len(),
+        percentage(done, entries.len())
+    );
+
+    let _ = writeln!(out, "## Chapters\n");
+    let _ = writeln!(out, "| Chapter | Done | Progress |");
+    let _ = writeln!(out, "| --- | --- | --- |");
+    for chapter in chapters {
+        let _ = writeln!(
+            out,
+            "| {} | {} / {} | {} |",
+            markdown_cell(&chapter.name),
+            chapter.done,
+            chapter.total,
+            percentage(chapter.done, chapter.total)
+        );
+    }
+
+    let _ = writeln!(out, "\n## Exercises\n");
+    let _ = writeln!(out, "| {} |", EXERCISE_HEADER.join(" | "));
+    let _ = writeln!(out, "|{}", " --- |".repeat(EXERCISE_HEADER.len()));
+    for entry in entries {
+        let cells: Vec<String> = exercise_cells(entry)
+            .iter()
+            .map(|cell| markdown_cell(cell))
+            .collect();
+        let _ = writeln!(out, "| {} |", cells.join(" | "));
+    }
+    out
+}
+
+fn markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+fn html(entries: &[ExerciseReport], chapters: &[ChapterReport], now: u64) -> String {
+    let done = entries.iter().filter(|e| e.status == Status::Done).count();
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str("<title>Rustlings progress</title>\n<style>\n");
+    out.push_str(
+        "body { font-family: sans-serif; max-width: 60em; margin: auto; padding: 1em; }\n",
+    );
+    out.push_str("td, th { text-align: left; padding: 0.2em 1em 0.2em 0; }\n");
+    out.push_str("</style>\n</head>\n<body>\n<h1>Rustlings progress</h1>\n");
+    let _ = writeln!(out, "<p>Generated on {}.</p>", format_timestamp(now));
+    let _ = writeln!(
+        out,
+        "<p><progress value=\"{done}\" max=\"{total}\"></progress> Completed <strong>{done} / {total} exercises</strong> ({}).</p>",
+        percentage(done, entries.len()),
+        total = entries.len()
+    );
+
+    out.push_str(
+        "<h2>Chapters</h2>\n<table>\n<tr><th>Chapter</th><th>Done</th><th>Progress</th></tr>\n",
+    );
+    for chapter in chapters {
+        let _ = writeln!(
+            out,
+            "<tr><td>{}</td><td>{} / {}</td><td>{}</td></tr>",
+            escape_html(&chapter.name),
+            chapter.done,
+            chapter.total,
+            percentage(chapter.done, chapter.total)
+        );
+    }
+    out.push_str("</table>\n<h2>Exercises</h2>\n<table>\n<tr>");
+    for heading in EXERCISE_HEADER {
+        let _ = write!(out, "<th>{heading}</th>");
+    }
+    out.push_str("</tr>\n");
+    for entry in entries {
+        out.push_str("<tr>");
+        for cell in exercise_cells(entry) {
+            let _ = write!(out, "<td>{}</td>", escape_html(&cell));
+        }
+        out.push_str("</tr>\n");
+    }
+    out.push_str("</table>\n</body>\n</html>\n");
+    out
+}
+
+// Format seconds since the Unix epoch as a date and time in UTC
+fn format_timestamp(secs: u64) -> String {
+    let (days, secs) = (secs / 86400, secs % 86400);
+    // Convert days since the epoch into a civil date, see
+    // https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02} UTC",
+        secs / 3600,
+        secs % 3600 / 60
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00 UTC");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00 UTC");
+        assert_eq!(format_timestamp(1_718_454_645), "2024-06-15 12:30 UTC");
+    }
+
+    #[test]
+    fn test_report_format_from_path() {
+        assert_eq!(
+            ReportFormat::from_path(Path::new("progress.md")),
+            Some(ReportFormat::Markdown)
+        );
+        assert_eq!(
+            ReportFormat::from_path(Path::new("out/Progress.HTML")),
+            Some(ReportFormat::Html)
+        );
+        assert_eq!(ReportFormat::from_path(Path::new("progress.pdf")), None);
+    }
+
+    fn entry<'a>(name: &'a str, chapter: &str, status: Status) -> ExerciseReport<'a> {
+        ExerciseReport {
+            name,
+            chapter: chapter.to_string(),
+            status,
+            attempts: 2,
+            solved_at: None,
+            time_to_solve: None,
+        }
+    }
+
+    fn intro() -> ChapterReport {
+        ChapterReport {
+            name: "Intro".to_string(),
+            done: 1,
+            total: 2,
+        }
+    }
+
+    #[test]
+    fn test_markdown_report() {
+        let entries = [
+            entry("intro1", "Intro", Status::Done),
+            entry("intro2", "Intro", Status::Pending),
+            entry("quiz1", "", Status::Skipped),
+        ];
+        let report = markdown(&entries, &[intro()], 0);
+        assert!(report.contains("Generated on 1970-01-01 00:00 UTC."));
+        assert!(report.contains("Completed **1 / 3 exercises** (33.3 %)."));
+        assert!(report.contains("| Intro | 1 / 2 | 50.0 % |"));
+        assert!(report.contains("| intro2 | Intro | Pending | 2 |  |  |"));
+        assert!(report.contains("| quiz1 |  | Skipped | 2 |  |  |"));
+    }
+
+    #[test]
+    fn test_html_report() {
+        let entries = [
+            entry("intro1", "Intro", Status::Done),
+            entry("a<b", "Intro", Status::Pending),
+        ];
+        let report = html(&entries, &[intro()], 0);
+        assert!(report.contains("<progress value=\"1\" max=\"2\"></progress>"));
+        assert!(report.contains("<tr><td>Intro</td><td>1 / 2</td><td>50.0 %</td></tr>"));
+        assert!(report.contains("<td>a&lt;b</td>"));
+        assert!(report.ends_with("</html>\n"));
+    }
+
+    #[test]
+    fn test_markdown_cell() {
+        assert_eq!(markdown_cell("a|b"), "a\\|b");
+    }
+}
@@ -0,0 +1,145 @@
+// Practicing solved exercises again, without touching the solutions.
+//
+// Fresh copies of a few random solved exercises are written to a temporary
+// directory, which gets its own state file, so watch mode can run there as if
+// it were a new course.
+use crate::exercise::Exercise;
+use crate::state::{ProgressState, Status};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// A temporary copy of the course with the exercises to review
+pub struct Review {
+    pub dir: PathBuf,
+    pub exercises: Vec<Exercise>,
+}
+
+impl Drop for Review {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+// Pick up to `count` random solved exercises and write their original
+// versions to a temporary directory. The paths of the exercises stay the same
+// relative to that directory.
+pub fn prepare(exercises: &[Exercise], count: usize) -> Result<Review, ()> {
+    let state = ProgressState::load();
+    let mut solved: Vec<&Exercise> = exercises
+        .iter()
+        .filter(|exercise| state.status(exercise) == Status::Done)
+        .collect();
+    if solved.is_empty() {
+        warn!(
+            "None of the {} exercises are solved yet, so there's nothing to review",
+            exercises.len()
+        );
+        return Err(());
+    }
+    let seed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(1, |time| time.as_nanos() as u64);
+    shuffle(&mut solved, seed);
+
+    let dir = std::env::temp_dir().join(format!("rustlings-review-{}", std::process::id()));
+    let mut review = Review {
+        dir,
+        exercises: Vec::new(),
+    };
+    fs::create_dir_all(review.dir.join("exercises")).map_err(|e| {
+        warn!("Couldn't create {}", review.dir.display());
+        println!("{e}");
+    })?;
+    for exercise in solved {
+        if review.exercises.len() == count {
+            break;
+        }
+        // Exercises made of several files are copied file by file
+        let Some(sources) = exercise
+            .source_files()
+            .into_iter()
+            .map(|file| Some((review.dir.join(&file), original_source(&file)?)))
+            .collect::<Option<Vec<_>>>()
+        else {
+            warn!(
+                "Skipping {}, since its original version isn't in git",
+                exercise
+            );
+            continue;
+        };
+        let written = sources.iter().try_for_each(|(path, source)| {
+            path.parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|_| fs::write(path, source))
+        });
+        if let Err(e) = written {
+            warn!("Couldn't copy {} for the review", exercise);
+            println!("{e}");
+            continue;
+        }
+        review.exercises.push(exercise.clone());
+    }
+    if review.exercises.is_empty() {
+        return Err(());
+    }
+    Ok(review)
+}
+
+// The file of an exercise as it was added to the git repository, which is
+// what `rustlings reset` would bring back
+fn original_source(file: &Path) -> Option<String> {
+    let path = file.to_str()?;
+    let log = Command::new("git")
+        .args(["log", "--diff-filter=A", "--format=%H", "--", path])
+        .output()
+        .ok()?;
+    let stdout = String::from_utf8_lossy(&log.stdout);
+    // The log starts with the latest commit, the one that added it last
+    let commit = stdout.lines().next()?;
+    let show = Command::new("git")
+        .arg("show")
+        .arg(format!("{commit}:./{}", path.trim_start_matches("./")))
+        .output()
+        .ok()?;
+    if !show.status.success() {
+        return None;
+    }
+    String::from_utf8(show.stdout).ok()
+}
+
+// Shuffle the items with a xorshift generator, which is random enough to
+// pick exercises
+fn shuffle<T>(items: &mut [T], seed: u64) {
+    let mut state = seed.max(1);
+    for i in (1..items.len()).rev() {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        items.swap(i, (state % (i as u64 + 1)) as usize);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_shuffle_keeps_items() {
+        let mut items: Vec<u32> = (0..20).collect();
+        shuffle(&mut items, 42);
+        assert_ne!(items, (0..20).collect::<Vec<_>>());
+        items.sort();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_shuffle_depends_on_seed() {
+        let mut first: Vec<u32> = (0..20).collect();
+        let mut second = first.clone();
+        shuffle(&mut first, 1);
+        shuffle(&mut second, 2);
+        assert_ne!(first, second);
+    }
+}
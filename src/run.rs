@@ -1,25 +1,83 @@
+use console::{style, Color};
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::process::Command;
-use std::time::Duration;
 
-use crate::exercise::{Exercise, Mode};
-use crate::verify::test;
-use indicatif::ProgressBar;
+use crate::backup::{backup, latest_backup, remove, restore};
+use crate::diagnostics::tidy;
+use crate::exercise::{
+    rust_files, wasm_runtime_installed, Exercise, ExerciseOutput, Mode, RunInput,
+};
+use crate::git;
+use crate::markdown;
+use crate::pager;
+use crate::ui;
+use crate::verify::{check_style, test, VerifyOptions};
 
 // Invoke the rust compiler on the path of the given exercise,
 // and run the ensuing binary.
-// The verbose argument helps determine whether or not to show
+// The verbose option helps determine whether or not to show
 // the output from the test harnesses (if the mode of the exercise is test)
-pub fn run(exercise: &Exercise, verbose: bool) -> Result<(), ()> {
+pub fn run(
+    exercise: &Exercise,
+    options: &VerifyOptions,
+    target: Option<&str>,
+    input: &RunInput,
+) -> Result<(), ()> {
+    if let Some(target) = target.filter(|target| !runs_on_host(Some(target))) {
+        return compile_for_target(exercise, target);
+    }
     match exercise.mode {
-        Mode::Test => test(exercise, verbose)?,
-        Mode::Compile => compile_and_run(exercise)?,
-        Mode::Clippy => compile_and_run(exercise)?,
+        Mode::Test | Mode::Async => test(exercise, options, input)?,
+        Mode::Compile => compile_and_run(exercise, options, input)?,
+        Mode::Clippy => compile_and_run(exercise, options, input)?,
+        Mode::Wasm => compile_and_run(exercise, options, input)?,
     }
     Ok(())
 }
 
+// Back up the exercise before resetting it, so a reset can be undone
+fn backup_before_reset(exercise: &Exercise) -> Result<(), ()> {
+    if !exercise.path.exists() {
+        return Ok(());
+    }
+    backup(exercise).map(|_| ()).map_err(|e| {
+        warn!("Not resetting {}, since it couldn't be backed up", exercise);
+        println!("{e}");
+    })
+}
+
+// Show what resetting the exercises would throw away, as a diff between
+// each changed file of the exercises and its original version. Returns how
+// many of the exercises were changed.
+pub fn show_reset_changes(exercises: &[&Exercise]) -> usize {
+    let mut changed = 0;
+    for exercise in exercises {
+        let mut exercise_changed = false;
+        for (file, original) in git::committed_files(&exercise.path) {
+            // A deleted file is restored by the reset as well
+            let current = fs::read_to_string(&file).unwrap_or_default();
+            let path = file.display();
+            if ui::print_diff(
+                &current,
+                &original,
+                &format!("{path} (yours)"),
+                &format!("{path} (original)"),
+            ) {
+                exercise_changed = true;
+                println!();
+            }
+        }
+        if exercise_changed {
+            changed += 1;
+        }
+    }
+    changed
+}
+
 // Resets the exercise by stashing the changes.
 pub fn reset(exercise: &Exercise) -> Result<(), ()> {
+    backup_before_reset(exercise)?;
     let command = Command::new("git")
         .args(["stash", "--"])
         .arg(&exercise.path)
@@ -31,44 +89,318 @@ pub fn reset(exercise: &Exercise) -> Result<(), ()> {
     }
 }
 
+// Resets several exercises at once, stashing all their changes together
+pub fn reset_many(exercises: &[&Exercise]) -> Result<(), ()> {
+    for exercise in exercises {
+        backup_before_reset(exercise)?;
+    }
+    let status = Command::new("git")
+        .args(["stash", "push", "--"])
+        .args(exercises.iter().map(|exercise| &exercise.path))
+        .status();
+
+    match status {
+        Ok(status) if status.success() => {
+            success!("Reset {} exercises", exercises.len());
+            Ok(())
+        }
+        _ => Err(()),
+    }
+}
+
+// Restores the exercise from the backup taken by the latest reset
+pub fn unreset(exercise: &Exercise) -> Result<(), ()> {
+    let Some(backup) = latest_backup(exercise) else {
+        warn!("There is no backup of {} to restore", exercise);
+        return Err(());
+    };
+    restore(&backup, exercise).map_err(|_| ())?;
+    // The backup was restored, so the next unreset goes back one reset further
+    let _ = remove(&backup);
+    success!("Restored {} from before it was reset", exercise);
+    Ok(())
+}
+
+// Overwrites the exercise with its reference solution, but only if the
+// exercise already looks done, so no in-progress work gets spoiled.
+pub fn reset_from_solution(exercise: &Exercise) -> Result<(), ()> {
+    if !exercise.looks_done() {
+        warn!(
+            "Refusing to write the solution: {} isn't done yet",
+            exercise
+        );
+        return Err(());
+    }
+
+    let solution_path = exercise.solution_path();
+    let Some(files) = read_solution(exercise) else {
+        warn!("No solution found at {}", solution_path.display());
+        return Err(());
+    };
+
+    let matches = files
+        .iter()
+        .all(|(file, solution)| fs::read_to_string(file).ok().as_ref() == Some(solution));
+    let no_other_files = !exercise.path.is_dir() || rust_files(&exercise.path).len() == files.len();
+    if matches && no_other_files {
+        println!("{exercise} already matches its solution, nothing to do.");
+        return Ok(());
+    }
+
+    backup_before_reset(exercise)?;
+    // The solution takes the place of the exercise like a backup would
+    restore(&solution_path, exercise).map_err(|_| ())?;
+    success!("Replaced {} with its solution", exercise);
+    Ok(())
+}
+
+// The files of the exercise along with their solutions. The solution of an
+// exercise made of several files is a directory of them as well. Returns
+// None if any of the solutions can't be read.
+fn read_solution(exercise: &Exercise) -> Option<Vec<(PathBuf, String)>> {
+    let solution_path = exercise.solution_path();
+    if !solution_path.is_dir() {
+        let solution = fs::read_to_string(&solution_path).ok()?;
+        return Some(vec![(exercise.path.clone(), solution)]);
+    }
+    rust_files(&solution_path)
+        .into_iter()
+        .map(|file| {
+            let solution = fs::read_to_string(&file).ok()?;
+            let relative = file.strip_prefix(&solution_path).ok()?;
+            Some((exercise.path.join(relative), solution))
+        })
+        .collect()
+}
+
+// Prints the reference solution of the exercise, or how it differs from
+// the user's version of the exercise if `diff` is set
+pub fn solution(exercise: &Exercise, diff: bool) -> Result<(), ()> {
+    let solution_path = exercise.solution_path();
+    let Some(files) = read_solution(exercise) else {
+        warn!("No solution found at {}", solution_path.display());
+        return Err(());
+    };
+
+    if !crate::ui::confirm(&format!(
+        "Do you really want to see the solution for {}?",
+        exercise.name
+    )) {
+        println!("Keep at it, you can do this!");
+        return Ok(());
+    }
+    println!();
+
+    let solution_name = |file: &Path| match file.strip_prefix(&exercise.path) {
+        Ok(relative) if !relative.as_os_str().is_empty() => solution_path.join(relative),
+        _ => solution_path.clone(),
+    };
+    if diff {
+        let diff: String = files
+            .iter()
+            .map(|(file, solution)| {
+                let attempt = fs::read_to_string(file).unwrap_or_default();
+                let attempt_name = file.display().to_string();
+                let solution_name = solution_name(file).display().to_string();
+                ui::diff(&attempt, solution, &attempt_name, &solution_name)
+            })
+            .collect();
+        if diff.is_empty() {
+            println!("Your version of {exercise} is identical to the solution.");
+        } else {
+            pager::page(&diff);
+        }
+    } else {
+        let mut out = String::new();
+        for (file, solution) in &files {
+            // Exercises of several files get a header for each of them,
+            // after the empty line that ends the previous one
+            if files.len() > 1 {
+                out.push_str(&format!(
+                    "{}\n",
+                    style(solution_name(file).display()).bold()
+                ));
+            }
+            out.push_str(&format!("{solution}\n"));
+        }
+        pager::page(&out);
+    }
+    Ok(())
+}
+
+// Prints the source of the exercise with highlighting and line numbers, or
+// the version of it that's committed to git if `original` is set
+pub fn show(exercise: &Exercise, original: bool) -> Result<(), ()> {
+    let files = exercise.source_files();
+    let mut out = String::new();
+    for file in &files {
+        let source = if original {
+            git::committed_version(file).ok_or_else(|| {
+                warn!(
+                    "Couldn't find the original version of {} in git",
+                    file.display()
+                );
+            })?
+        } else {
+            fs::read_to_string(file).map_err(|e| {
+                warn!("Couldn't read {}", file.display());
+                println!("{e}");
+            })?
+        };
+        // Exercises of several files get a header for each of them
+        if files.len() > 1 {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str(&format!("{}\n", style(file.display()).bold()));
+        }
+        out.push_str(&numbered(&source));
+    }
+    pager::page(&out);
+    Ok(())
+}
+
+// The lines of the Rust code, highlighted and with their numbers
+fn numbered(source: &str) -> String {
+    let width = source.lines().count().to_string().len();
+    source
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            format!(
+                "{:>width$} {}  {}\n",
+                style(i + 1).blue().bold(),
+                style("|").blue(),
+                markdown::highlight_line(line)
+            )
+        })
+        .collect()
+}
+
 // Invoke the rust compiler on the path of the given exercise
 // and run the ensuing binary.
 // This is strictly for non-test binaries, so output is displayed
-fn compile_and_run(exercise: &Exercise) -> Result<(), ()> {
-    let progress_bar = ProgressBar::new_spinner();
-    progress_bar.set_message(format!("Compiling {exercise}..."));
-    progress_bar.enable_steady_tick(Duration::from_millis(100));
+fn compile_and_run(
+    exercise: &Exercise,
+    options: &VerifyOptions,
+    input: &RunInput,
+) -> Result<(), ()> {
+    let progress_bar = ui::spinner(format!("Compiling {exercise}..."));
 
     let compilation_result = exercise.compile();
     let compilation = match compilation_result {
         Ok(compilation) => compilation,
         Err(output) => {
             progress_bar.finish_and_clear();
-            warn!(
-                "Compilation of {} failed!, Compiler error message:\n",
-                exercise
-            );
-            println!("{}", output.stderr);
+            warn!("Compilation of {} failed!", exercise);
+            print_compiler_messages(&output.stderr);
             return Err(());
         }
     };
 
+    check_style(exercise, options, &progress_bar).map_err(|_| ())?;
+
     progress_bar.set_message(format!("Running {exercise}..."));
-    let result = compilation.run();
+    let result = compilation.run_with(input, options.timeout);
     progress_bar.finish_and_clear();
 
     match result {
+        Ok(_) if matches!(exercise.mode, Mode::Wasm) && !wasm_runtime_installed() => {
+            println!("Note: wasmtime isn't installed, so {exercise} was only built, not run.");
+            success!("Successfully compiled {}", exercise);
+            Ok(())
+        }
         Ok(output) => {
-            println!("{}", output.stdout);
+            print_program_output(&output);
             success!("Successfully ran {}", exercise);
             Ok(())
         }
         Err(output) => {
-            println!("{}", output.stdout);
-            println!("{}", output.stderr);
-
+            print_program_output(&output);
             warn!("Ran {} with errors", exercise);
             Err(())
         }
     }
 }
+
+// Print what the compiler reported, so it's not mistaken for what the
+// exercise printed
+fn print_compiler_messages(stderr: &str) {
+    ui::print_section("Compiler messages", &tidy(stderr), Color::Yellow);
+}
+
+// Print what the exercise printed to stdout and to stderr apart from each other
+fn print_program_output(output: &ExerciseOutput) {
+    ui::print_section("Program output", &output.stdout, Color::Cyan);
+    ui::print_section("Program errors", &output.stderr, Color::Red);
+}
+
+// Whether exercises built for the target are run, or only built since the
+// target isn't the one of this machine
+pub fn runs_on_host(target: Option<&str>) -> bool {
+    target.is_none_or(|target| host_target().as_deref() == Some(target))
+}
+
+// Determine the target triple of the host from `rustc -vV`
+fn host_target() -> Option<String> {
+    let output = Command::new("rustc").arg("-vV").output().ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(|line| line.strip_prefix("host: "))
+        .map(|host| host.trim().to_string())
+}
+
+// Check with rustup whether the standard library for the given target is installed.
+// Returns None if rustup isn't available, so we can't tell.
+pub fn target_installed(target: &str) -> Option<bool> {
+    let output = Command::new("rustup")
+        .args(["target", "list", "--installed"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .any(|line| line.trim() == target),
+    )
+}
+
+// Build the exercise for a non-native target.
+// The resulting binary can't be run on this machine, so we only build it.
+fn compile_for_target(exercise: &Exercise, target: &str) -> Result<(), ()> {
+    let progress_bar = ui::spinner(format!("Compiling {exercise} for {target}..."));
+
+    let compilation_result = exercise.compile_for(Some(target));
+    progress_bar.finish_and_clear();
+
+    match compilation_result {
+        Ok(_) => {
+            if !matches!(exercise.mode, Mode::Clippy) {
+                println!(
+                    "Note: {target} isn't the host target, so {exercise} was only built, not run."
+                );
+            }
+            success!("Successfully compiled {}", exercise);
+            Ok(())
+        }
+        Err(output) => {
+            warn!("Compilation of {} failed!", exercise);
+            print_compiler_messages(&output.stderr);
+            Err(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_runs_on_host() {
+        assert!(runs_on_host(None));
+        assert!(runs_on_host(host_target().as_deref()));
+        assert!(!runs_on_host(Some("not-a-real-target")));
+    }
+}
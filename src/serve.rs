@@ -0,0 +1,246 @@
+// A dashboard of the progress in the browser, e.g. for projecting it in a
+// classroom or for following along next to watch mode.
+//
+// The page reloads itself every few seconds and reads the state file anew
+// every time, so it keeps up with `watch` and `verify` running elsewhere.
+use crate::exercise::{Exercise, Mode, State};
+use crate::state::{file_hash, ProgressState, Status};
+use crate::ui;
+use crate::util::escape_html;
+use crate::verify::VerifyOptions;
+use std::fmt::Write as _;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::Duration;
+
+// How often the page reloads itself, in seconds
+const REFRESH_SECS: u32 = 2;
+// How long a browser may take to send its request. Connections are handled
+// one at a time, so one that stays silent mustn't hold up the others.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+// An exercise in the list on the page
+struct Row<'a> {
+    name: &'a str,
+    path: String,
+    status: Status,
+}
+
+// The exercise to work on next and what went wrong when checking it
+struct Current<'a> {
+    exercise: &'a Exercise,
+    // The output of the compiler or the exercise, if it didn't pass
+    output: Option<&'a str>,
+    // Whether it passed but still contains `I AM NOT DONE`
+    pending: bool,
+}
+
+// The output of the exercise that was checked last, along with the hash of
+// its file, so it's only checked again once it changes
+#[derive(Default)]
+struct Cache {
+    name: String,
+    hash: Option<u64>,
+    output: Option<String>,
+}
+
+// Serve the dashboard on the given port of localhost until Rustlings is stopped
+pub fn serve(exercises: &[Exercise], options: &VerifyOptions, port: u16) -> io::Result<()> {
+    let listener = TcpListener::bind(("127.0.0.1", port))?;
+    ui::disable_color();
+    success!("The dashboard is running on http://localhost:{}", port);
+    println!("Press Ctrl-C to stop it.");
+    let mut cache = Cache::default();
+    for stream in listener.incoming() {
+        // A browser that went away isn't a reason to stop serving others
+        let _ = stream.and_then(|stream| respond(stream, exercises, options, &mut cache));
+    }
+    Ok(())
+}
+
+fn respond(
+    mut stream: TcpStream,
+    exercises: &[Exercise],
+    options: &VerifyOptions,
+    cache: &mut Cache,
+) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(&stream);
+    let mut request = String::new();
+    reader.read_line(&mut request)?;
+    // The headers don't matter, but they have to be read before responding
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+    }
+
+    let (status, body) = match request.split_whitespace().nth(1) {
+        Some("/") => ("200 OK", dashboard(exercises, options, cache)),
+        _ => (
+            "404 Not Found",
+            page("Not found", "<p>There's nothing here.</p>"),
+        ),
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()
+}
+
+fn dashboard(exercises: &[Exercise], options: &VerifyOptions, cache: &mut Cache) -> String {
+    let state = ProgressState::load();
+    let rows: Vec<Row> = exercises
+        .iter()
+        .map(|exercise| Row {
+            name: &exercise.name,
+            path: exercise.path.display().to_string(),
+            status: state.status(exercise),
+        })
+        .collect();
+    let current = exercises
+        .iter()
+        .find(|exercise| state.status(exercise) == Status::Pending)
+        .map(|exercise| {
+            let hash = file_hash(&exercise.path);
+            if cache.name != exercise.name || cache.hash != hash {
+                *cache = Cache {
+                    name: exercise.name.clone(),
+                    hash,
+                    output: check(exercise, options),
+                };
+            }
+            Current {
+                exercise,
+                output: cache.output.as_deref(),
+                pending: matches!(exercise.state(), State::Pending(_)),
+            }
+        });
+    render(&rows, current.as_ref())
+}
+
+// Compile and run the exercise, returning what it printed if it didn't pass
+fn check(exercise: &Exercise, options: &VerifyOptions) -> Option<String> {
+    let compilation = match exercise.compile() {
+        Ok(compilation) => compilation,
+        Err(output) => return Some(output.stdout + &output.stderr),
+    };
+    if let Mode::Clippy = exercise.mode {
+        return None;
+    }
+    compilation
+        .run(options.timeout)
+        .err()
+        .map(|output| output.stdout + &output.stderr)
+}
+
+fn render(rows: &[Row], current: Option<&Current>) -> String {
+    let done = rows.iter().filter(|row| row.status == Status::Done).count();
+    let total = rows.len();
+    let mut body = String::new();
+    let _ = write!(
+        body,
+        "<p><progress value=\"{done}\" max=\"{total}\"></progress> {done} / {total} exercises done ({:.1} %)</p>",
+        done as f32 / total.max(1) as f32 * 100.0
+    );
+
+    match current {
+        Some(current) => {
+            let exercise = current.exercise;
+            let _ = write!(
+                body,
+                "<h2>Current exercise: {}</h2><p><code>{}</code></p>",
+                escape_html(&exercise.name),
+                escape_html(&exercise.path.display().to_string())
+            );
+            match current.output {
+                Some(output) => {
+                    let _ = write!(body, "<pre class=\"output\">{}</pre>", escape_html(output));
+                }
+                None if current.pending => body.push_str(
+                    "<p>It works! Remove the <code>I AM NOT DONE</code> comment to move on.</p>",
+                ),
+                None => {}
+            }
+            let hints = exercise.hints();
+            for (level, hint) in hints.iter().enumerate() {
+                let _ = write!(
+                    body,
+                    "<details><summary>Hint {} of {}</summary><pre>{}</pre></details>",
+                    level + 1,
+                    hints.len(),
+                    escape_html(hint)
+                );
+            }
+        }
+        None => body.push_str("<h2>All exercises are done!</h2>"),
+    }
+
+    body.push_str("<h2>Exercises</h2><table><tr><th>Name</th><th>Path</th><th>Status</th></tr>");
+    for row in rows {
+        let _ = write!(
+            body,
+            "<tr class=\"{}\"><td>{}</td><td><code>{}</code></td><td>{}</td></tr>",
+            row.status.to_string().to_lowercase(),
+            escape_html(row.name),
+            escape_html(&row.path),
+            row.status
+        );
+    }
+    body.push_str("</table>");
+    page("Rustlings", &body)
+}
+
+fn page(title: &str, body: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<meta http-equiv="refresh" content="{REFRESH_SECS}">
+<title>{title}</title>
+<style>
+body {{ font-family: sans-serif; max-width: 60em; margin: auto; padding: 1em; }}
+pre {{ background: #f4f4f4; padding: 1em; overflow-x: auto; }}
+pre.output {{ border-left: 4px solid #c33; }}
+progress {{ width: 20em; }}
+td, th {{ text-align: left; padding: 0.2em 1em 0.2em 0; }}
+tr.done td:last-child {{ color: #2a2; }}
+tr.skipped td:last-child {{ color: #888; }}
+</style>
+</head>
+<body>
+<h1>{title}</h1>
+{body}
+</body>
+</html>
+"#
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_render_progress() {
+        let rows = [
+            Row {
+                name: "intro1",
+                path: "exercises/intro/intro1.rs".to_string(),
+                status: Status::Done,
+            },
+            Row {
+                name: "intro2",
+                path: "exercises/intro/intro2.rs".to_string(),
+                status: Status::Pending,
+            },
+        ];
+        let html = render(&rows, None);
+        assert!(html.contains("1 / 2 exercises done (50.0 %)"));
+        assert!(html.contains("<tr class=\"pending\"><td>intro2</td>"));
+    }
+}
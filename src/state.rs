@@ -0,0 +1,497 @@
+use crate::exercise::{rust_files, Exercise};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::RandomState;
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::hash::{BuildHasher, Hasher};
+use std::io;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const STATE_FILE: &str = ".rustlings-state.json";
+
+// The progress of the user, persisted between runs so that the status
+// of an exercise doesn't have to be re-derived every time
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct ProgressState {
+    #[serde(default)]
+    pub exercises: BTreeMap<String, ExerciseProgress>,
+    // The anonymous ID the progress webhook tells the learner apart by,
+    // made up when it's first needed
+    #[serde(default)]
+    pub session_id: Option<String>,
+}
+
+// Where the learner is with an exercise, from the least to the most finished
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Status {
+    Pending,
+    Skipped,
+    Done,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.pad(match self {
+            Status::Pending => "Pending",
+            Status::Skipped => "Skipped",
+            Status::Done => "Done",
+        })
+    }
+}
+
+// What we know about a single exercise
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+pub struct ExerciseProgress {
+    // Hash of the exercise file when it was last checked
+    pub hash: u64,
+    // Whether the exercise was solved at that point
+    pub solved: bool,
+    // The options it was checked with that change the outcome or what's
+    // shown, from `VerifyOptions::cache_key`
+    #[serde(default)]
+    pub checked_with: String,
+    // Whether the user chose to skip the exercise
+    #[serde(default)]
+    pub skipped: bool,
+    // How many hint levels have been revealed so far
+    #[serde(default)]
+    pub hints_shown: usize,
+    // The line summing up why the exercise failed the last time it was checked
+    #[serde(default)]
+    pub last_error: Option<String>,
+    // How many times the exercise was checked without being solved
+    #[serde(default)]
+    pub attempts: u32,
+    // When the exercise was first checked, in seconds since the Unix epoch
+    #[serde(default)]
+    pub first_attempt: Option<u64>,
+    // When the exercise was first solved, in seconds since the Unix epoch
+    #[serde(default)]
+    pub solved_at: Option<u64>,
+}
+
+impl ExerciseProgress {
+    // How long it took from the first attempt until the exercise was solved
+    pub fn time_to_solve(&self) -> Option<Duration> {
+        let (start, end) = (self.first_attempt?, self.solved_at?);
+        Some(Duration::from_secs(end.saturating_sub(start)))
+    }
+}
+
+impl ProgressState {
+    // Read the state file, starting fresh if it's missing or unreadable
+    pub fn load() -> ProgressState {
+        fs::read_to_string(STATE_FILE)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    // Write the state file to disk
+    pub fn save(&self) -> Result<(), io::Error> {
+        let contents = serde_json::to_string_pretty(self).expect("Failed to serialize state");
+        fs::write(STATE_FILE, contents)
+    }
+
+    // Whether the exercise is done. The recorded status is used as long as
+    // the file hasn't changed since. Otherwise, it goes by the `I AM NOT
+    // DONE` marker, as it did before there was a state file: an exercise
+    // that was never checked or was edited since can only be known to be
+    // solved by compiling and running it, which is what the state file saves
+    // `list`, `run next` and watch mode from. Scanning a single file is cheap
+    // next to that, and exercises solved before there was a state file still
+    // count as done.
+    pub fn is_done(&self, exercise: &Exercise) -> bool {
+        match (
+            self.exercises.get(&exercise.name),
+            file_hash(&exercise.path),
+        ) {
+            (Some(progress), Some(hash)) if progress.hash == hash => progress.solved,
+            _ => exercise.looks_done(),
+        }
+    }
+
+    // Whether the exercise was solved when it was last checked with the
+    // same options and hasn't changed since, so it doesn't have to be
+    // compiled and run again
+    pub fn is_verified(&self, exercise: &Exercise, checked_with: &str) -> bool {
+        match (
+            self.exercises.get(&exercise.name),
+            file_hash(&exercise.path),
+        ) {
+            (Some(progress), Some(hash)) => {
+                progress.hash == hash && progress.solved && progress.checked_with == checked_with
+            }
+            _ => false,
+        }
+    }
+
+    // Whether the exercise was solved when it was last checked, even if it
+    // changed since. Unlike `is_done`, removing the `I AM NOT DONE` marker
+    // doesn't make an exercise that failed count as solved.
+    pub fn was_solved(&self, exercise: &Exercise) -> bool {
+        self.exercises
+            .get(&exercise.name)
+            .is_some_and(|progress| progress.solved)
+    }
+
+    // Whether the user skipped the exercise and hasn't changed it since.
+    // Once it's edited, the exercise is checked like any other again.
+    pub fn is_skipped(&self, exercise: &Exercise) -> bool {
+        match (
+            self.exercises.get(&exercise.name),
+            file_hash(&exercise.path),
+        ) {
+            (Some(progress), Some(hash)) => progress.skipped && progress.hash == hash,
+            _ => false,
+        }
+    }
+
+    // Whether the exercise is done, skipped or still has to be done
+    pub fn status(&self, exercise: &Exercise) -> Status {
+        if self.is_done(exercise) {
+            Status::Done
+        } else if self.is_skipped(exercise) {
+            Status::Skipped
+        } else {
+            Status::Pending
+        }
+    }
+
+    // Mark the exercise as skipped in its current state, so verification
+    // moves on past it until it's changed
+    pub fn skip(&mut self, exercise: &Exercise) {
+        let progress = self.exercises.entry(exercise.name.clone()).or_default();
+        progress.skipped = true;
+        if let Some(hash) = file_hash(&exercise.path) {
+            progress.hash = hash;
+            progress.solved = false;
+        }
+    }
+
+    // Reveal the next hint level of the exercise, returning its index.
+    // Once every level was revealed, the last one keeps being returned.
+    pub fn next_hint(&mut self, exercise: &Exercise) -> Option<usize> {
+        let levels = exercise.hints().len();
+        if levels == 0 {
+            return None;
+        }
+        let progress = self.exercises.entry(exercise.name.clone()).or_default();
+        progress.hints_shown = (progress.hints_shown + 1).min(levels);
+        Some(progress.hints_shown - 1)
+    }
+
+    // Reveal every hint level of the exercise at once
+    pub fn reveal_all_hints(&mut self, exercise: &Exercise) {
+        let levels = exercise.hints().len();
+        if levels > 0 {
+            self.exercises
+                .entry(exercise.name.clone())
+                .or_default()
+                .hints_shown = levels;
+        }
+    }
+
+    // Remember the status of the exercise for its current contents and the
+    // options it was checked with, which takes back skipping it. Checking the
+    // same contents again, like watch mode does when another file is saved,
+    // isn't another attempt. Returns whether it's solved now, but wasn't when
+    // it was checked before.
+    pub fn record(&mut self, exercise: &Exercise, solved: bool, checked_with: &str) -> bool {
+        if let Some(hash) = file_hash(&exercise.path) {
+            let progress = self.exercises.entry(exercise.name.clone()).or_default();
+            let was_solved = progress.solved;
+            let changed = progress.hash != hash;
+            progress.hash = hash;
+            progress.solved = solved;
+            progress.checked_with = checked_with.to_string();
+            progress.skipped = false;
+            progress.last_error = None;
+
+            let now = now();
+            progress.first_attempt.get_or_insert(now);
+            if solved {
+                progress.solved_at.get_or_insert(now);
+            } else if changed {
+                progress.attempts += 1;
+            }
+            return solved && !was_solved;
+        }
+        false
+    }
+
+    // Remember when checking the exercise started, unless it was checked
+    // before. Checking can take a while, so that's when the time to solve it
+    // counts from, rather than from when the result is recorded.
+    pub fn start_attempt(&mut self, exercise: &Exercise, at: u64) {
+        self.exercises
+            .entry(exercise.name.clone())
+            .or_default()
+            .first_attempt
+            .get_or_insert(at);
+    }
+
+    // The anonymous ID of the learner's session, made up of the time and
+    // the randomly seeded hasher of the standard library
+    pub fn session_id(&mut self) -> &str {
+        self.session_id.get_or_insert_with(|| {
+            let mut hasher = RandomState::new().build_hasher();
+            hasher.write_u128(
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_nanos(),
+            );
+            hasher.write_u32(std::process::id());
+            format!("{:016x}", hasher.finish())
+        })
+    }
+
+    // Remember why the exercise failed
+    pub fn record_error(&mut self, exercise: &Exercise, error: Option<String>) {
+        self.exercises
+            .entry(exercise.name.clone())
+            .or_default()
+            .last_error = error;
+    }
+
+    // Why the exercise failed the last time it was checked
+    pub fn last_error(&self, exercise: &Exercise) -> Option<&str> {
+        self.exercises
+            .get(&exercise.name)
+            .and_then(|progress| progress.last_error.as_deref())
+    }
+}
+
+// The current time in seconds since the Unix epoch
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|time| time.as_secs())
+        .unwrap_or_default()
+}
+
+// Hash the contents of a file with 64-bit FNV-1a.
+// Unlike the hashers of the standard library, this is stable across
+// Rust versions, which matters since the result is written to disk.
+pub fn file_hash(path: &Path) -> Option<u64> {
+    // Exercises made of several files are hashed along with their file names
+    let contents = if path.is_dir() {
+        let mut contents = Vec::new();
+        for file in rust_files(path) {
+            contents.extend(file.to_string_lossy().as_bytes());
+            contents.extend(fs::read(&file).ok()?);
+        }
+        contents
+    } else {
+        fs::read(path).ok()?
+    };
+    Some(hash_bytes(&contents))
+}
+
+// Hash the bytes with 64-bit FNV-1a, like `file_hash`
+pub fn hash_bytes(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_file_hash_is_stable() {
+        let path = Path::new("tests/fixture/state/finished_exercise.rs");
+        assert_eq!(file_hash(path), file_hash(path));
+        assert_ne!(
+            file_hash(path),
+            file_hash(Path::new("tests/fixture/state/pending_exercise.rs"))
+        );
+        assert_eq!(file_hash(Path::new("does/not/exist.rs")), None);
+    }
+
+    #[test]
+    fn test_recorded_status_is_used_for_unchanged_file() {
+        let pending = Exercise::new(
+            "pending_exercise",
+            "tests/fixture/state/pending_exercise.rs",
+        );
+        let mut state = ProgressState::default();
+        assert!(!state.is_done(&pending));
+
+        state.record(&pending, true, "");
+        assert!(state.is_done(&pending));
+    }
+
+    #[test]
+    fn test_only_unchanged_solved_exercises_are_verified() {
+        let finished = Exercise::new(
+            "finished_exercise",
+            "tests/fixture/state/finished_exercise.rs",
+        );
+        let mut state = ProgressState::default();
+        assert!(!state.is_verified(&finished, ""));
+
+        state.record(&finished, false, "");
+        assert!(!state.is_verified(&finished, ""));
+        state.record(&finished, true, "");
+        assert!(state.is_verified(&finished, ""));
+
+        // Not with other options, nor once it changed
+        assert!(!state.is_verified(&finished, "timeout=1s"));
+        state.exercises.get_mut("finished_exercise").unwrap().hash = 0;
+        assert!(!state.is_verified(&finished, ""));
+    }
+
+    #[test]
+    fn test_skip_is_taken_back_when_recording() {
+        let pending = Exercise::new(
+            "pending_exercise",
+            "tests/fixture/state/pending_exercise.rs",
+        );
+        let mut state = ProgressState::default();
+        state.skip(&pending);
+        assert!(state.is_skipped(&pending));
+        state.record(&pending, false, "");
+
+        assert!(!state.is_skipped(&pending));
+        assert!(!state.is_done(&pending));
+    }
+
+    #[test]
+    fn test_skip_ends_once_the_exercise_changes() {
+        let pending = Exercise::new(
+            "pending_exercise",
+            "tests/fixture/state/pending_exercise.rs",
+        );
+        let mut state = ProgressState::default();
+        state.skip(&pending);
+        assert!(state.is_skipped(&pending));
+
+        state.exercises.get_mut("pending_exercise").unwrap().hash = 0;
+        assert!(!state.is_skipped(&pending));
+    }
+
+    #[test]
+    fn test_status() {
+        let pending = Exercise::new(
+            "pending_exercise",
+            "tests/fixture/state/pending_exercise.rs",
+        );
+        let finished = Exercise::new(
+            "finished_exercise",
+            "tests/fixture/state/finished_exercise.rs",
+        );
+        let mut state = ProgressState::default();
+        assert_eq!(state.status(&pending), Status::Pending);
+        assert_eq!(state.status(&finished), Status::Done);
+        state.skip(&pending);
+        assert_eq!(state.status(&pending), Status::Skipped);
+        assert_eq!(format!("{:<8}|", Status::Done), "Done    |");
+    }
+
+    #[test]
+    fn test_hints_are_revealed_one_at_a_time() {
+        let mut pending = Exercise::new(
+            "pending_exercise",
+            "tests/fixture/state/pending_exercise.rs",
+        );
+        let mut state = ProgressState::default();
+        assert_eq!(state.next_hint(&pending), None);
+
+        pending.hints = vec!["First".into(), "Second".into()];
+        assert_eq!(state.next_hint(&pending), Some(0));
+        assert_eq!(state.next_hint(&pending), Some(1));
+        assert_eq!(state.next_hint(&pending), Some(1));
+    }
+
+    #[test]
+    fn test_all_hints_are_revealed_at_once() {
+        let mut pending = Exercise::new(
+            "pending_exercise",
+            "tests/fixture/state/pending_exercise.rs",
+        );
+        let mut state = ProgressState::default();
+        state.reveal_all_hints(&pending);
+        assert!(state.exercises.is_empty());
+
+        pending.hints = vec!["First".into(), "Second".into()];
+        state.reveal_all_hints(&pending);
+        assert_eq!(state.exercises["pending_exercise"].hints_shown, 2);
+        assert_eq!(state.next_hint(&pending), Some(1));
+    }
+
+    #[test]
+    fn test_attempts_until_solved() {
+        let path =
+            std::env::temp_dir().join(format!("rustlings-attempts-{}.rs", std::process::id()));
+        let pending = Exercise::new("pending", path.to_str().unwrap());
+        let mut state = ProgressState::default();
+        fs::write(&path, "fn main() { 1 }\n").unwrap();
+        state.record(&pending, false, "");
+        // Checking it again without a change isn't another attempt
+        state.record(&pending, false, "");
+        fs::write(&path, "fn main() { 2 }\n").unwrap();
+        state.record(&pending, false, "");
+        fs::write(&path, "fn main() {}\n").unwrap();
+        state.record(&pending, true, "");
+        state.record(&pending, true, "");
+        fs::remove_file(&path).unwrap();
+
+        let progress = &state.exercises["pending"];
+        assert_eq!(progress.attempts, 2);
+        assert!(progress.time_to_solve().is_some());
+    }
+
+    #[test]
+    fn test_time_to_solve_counts_from_the_start_of_the_check() {
+        let pending = Exercise::new(
+            "pending_exercise",
+            "tests/fixture/state/pending_exercise.rs",
+        );
+        let mut state = ProgressState::default();
+        state.start_attempt(&pending, now() - 30);
+        state.record(&pending, true, "");
+        // Checking it again later doesn't move the start
+        state.start_attempt(&pending, now());
+
+        let time = state.exercises["pending_exercise"].time_to_solve();
+        assert!(time >= Some(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn test_removing_the_marker_doesnt_solve_the_exercise() {
+        let path = std::env::temp_dir().join(format!("rustlings-marker-{}.rs", std::process::id()));
+        fs::write(&path, "// I AM NOT DONE\nfn main() {}\n").unwrap();
+        let pending = Exercise::new("pending", path.to_str().unwrap());
+        let mut state = ProgressState::default();
+        state.record(&pending, false, "");
+
+        fs::write(&path, "fn main() {}\n").unwrap();
+        let (done, solved) = (state.is_done(&pending), state.was_solved(&pending));
+        fs::remove_file(&path).unwrap();
+        assert!(done);
+        assert!(!solved);
+    }
+
+    #[test]
+    fn test_stale_record_falls_back_to_scanning() {
+        let finished = Exercise::new(
+            "finished_exercise",
+            "tests/fixture/state/finished_exercise.rs",
+        );
+        let mut state = ProgressState::default();
+        state.exercises.insert(
+            finished.name.clone(),
+            ExerciseProgress {
+                hash: 0,
+                ..Default::default()
+            },
+        );
+
+        assert!(state.is_done(&finished));
+    }
+}
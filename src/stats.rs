@@ -0,0 +1,188 @@
+use crate::exercise::Exercise;
+use crate::state::ProgressState;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+// The statistics of a single exercise, as printed by `stats --json`
+#[derive(Serialize)]
+struct ExerciseStats<'a> {
+    name: &'a str,
+    solved: bool,
+    attempts: u32,
+    // How many hint levels were revealed
+    hints_shown: usize,
+    // Seconds from the first attempt until the exercise was solved
+    time_to_solve: Option<u64>,
+}
+
+// Print how many attempts and how much time each exercise took
+pub fn stats(exercises: &[Exercise], json: bool) {
+    let state = ProgressState::load();
+    let stats: Vec<ExerciseStats> = exercises
+        .iter()
+        .filter_map(|e| {
+            let progress = state.exercises.get(&e.name)?;
+            Some(ExerciseStats {
+                name: &e.name,
+                solved: state.is_done(e),
+                attempts: progress.attempts,
+                hints_shown: progress.hints_shown,
+                time_to_solve: progress.time_to_solve().map(|time| time.as_secs()),
+            })
+        })
+        .collect();
+
+    if json {
+        let json = serde_json::to_string_pretty(&stats).expect("Failed to serialize stats");
+        println!("{json}");
+        return;
+    }
+
+    if stats.is_empty() {
+        println!("No statistics yet, they are collected as you work on the exercises.");
+        return;
+    }
+
+    println!(
+        "{:<17}\t{:<8}\t{:<5}\t{:<13}",
+        "Name", "Attempts", "Hints", "Time to solve"
+    );
+    for exercise in &stats {
+        println!(
+            "{:<17}\t{:<8}\t{:<5}\t{:<13}",
+            exercise.name,
+            exercise.attempts,
+            exercise.hints_shown,
+            exercise
+                .time_to_solve
+                .map_or_else(|| "—".to_string(), format_duration_secs)
+        );
+    }
+
+    let solved = stats.iter().filter(|e| e.solved).count();
+    let attempts: u32 = stats.iter().map(|e| e.attempts).sum();
+    let time: u64 = stats.iter().filter_map(|e| e.time_to_solve).sum();
+    let hints: usize = stats.iter().map(|e| e.hints_shown).sum();
+    println!();
+    println!(
+        "Solved {solved} / {} exercises with {attempts} failed attempts, taking {} in total.",
+        exercises.len(),
+        format_duration_secs(time)
+    );
+    println!("Looked at {hints} hints along the way.");
+}
+
+// How long the learner took on average to solve exercises, in every chapter
+// and over the whole course, for `list --estimate-time`
+pub struct Pace {
+    by_section: HashMap<Option<String>, Duration>,
+    overall: Option<Duration>,
+}
+
+impl Pace {
+    pub fn new(exercises: &[Exercise], state: &ProgressState) -> Pace {
+        let mut times: HashMap<Option<String>, Vec<Duration>> = HashMap::new();
+        for exercise in exercises {
+            if let Some(time) = state
+                .exercises
+                .get(&exercise.name)
+                .and_then(|progress| progress.time_to_solve())
+            {
+                times
+                    .entry(exercise.metadata().section)
+                    .or_default()
+                    .push(time);
+            }
+        }
+        let all: Vec<Duration> = times.values().flatten().copied().collect();
+        Pace {
+            overall: average(&all),
+            by_section: times
+                .iter()
+                .filter_map(|(section, times)| Some((section.clone(), average(times)?)))
+                .collect(),
+        }
+    }
+
+    // How long the exercise will likely take: as long as the solved exercises
+    // of its chapter took, or those of the whole course if none of its
+    // chapter are solved yet
+    pub fn estimate(&self, exercise: &Exercise) -> Option<Duration> {
+        self.by_section
+            .get(&exercise.metadata().section)
+            .copied()
+            .or(self.overall)
+    }
+}
+
+fn average(times: &[Duration]) -> Option<Duration> {
+    let count = u32::try_from(times.len()).ok().filter(|&count| count > 0)?;
+    Some(times.iter().sum::<Duration>() / count)
+}
+
+fn format_duration_secs(secs: u64) -> String {
+    format_duration(Duration::from_secs(secs))
+}
+
+// Format a duration the way a human would say it, e.g. "1h 5m" or "42s"
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (hours, minutes, seconds) = (secs / 3600, secs % 3600 / 60, secs % 60);
+    if hours > 0 {
+        format!("{hours}h {minutes}m")
+    } else if minutes > 0 {
+        format!("{minutes}m {seconds}s")
+    } else {
+        format!("{seconds}s")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::state::ExerciseProgress;
+
+    fn solved_in(secs: u64) -> ExerciseProgress {
+        ExerciseProgress {
+            solved: true,
+            first_attempt: Some(1000),
+            solved_at: Some(1000 + secs),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_pace_estimates_from_the_chapter_first() {
+        let exercises = [
+            Exercise::new("intro1", "exercises/intro/intro1.rs"),
+            Exercise::new("intro2", "exercises/intro/intro2.rs"),
+            Exercise::new("intro3", "exercises/intro/intro3.rs"),
+            Exercise::new("vecs1", "exercises/vecs/vecs1.rs"),
+        ];
+        let mut state = ProgressState::default();
+        state.exercises.insert("intro1".into(), solved_in(60));
+        state.exercises.insert("intro2".into(), solved_in(120));
+        state.exercises.insert("vecs1".into(), solved_in(600));
+        let pace = Pace::new(&exercises, &state);
+        assert_eq!(pace.estimate(&exercises[2]), Some(Duration::from_secs(90)));
+
+        // Without solved exercises in its chapter, the whole course counts
+        let quiz = Exercise::new("quiz1", "exercises/quiz1.rs");
+        assert_eq!(pace.estimate(&quiz), Some(Duration::from_secs(260)));
+    }
+
+    #[test]
+    fn test_pace_without_solved_exercises() {
+        let exercises = [Exercise::new("intro1", "exercises/intro/intro1.rs")];
+        let pace = Pace::new(&exercises, &ProgressState::default());
+        assert_eq!(pace.estimate(&exercises[0]), None);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(42)), "42s");
+        assert_eq!(format_duration(Duration::from_secs(312)), "5m 12s");
+        assert_eq!(format_duration(Duration::from_secs(3900)), "1h 5m");
+    }
+}
@@ -0,0 +1,367 @@
+// Syncing the progress with a remote copy, for learners who work on the
+// course on more than one machine. The remote holds the progress in the
+// format of `progress export`, without the sources of the exercises unless
+// they're synced as well.
+//
+// The remote is a GitHub gist, or a URL the progress is fetched from with GET
+// and stored at with PUT, like a WebDAV folder or a `file://` URL. Like
+// upgrading, the requests are made with `curl`. What was synced last is kept
+// in `.rustlings/sync.json`, so progress that changed on both sides since is
+// reported as a conflict instead of one overwriting the other.
+use crate::exercise::Exercise;
+use crate::progress::{apply, parse_export, ProgressExport};
+use crate::state::hash_bytes;
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+const SYNC_FILE: &str = ".rustlings/sync.json";
+// Where the body of requests to the GitHub API is written to, since stdin
+// passes the token to curl
+const UPLOAD_FILE: &str = ".rustlings/sync-upload.json";
+const GIST_API: &str = "https://api.github.com/gists";
+// The file in the gist holding the progress
+const GIST_FILE: &str = "rustlings-progress.json";
+// The exit codes of curl for a missing file and for an HTTP error
+const CURL_FILE_NOT_FOUND: i32 = 37;
+const CURL_HTTP_ERROR: i32 = 22;
+
+pub struct SyncOptions {
+    // Overwrite the remote progress, even if it changed
+    pub push: bool,
+    // Overwrite the local progress, even if it changed
+    pub pull: bool,
+    // Sync the sources of the exercises that were worked on as well
+    pub files: bool,
+}
+
+// What was synced last
+#[derive(Serialize, Deserialize, Debug)]
+struct LastSync {
+    remote: String,
+    // The fingerprints of the progress on both sides right after syncing
+    local_fingerprint: u64,
+    remote_fingerprint: u64,
+}
+
+impl LastSync {
+    fn load() -> Option<LastSync> {
+        let contents = fs::read_to_string(SYNC_FILE).ok()?;
+        serde_json::from_str(&contents).ok()
+    }
+
+    fn save(&self) {
+        let contents = serde_json::to_string_pretty(self).expect("Failed to serialize sync");
+        let result = fs::create_dir_all(".rustlings").and_then(|_| fs::write(SYNC_FILE, contents));
+        if let Err(e) = result {
+            warn!("Couldn't remember what was synced: {}", e);
+        }
+    }
+}
+
+// Tells apart different progress. The serialized maps are sorted, so the
+// same progress always has the same fingerprint.
+fn fingerprint(json: &str) -> u64 {
+    hash_bytes(json.as_bytes())
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Action {
+    UpToDate,
+    Push,
+    Pull,
+    Conflict,
+}
+
+// What to do with the local and the remote progress, given their
+// fingerprints and those after the last sync
+fn decide(last: Option<(u64, u64)>, local: u64, local_empty: bool, remote: Option<u64>) -> Action {
+    let Some(remote) = remote else {
+        return Action::Push;
+    };
+    if remote == local {
+        return Action::UpToDate;
+    }
+    match last {
+        Some((last_local, last_remote)) => match (local != last_local, remote != last_remote) {
+            (_, false) => Action::Push,
+            (false, true) => Action::Pull,
+            (true, true) => Action::Conflict,
+        },
+        // A new copy of the course takes over the progress made elsewhere
+        None if local_empty => Action::Pull,
+        None => Action::Conflict,
+    }
+}
+
+#[derive(PartialEq, Eq, Debug)]
+enum Remote {
+    // A gist by its ID, or a new one that's created on the first push
+    Gist(Option<String>),
+    Url(String),
+}
+
+impl Remote {
+    // Like `gist:<ID>` or a URL. Plain `gist` is the gist synced with last.
+    fn parse(remote: &str, last: Option<&LastSync>) -> Remote {
+        match remote.strip_prefix("gist") {
+            Some("") => Remote::Gist(
+                last.and_then(|last| last.remote.strip_prefix("gist:"))
+                    .map(str::to_string),
+            ),
+            Some(id) if id.starts_with(':') => Remote::Gist(Some(id[1..].to_string())),
+            _ => Remote::Url(remote.to_string()),
+        }
+    }
+
+    fn name(&self) -> String {
+        match self {
+            Remote::Gist(Some(id)) => format!("gist:{id}"),
+            Remote::Gist(None) => "a new gist".to_string(),
+            Remote::Url(url) => url.clone(),
+        }
+    }
+
+    // The progress stored at the remote, or None if there's none yet
+    fn fetch(&self) -> Result<Option<String>, String> {
+        match self {
+            Remote::Gist(None) => Ok(None),
+            Remote::Gist(Some(id)) => {
+                let gist = gist_request(&[&format!("{GIST_API}/{id}")])?;
+                let gist: serde_json::Value =
+                    serde_json::from_slice(&gist).map_err(|e| e.to_string())?;
+                Ok(gist["files"][GIST_FILE]["content"]
+                    .as_str()
+                    .map(str::to_string))
+            }
+            Remote::Url(url) => match curl(&[url], None) {
+                Ok(contents) => Ok(Some(String::from_utf8_lossy(&contents).to_string())),
+                Err((Some(CURL_FILE_NOT_FOUND), _)) => Ok(None),
+                Err((Some(CURL_HTTP_ERROR), e)) if e.contains("404") => Ok(None),
+                Err((_, e)) => Err(e),
+            },
+        }
+    }
+
+    // Store the progress at the remote, creating the gist if there's none yet
+    fn store(&mut self, progress: &str) -> Result<(), String> {
+        match self {
+            Remote::Gist(id) => {
+                let files = serde_json::json!({ GIST_FILE: { "content": progress } });
+                let (method, url, body) = match id {
+                    Some(id) => (
+                        "PATCH",
+                        format!("{GIST_API}/{id}"),
+                        serde_json::json!({ "files": files }),
+                    ),
+                    None => (
+                        "POST",
+                        GIST_API.to_string(),
+                        serde_json::json!({
+                            "description": "Rustlings progress",
+                            "public": false,
+                            "files": files,
+                        }),
+                    ),
+                };
+                fs::create_dir_all(".rustlings")
+                    .and_then(|_| fs::write(UPLOAD_FILE, body.to_string()))
+                    .map_err(|e| e.to_string())?;
+                let upload = format!("@{UPLOAD_FILE}");
+                let response = gist_request(&["-X", method, "--data-binary", &upload, &url]);
+                let _ = fs::remove_file(UPLOAD_FILE);
+                let response: serde_json::Value =
+                    serde_json::from_slice(&response?).map_err(|e| e.to_string())?;
+                if id.is_none() {
+                    let created = response["id"]
+                        .as_str()
+                        .ok_or("GitHub didn't tell the ID of the new gist")?;
+                    *id = Some(created.to_string());
+                }
+                Ok(())
+            }
+            Remote::Url(url) => curl(&["-T", "-", url], Some(progress))
+                .map(|_| ())
+                .map_err(|(_, e)| e),
+        }
+    }
+}
+
+// Make a request to the GitHub API, authenticated with the token in
+// GITHUB_TOKEN, if there is one. Secret gists can be read without it, but
+// not written.
+fn gist_request(args: &[&str]) -> Result<Vec<u8>, String> {
+    let mut all = vec!["-H", "Accept: application/vnd.github+json"];
+    // The header is read from stdin, so the token doesn't show up in the
+    // list of processes
+    let header = env::var("GITHUB_TOKEN")
+        .ok()
+        .map(|token| format!("Authorization: Bearer {token}"));
+    if header.is_some() {
+        all.extend(["-H", "@-"]);
+    }
+    all.extend(args);
+    curl(&all, header.as_deref()).map_err(|(_, e)| e)
+}
+
+// Run curl with the given input, returning its exit code and what it
+// printed to stderr if it failed
+fn curl(args: &[&str], input: Option<&str>) -> Result<Vec<u8>, (Option<i32>, String)> {
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--location"])
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| (None, format!("Failed to run curl: {e}")))?;
+    // Dropping stdin closes it, which tells curl that the input is complete
+    if let (Some(mut stdin), Some(input)) = (child.stdin.take(), input) {
+        let _ = stdin.write_all(input.as_bytes());
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|e| (None, format!("Failed to run curl: {e}")))?;
+    if output.status.success() {
+        Ok(output.stdout)
+    } else {
+        Err((
+            output.status.code(),
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ))
+    }
+}
+
+// Sync the progress with the remote, pushing or pulling whichever changed
+// since the last sync
+pub fn sync(exercises: &[Exercise], remote: &str, options: &SyncOptions) -> Result<(), ()> {
+    let last = LastSync::load();
+    let mut remote = Remote::parse(remote, last.as_ref());
+    // The last sync only tells what changed if it was with the same remote
+    let last = last.filter(|last| last.remote == remote.name());
+
+    let local = ProgressExport::collect(exercises, options.files);
+    let local_json = serde_json::to_string_pretty(&local).expect("Failed to serialize progress");
+    let remote_json = remote.fetch().map_err(|e| {
+        warn!("Couldn't fetch the progress from {}", remote.name());
+        println!("{e}");
+    })?;
+    let remote_progress = match &remote_json {
+        Some(json) => Some(parse_export(json).map_err(|e| {
+            warn!("The progress at {} is invalid", remote.name());
+            println!("{e}");
+        })?),
+        None => None,
+    };
+
+    let action = if options.push {
+        Action::Push
+    } else if options.pull && remote_json.is_some() {
+        Action::Pull
+    } else if options.pull {
+        warn!("There's no progress at {} to pull yet", remote.name());
+        return Err(());
+    } else {
+        decide(
+            last.as_ref()
+                .map(|last| (last.local_fingerprint, last.remote_fingerprint)),
+            fingerprint(&local_json),
+            local.is_empty(),
+            remote_json.as_deref().map(fingerprint),
+        )
+    };
+    match (action, remote_json, remote_progress) {
+        (Action::UpToDate, Some(remote_json), _) => {
+            record(&remote, &local_json, &remote_json);
+            success!("Your progress is up to date with {}", remote.name());
+        }
+        (Action::Pull, Some(remote_json), Some(progress)) => {
+            let count = apply(exercises, &progress)?;
+            // The progress made only here is kept, so it can differ from the remote
+            let local = ProgressExport::collect(exercises, options.files);
+            let local_json =
+                serde_json::to_string_pretty(&local).expect("Failed to serialize progress");
+            record(&remote, &local_json, &remote_json);
+            success!("Pulled the progress from {}", remote.name());
+            if count > 0 {
+                println!(
+                    "Updated {count} exercises, the ones that were overwritten are backed up."
+                );
+            }
+        }
+        (Action::Conflict, _, _) => {
+            warn!(
+                "Your progress and the one at {} both changed since they were last synced",
+                remote.name()
+            );
+            println!("Run `rustlings sync --push` to keep yours, or `rustlings sync --pull` to take over the other one.");
+            return Err(());
+        }
+        _ => {
+            let new_gist = remote == Remote::Gist(None);
+            remote.store(&local_json).map_err(|e| {
+                warn!("Couldn't push the progress to {}", remote.name());
+                println!("{e}");
+            })?;
+            record(&remote, &local_json, &local_json);
+            success!("Pushed the progress to {}", remote.name());
+            if new_gist {
+                println!(
+                    "Run `rustlings sync --remote {}` on your other machines to sync with it.",
+                    remote.name()
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+fn record(remote: &Remote, local_json: &str, remote_json: &str) {
+    LastSync {
+        remote: remote.name(),
+        local_fingerprint: fingerprint(local_json),
+        remote_fingerprint: fingerprint(remote_json),
+    }
+    .save();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_decide() {
+        assert_eq!(decide(None, 1, false, None), Action::Push);
+        assert_eq!(decide(None, 1, false, Some(1)), Action::UpToDate);
+        assert_eq!(decide(None, 1, true, Some(2)), Action::Pull);
+        assert_eq!(decide(None, 1, false, Some(2)), Action::Conflict);
+        assert_eq!(decide(Some((1, 2)), 3, false, Some(2)), Action::Push);
+        assert_eq!(decide(Some((1, 2)), 1, false, Some(3)), Action::Pull);
+        assert_eq!(decide(Some((1, 2)), 3, false, Some(4)), Action::Conflict);
+        assert_eq!(decide(Some((1, 2)), 1, false, Some(2)), Action::Push);
+    }
+
+    #[test]
+    fn test_parse_remote() {
+        let last = LastSync {
+            remote: "gist:abc123".to_string(),
+            local_fingerprint: 1,
+            remote_fingerprint: 2,
+        };
+        assert_eq!(Remote::parse("gist", None), Remote::Gist(None));
+        assert_eq!(
+            Remote::parse("gist", Some(&last)),
+            Remote::Gist(Some("abc123".to_string()))
+        );
+        assert_eq!(
+            Remote::parse("gist:def456", Some(&last)),
+            Remote::Gist(Some("def456".to_string()))
+        );
+        assert_eq!(
+            Remote::parse("https://example.com/progress.json", None),
+            Remote::Url("https://example.com/progress.json".to_string())
+        );
+    }
+}
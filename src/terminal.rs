@@ -0,0 +1,254 @@
+// Reading single key presses for the commands of watch mode.
+//
+// On Unix, the terminal is switched into a mode without line buffering and
+// echo while keys are read. Unlike the raw mode of full-screen programs, the
+// processing of output is left alone, so everything else can still be printed
+// as usual while watch mode waits for a key.
+use std::io::{self, Write};
+use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(unix)]
+use std::time::Duration;
+
+// Whether the alternate screen of the terminal is being drawn on
+static FULL_SCREEN: AtomicBool = AtomicBool::new(false);
+
+// Reading keys stays enabled as long as this is kept around
+pub struct KeyInput {
+    _private: (),
+}
+
+impl KeyInput {
+    // Start reading single keys. Returns None if the input isn't a terminal.
+    pub fn enable() -> Option<KeyInput> {
+        imp::enable().then_some(KeyInput { _private: () })
+    }
+}
+
+impl Drop for KeyInput {
+    fn drop(&mut self) {
+        imp::disable();
+    }
+}
+
+// Wait for the next key press. Returns None once the input is closed.
+// Since Ctrl-C doesn't interrupt Rustlings while keys are read,
+// it's handled here, restoring the terminal before quitting.
+pub fn read_key() -> io::Result<Option<char>> {
+    let key = imp::read_key()?;
+    if key == Some('\x03') {
+        leave_full_screen();
+        imp::disable();
+        process::exit(130);
+    }
+    Ok(key)
+}
+
+// Whether another key press arrives within the timeout, e.g. to tell the
+// Escape key apart from the escape sequences of the arrow keys
+#[cfg(unix)]
+pub fn key_pending(timeout: Duration) -> bool {
+    imp::key_pending(timeout)
+}
+
+// Draw on the alternate screen of the terminal with the cursor hidden while
+// this is kept around. Afterwards, the screen is back to how it was before.
+#[cfg(unix)]
+pub struct FullScreen {
+    _private: (),
+}
+
+#[cfg(unix)]
+impl FullScreen {
+    pub fn enter() -> FullScreen {
+        enter_full_screen();
+        FullScreen { _private: () }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for FullScreen {
+    fn drop(&mut self) {
+        leave_full_screen();
+    }
+}
+
+fn enter_full_screen() {
+    FULL_SCREEN.store(true, Ordering::SeqCst);
+    print!("\x1B[?1049h\x1B[?25l");
+    let _ = io::stdout().flush();
+}
+
+// Go back to the normal screen, returning whether the alternate one was used
+fn leave_full_screen() -> bool {
+    let full_screen = FULL_SCREEN.swap(false, Ordering::SeqCst);
+    if full_screen {
+        print!("\x1B[?25h\x1B[?1049l");
+        let _ = io::stdout().flush();
+    }
+    full_screen
+}
+
+// Run `f` with the terminal in its usual mode, e.g. to read a whole line
+// or to run another program, then go back to reading single keys and to the
+// alternate screen if it was used
+pub fn with_line_input<T>(f: impl FnOnce() -> T) -> T {
+    let full_screen = leave_full_screen();
+    let enabled = imp::disable();
+    let result = f();
+    if enabled {
+        imp::enable();
+    }
+    if full_screen {
+        enter_full_screen();
+    }
+    result
+}
+
+#[cfg(unix)]
+mod imp {
+    use std::fs::File;
+    use std::io::{self, Read};
+    use std::mem::{ManuallyDrop, MaybeUninit};
+    use std::os::unix::io::FromRawFd;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    // The mode of the terminal from before reading single keys was enabled
+    static ORIGINAL_MODE: Mutex<Option<libc::termios>> = Mutex::new(None);
+
+    pub fn enable() -> bool {
+        let mut original = MaybeUninit::uninit();
+        // SAFETY: tcgetattr initializes the termios struct if it succeeds
+        let original = unsafe {
+            if libc::tcgetattr(libc::STDIN_FILENO, original.as_mut_ptr()) != 0 {
+                return false;
+            }
+            original.assume_init()
+        };
+        let mut mode = original;
+        // Ctrl-C arrives as a key too, so the terminal can be restored before quitting
+        mode.c_lflag &= !(libc::ICANON | libc::ECHO | libc::ISIG);
+        mode.c_cc[libc::VMIN] = 1;
+        mode.c_cc[libc::VTIME] = 0;
+        // SAFETY: `mode` is a valid termios struct
+        if unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &mode) } != 0 {
+            return false;
+        }
+        *ORIGINAL_MODE.lock().unwrap_or_else(|e| e.into_inner()) = Some(original);
+        true
+    }
+
+    // Restore the original mode, returning whether reading keys was enabled
+    pub fn disable() -> bool {
+        let original = ORIGINAL_MODE
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take();
+        if let Some(original) = original {
+            // SAFETY: `original` was filled in by tcgetattr
+            unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &original) };
+        }
+        original.is_some()
+    }
+
+    // Keys are read without the buffer of `io::stdin`, so bytes that were
+    // typed but not read yet are still pending for `key_pending`
+    pub fn read_key() -> io::Result<Option<char>> {
+        // SAFETY: standard input stays open, and it isn't closed here since
+        // the file is never dropped
+        let mut stdin = ManuallyDrop::new(unsafe { File::from_raw_fd(libc::STDIN_FILENO) });
+        read_char(&mut *stdin)
+    }
+
+    pub fn key_pending(timeout: Duration) -> bool {
+        let mut stdin = libc::pollfd {
+            fd: libc::STDIN_FILENO,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        let timeout = i32::try_from(timeout.as_millis()).unwrap_or(i32::MAX);
+        // SAFETY: `stdin` is a single valid pollfd struct
+        unsafe { libc::poll(&mut stdin, 1, timeout) > 0 }
+    }
+
+    // Read one UTF-8 encoded character, which may take up to four bytes.
+    // Bytes that aren't valid UTF-8 become the replacement character.
+    pub fn read_char(input: &mut impl Read) -> io::Result<Option<char>> {
+        let mut bytes = [0; 4];
+        if input.read(&mut bytes[..1])? == 0 {
+            return Ok(None);
+        }
+        let len = match bytes[0] {
+            0x00..=0x7f => 1,
+            0xc0..=0xdf => 2,
+            0xe0..=0xef => 3,
+            0xf0..=0xf7 => 4,
+            _ => return Ok(Some(char::REPLACEMENT_CHARACTER)),
+        };
+        input.read_exact(&mut bytes[1..len])?;
+        Ok(Some(
+            std::str::from_utf8(&bytes[..len])
+                .ok()
+                .and_then(|text| text.chars().next())
+                .unwrap_or(char::REPLACEMENT_CHARACTER),
+        ))
+    }
+}
+
+#[cfg(not(unix))]
+mod imp {
+    use console::{Key, Term};
+    use std::io;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    // Whether reading single keys is enabled. The console reads them one at
+    // a time anyway, so there's no mode to switch, only this to remember.
+    static ENABLED: AtomicBool = AtomicBool::new(false);
+
+    pub fn enable() -> bool {
+        let enabled = Term::stdout().is_term();
+        ENABLED.store(enabled, Ordering::SeqCst);
+        enabled
+    }
+
+    // Returns whether reading keys was enabled
+    pub fn disable() -> bool {
+        ENABLED.swap(false, Ordering::SeqCst)
+    }
+
+    // Keys that aren't characters are turned into the control characters
+    // the Unix terminal sends for them, or for their shortcuts
+    pub fn read_key() -> io::Result<Option<char>> {
+        match Term::stdout().read_key() {
+            Ok(Key::Char(c)) => Ok(Some(c)),
+            Ok(Key::Enter) => Ok(Some('\n')),
+            Ok(Key::Backspace) => Ok(Some('\x7f')),
+            Ok(Key::ArrowUp) => Ok(Some('\x10')),
+            Ok(Key::ArrowDown) => Ok(Some('\x0e')),
+            Ok(_) => Ok(Some('\0')),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => Ok(Some('\x03')),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod test {
+    use super::imp::read_char;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_read_char() {
+        let mut input = Cursor::new("hé→🦀".as_bytes().to_vec());
+        let chars: Vec<char> = std::iter::from_fn(|| read_char(&mut input).unwrap()).collect();
+        assert_eq!(chars, ['h', 'é', '→', '🦀']);
+
+        let mut input = Cursor::new(vec![0xff, b'a']);
+        assert_eq!(
+            read_char(&mut input).unwrap(),
+            Some(char::REPLACEMENT_CHARACTER)
+        );
+        assert_eq!(read_char(&mut input).unwrap(), Some('a'));
+    }
+}
@@ -0,0 +1,375 @@
+// The full screen of watch mode: what's going on at the top, the output of
+// the latest check in a pane that can be scrolled in between, and the keys
+// to press at the bottom. Everything watch mode runs prints as usual, and is
+// captured to be shown in the pane.
+use crate::terminal;
+use console::Term;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, Write};
+use std::os::unix::io::AsRawFd;
+use std::process;
+use std::time::{Duration, Instant};
+
+// A key press, with the escape sequences of the keys for scrolling decoded
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Key {
+    Char(char),
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    Home,
+    End,
+    Escape,
+    // A key without a use here, like F1
+    Other,
+}
+
+// Wait for the next key press. Returns None once the input is closed.
+pub fn read_key() -> io::Result<Option<Key>> {
+    let Some(key) = terminal::read_key()? else {
+        return Ok(None);
+    };
+    if key != '\x1B' {
+        return Ok(Some(Key::Char(key)));
+    }
+    // The Escape key on its own isn't followed by the rest of a sequence
+    if !terminal::key_pending(Duration::from_millis(30)) {
+        return Ok(Some(Key::Escape));
+    }
+    // Sequences end in a letter or a tilde, after the `[` or `O` they start with
+    let mut sequence = String::new();
+    while let Some(c) = terminal::read_key()? {
+        sequence.push(c);
+        let ended = c == '~' || (c.is_ascii_alphabetic() && sequence.len() > 1);
+        if ended || sequence.len() > 8 || !terminal::key_pending(Duration::from_millis(30)) {
+            break;
+        }
+    }
+    Ok(Some(decode(&sequence)))
+}
+
+// The key of an escape sequence, without the escape character it starts with
+fn decode(sequence: &str) -> Key {
+    match sequence {
+        "[A" | "OA" => Key::Up,
+        "[B" | "OB" => Key::Down,
+        "[5~" => Key::PageUp,
+        "[6~" => Key::PageDown,
+        "[H" | "OH" | "[1~" | "[7~" => Key::Home,
+        "[F" | "OF" | "[4~" | "[8~" => Key::End,
+        _ => Key::Other,
+    }
+}
+
+// Run `f` with what it prints to stdout and stderr going into a file instead
+// of onto the screen, and return that along with the result of `f`
+pub fn capture<T>(f: impl FnOnce() -> T) -> (T, String) {
+    let path = std::env::temp_dir().join(format!("rustlings-watch-{}", process::id()));
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(&path);
+    let Ok(mut file) = file else {
+        return (f(), String::new());
+    };
+    let _ = io::stdout().flush();
+    // SAFETY: dup only duplicates the file descriptors of stdout and stderr
+    let saved = unsafe { [libc::dup(1), libc::dup(2)] };
+    if saved.contains(&-1) {
+        return (f(), String::new());
+    }
+    redirect(&file, [1, 2]);
+    let result = f();
+    let _ = io::stdout().flush();
+    for (saved, fd) in saved.into_iter().zip([1, 2]) {
+        // SAFETY: `saved` is a duplicate of `fd` made above
+        unsafe {
+            libc::dup2(saved, fd);
+            libc::close(saved);
+        }
+    }
+    let mut bytes = Vec::new();
+    let _ = file.rewind().and_then(|_| file.read_to_end(&mut bytes));
+    let _ = fs::remove_file(&path);
+    (result, String::from_utf8_lossy(&bytes).into_owned())
+}
+
+fn redirect(file: &File, fds: [i32; 2]) {
+    for fd in fds {
+        // SAFETY: both are open file descriptors
+        unsafe { libc::dup2(file.as_raw_fd(), fd) };
+    }
+}
+
+// What the screen shows
+#[derive(Default)]
+pub struct View {
+    // Lines about the progress and the current exercise
+    pub header: Vec<String>,
+    // The output shown in the pane
+    pub body: String,
+    // The first row of the pane that's shown
+    pub scroll: usize,
+    // The keys to press
+    pub footer: String,
+}
+
+impl View {
+    // Replace what the pane shows, starting at its top
+    pub fn show(&mut self, body: String) {
+        self.body = body;
+        self.scroll = 0;
+    }
+
+    // Add to what the pane shows, scrolling down to it
+    pub fn append(&mut self, text: &str, width: usize) {
+        if !self.body.is_empty() && !self.body.ends_with('\n') {
+            self.body.push('\n');
+        }
+        let rows = self.rows(width).len();
+        self.body.push_str(text);
+        self.scroll = rows;
+    }
+
+    // Move the pane by the key, returning whether it's a key for scrolling
+    pub fn scroll_by(&mut self, key: Key, (height, width): (usize, usize)) -> bool {
+        let page = self.pane_height(height).max(1);
+        let last = self.rows(width).len().saturating_sub(page);
+        self.scroll = match key {
+            Key::Up | Key::Char('k') => self.scroll.saturating_sub(1),
+            Key::Down | Key::Char('j') => self.scroll + 1,
+            Key::PageUp => self.scroll.saturating_sub(page),
+            Key::PageDown | Key::Char(' ') => self.scroll + page,
+            Key::Home | Key::Char('g') => 0,
+            Key::End | Key::Char('G') => last,
+            _ => return false,
+        }
+        .min(last);
+        true
+    }
+
+    fn rows(&self, width: usize) -> Vec<String> {
+        self.body
+            .trim_end()
+            .lines()
+            .flat_map(|line| wrap(line, width))
+            .collect()
+    }
+
+    // The rows left for the pane between the header, the two rules and the
+    // footer
+    fn pane_height(&self, height: usize) -> usize {
+        height.saturating_sub(self.header.len() + 3)
+    }
+
+    // The whole screen, drawn from its top left corner. Every row clears what
+    // was left of the previous screen, and none ends in a line break, so the
+    // screen never scrolls.
+    pub fn render(&self, (height, width): (usize, usize)) -> String {
+        let rows = self.rows(width);
+        let pane = self.pane_height(height);
+        let scroll = self.scroll.min(rows.len().saturating_sub(pane));
+        let rule = console::style("─".repeat(width)).dim().to_string();
+
+        let mut screen: Vec<String> = self
+            .header
+            .iter()
+            .map(|line| console::truncate_str(line, width, "…").into_owned())
+            .collect();
+        screen.push(rule.clone());
+        screen.extend(rows.iter().skip(scroll).take(pane).cloned());
+        screen.resize(self.header.len() + 1 + pane, String::new());
+        screen.push(rule);
+        let position = if rows.len() > pane {
+            format!(
+                " {}–{}/{}",
+                scroll + 1,
+                (scroll + pane).min(rows.len()),
+                rows.len()
+            )
+        } else {
+            String::new()
+        };
+        let footer = console::truncate_str(
+            &self.footer,
+            width.saturating_sub(position.chars().count()),
+            "…",
+        )
+        .into_owned();
+        screen.push(format!("{footer}{}", console::style(position).dim()));
+        screen.truncate(height);
+
+        let rows: Vec<String> = screen
+            .into_iter()
+            .map(|row| format!("{row}\x1B[0m\x1B[K"))
+            .collect();
+        format!("\x1B[H{}", rows.join("\r\n"))
+    }
+}
+
+// The alternate screen of the terminal, which views are drawn on as long as
+// it's kept around
+pub struct Screen {
+    _full_screen: terminal::FullScreen,
+    // What's on the screen, and since when
+    frame: String,
+    drawn_at: Instant,
+}
+
+impl Screen {
+    pub fn enter() -> Screen {
+        Screen {
+            _full_screen: terminal::FullScreen::enter(),
+            frame: String::new(),
+            drawn_at: Instant::now(),
+        }
+    }
+
+    // Draw the view if it changed. The screen is drawn again every second
+    // regardless, in case something else printed onto it, like a hook.
+    pub fn draw(&mut self, view: &View) {
+        let frame = view.render(size());
+        if frame == self.frame && self.drawn_at.elapsed() < Duration::from_secs(1) {
+            return;
+        }
+        let mut stdout = io::stdout();
+        let _ = stdout.write_all(frame.as_bytes());
+        let _ = stdout.flush();
+        self.frame = frame;
+        self.drawn_at = Instant::now();
+    }
+
+    // Draw the next view even if it didn't change, e.g. after another
+    // program used the terminal
+    pub fn invalidate(&mut self) {
+        self.frame.clear();
+    }
+}
+
+// The rows and columns of the screen
+pub fn size() -> (usize, usize) {
+    let (rows, columns) = Term::stdout().size();
+    (usize::from(rows), usize::from(columns))
+}
+
+// Break a line of output into rows of at most `width` columns. Escape codes
+// for colors take no room, and the colors that are on at the end of a row are
+// turned on again at the start of the next one. Tabs are expanded, since the
+// rows are cut to size.
+fn wrap(line: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut rows = Vec::new();
+    let mut row = String::new();
+    let mut column = 0;
+    // The escape codes for the colors that are on
+    let mut colors = String::new();
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\x1B' {
+            let mut code = String::from(c);
+            while let Some(c) = chars.next_if(|c| !c.is_ascii_alphabetic()) {
+                code.push(c);
+            }
+            code.extend(chars.next());
+            if code == "\x1B[0m" || code == "\x1B[m" {
+                colors.clear();
+            } else if code.ends_with('m') {
+                colors.push_str(&code);
+            }
+            row.push_str(&code);
+            continue;
+        }
+        let (text, columns) = if c == '\t' {
+            let columns = 8 - column % 8;
+            (" ".repeat(columns), columns)
+        } else {
+            (c.to_string(), console::measure_text_width(&c.to_string()))
+        };
+        if column + columns > width && column > 0 {
+            rows.push(std::mem::take(&mut row));
+            row.push_str(&colors);
+            column = 0;
+        }
+        row.push_str(&text);
+        column += columns;
+    }
+    rows.push(row);
+    rows
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(wrap("abcdef", 4), ["abcd", "ef"]);
+        assert_eq!(wrap("", 4), [""]);
+        assert_eq!(wrap("a\tb", 20), ["a       b"]);
+        // Colors don't count, and carry over to the next row
+        assert_eq!(
+            wrap("\x1B[31mabcdef\x1B[0m", 4),
+            ["\x1B[31mabcd", "\x1B[31mef\x1B[0m"]
+        );
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode("[A"), Key::Up);
+        assert_eq!(decode("[6~"), Key::PageDown);
+        assert_eq!(decode("OH"), Key::Home);
+        assert_eq!(decode("[15~"), Key::Other);
+    }
+
+    #[test]
+    fn test_scroll_stays_within_the_pane() {
+        let mut view = View {
+            header: vec!["header".to_string()],
+            body: (1..=10).map(|n| format!("{n}\n")).collect(),
+            ..Default::default()
+        };
+        // 4 rows are left for the pane
+        let size = (8, 20);
+        assert!(view.scroll_by(Key::End, size));
+        assert_eq!(view.scroll, 6);
+        view.scroll_by(Key::Down, size);
+        assert_eq!(view.scroll, 6);
+        view.scroll_by(Key::PageUp, size);
+        assert_eq!(view.scroll, 2);
+        assert!(!view.scroll_by(Key::Char('h'), size));
+    }
+
+    #[test]
+    fn test_render_fills_the_screen() {
+        let view = View {
+            header: vec!["Progress".to_string()],
+            body: "line 1\nline 2\nline 3\n".to_string(),
+            scroll: 1,
+            footer: "q: quit".to_string(),
+        };
+        let screen = view.render((6, 10));
+        let rows: Vec<String> = screen
+            .trim_start_matches("\x1B[H")
+            .split("\r\n")
+            .map(|row| {
+                console::strip_ansi_codes(row)
+                    .trim_end_matches("\x1B[K")
+                    .to_string()
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                "Progress",
+                "──────────",
+                "line 2",
+                "line 3",
+                "──────────",
+                "q: … 2–3/3"
+            ]
+        );
+    }
+}
@@ -1,9 +1,8 @@
 macro_rules! warn {
-    ($fmt:literal, $ex:expr) => {{
+    ($fmt:literal $(, $arg:expr)* $(,)?) => {{
         use console::{style, Emoji};
-        use std::env;
-        let formatstr = format!($fmt, $ex);
-        if env::var("NO_EMOJI").is_ok() {
+        let formatstr = format!($fmt $(, $arg)*);
+        if !$crate::ui::wants_emoji() {
             println!("{} {}", style("!").red(), style(formatstr).red());
         } else {
             println!(
@@ -16,11 +15,10 @@ macro_rules! warn {
 }
 
 macro_rules! success {
-    ($fmt:literal, $ex:expr) => {{
+    ($fmt:literal $(, $arg:expr)* $(,)?) => {{
         use console::{style, Emoji};
-        use std::env;
-        let formatstr = format!($fmt, $ex);
-        if env::var("NO_EMOJI").is_ok() {
+        let formatstr = format!($fmt $(, $arg)*);
+        if !$crate::ui::wants_emoji() {
             println!("{} {}", style("✓").green(), style(formatstr).green());
         } else {
             println!(
@@ -31,3 +29,237 @@ macro_rules! success {
         }
     }};
 }
+
+use console::Emoji;
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use std::env;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+static COLOR: AtomicBool = AtomicBool::new(true);
+static EMOJI: AtomicBool = AtomicBool::new(true);
+static PLAIN: AtomicBool = AtomicBool::new(false);
+static ANSI: AtomicBool = AtomicBool::new(true);
+
+// Make sure the terminal understands the escape codes for colors and for
+// clearing the screen. Consoles on Windows only do once their virtual
+// terminal processing is turned on, which the console crate does when asked
+// whether colors are supported. Older consoles that can't turn it on, like
+// cmd.exe before Windows 10, would print the escape codes as garbage, so
+// they get output without colors and are cleared through the console API.
+pub fn setup_terminal() {
+    if cfg!(windows) && !console::Term::stdout().features().colors_supported() {
+        ANSI.store(false, Ordering::Relaxed);
+        disable_color();
+    }
+}
+
+// Print only plain text from now on: no colors, emoji, escape codes to clear
+// the screen or animated progress, for screen readers and dumb terminals
+pub fn enable_plain() {
+    PLAIN.store(true, Ordering::Relaxed);
+    disable_color();
+    disable_emoji();
+}
+
+pub fn is_plain() -> bool {
+    PLAIN.load(Ordering::Relaxed)
+}
+
+// Whether the NO_COLOR environment variable asks for output without colors,
+// see https://no-color.org
+pub fn no_color_requested() -> bool {
+    env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+// Print without colors from now on, including the output of the compiler
+pub fn disable_color() {
+    COLOR.store(false, Ordering::Relaxed);
+    console::set_colors_enabled(false);
+    console::set_colors_enabled_stderr(false);
+}
+
+pub fn wants_color() -> bool {
+    COLOR.load(Ordering::Relaxed)
+}
+
+// Print fallbacks instead of emoji from now on
+pub fn disable_emoji() {
+    EMOJI.store(false, Ordering::Relaxed);
+}
+
+// Whether emoji should be printed, which can also be turned off with NO_EMOJI
+pub fn wants_emoji() -> bool {
+    EMOJI.load(Ordering::Relaxed) && env::var_os("NO_EMOJI").is_none()
+}
+
+// An emoji that turns into its fallback if emoji are disabled
+pub fn emoji(emoji: &'static str, fallback: &'static str) -> Emoji<'static, 'static> {
+    if wants_emoji() {
+        Emoji(emoji, fallback)
+    } else {
+        Emoji(fallback, fallback)
+    }
+}
+
+/* Clears the terminal with an ANSI escape code.
+Works in UNIX and newer Windows terminals, older Windows consoles are cleared
+through the console API instead. In plain mode, an empty line separates the
+old output from the new one instead. */
+pub fn clear_screen() {
+    if is_plain() {
+        println!();
+    } else if !ANSI.load(Ordering::Relaxed) {
+        let _ = console::Term::stdout().clear_screen();
+    } else {
+        println!("\x1Bc");
+    }
+}
+
+// Start over at the top of an empty screen, like `clear_screen`, but
+// without wiping the terminal: the old output is scrolled up out of view
+// first, so it stays in the scrollback. Watch mode redraws this way, so the
+// errors it showed before can still be scrolled back to. Consoles without
+// escape codes can only be cleared.
+pub fn redraw_screen() {
+    if is_plain() {
+        println!();
+        return;
+    }
+    if !ANSI.load(Ordering::Relaxed) {
+        clear_screen();
+        return;
+    }
+    let (rows, _) = console::Term::stdout().size();
+    print!("{}\x1B[H", "\n".repeat(rows as usize));
+    let _ = std::io::Write::flush(&mut std::io::stdout());
+}
+
+// A spinner showing what's being done and for how long, so slow builds don't
+// look like Rustlings got stuck. It keeps spinning until it's cleared, which
+// has to happen before anything else is printed. In plain mode, the message
+// is printed once instead, and later messages aren't shown.
+pub fn spinner(message: String) -> ProgressBar {
+    if is_plain() {
+        println!("{message}");
+        return ProgressBar::hidden();
+    }
+    let spinner = ProgressBar::new_spinner();
+    spinner.set_style(
+        ProgressStyle::default_spinner()
+            .template("{spinner} {msg} {elapsed:.dim}")
+            .expect("Spinner template should be valid!"),
+    );
+    spinner.set_message(message);
+    spinner.enable_steady_tick(Duration::from_millis(100));
+    spinner
+}
+
+// The bar showing how many of the exercises are done,
+// which is only printed by `print_progress` in plain mode
+pub fn progress_bar(total: u64) -> ProgressBar {
+    if is_plain() {
+        return ProgressBar::with_draw_target(Some(total), ProgressDrawTarget::hidden());
+    }
+    let bar = ProgressBar::new(total);
+    bar.set_style(
+        ProgressStyle::default_bar()
+            .template("Progress: [{bar:60.green/red}] {pos}/{len} {msg}")
+            .expect("Progressbar template should be valid!")
+            .progress_chars("#>-"),
+    );
+    bar
+}
+
+// A bar like "[####------]" for how much of something is done, drawn as
+// text so it can be printed along with other output
+pub fn text_bar(done: usize, total: usize, width: usize) -> String {
+    let filled = (done * width).checked_div(total).unwrap_or(0).min(width);
+    format!(
+        "[{}{}]",
+        console::style("#".repeat(filled)).green(),
+        console::style("-".repeat(width - filled)).red()
+    )
+}
+
+// Print the final state of a progress bar as text in plain mode,
+// where it isn't drawn
+pub fn print_progress(bar: &ProgressBar) {
+    if is_plain() {
+        println!(
+            "Progress: {}/{} {}",
+            bar.position(),
+            bar.length().unwrap_or_default(),
+            bar.message()
+        );
+    }
+}
+
+// Ask the user a yes/no question, defaulting to no
+pub fn confirm(question: &str) -> bool {
+    use std::io::{self, Write};
+    print!("{question} [y/N] ");
+    let _ = io::stdout().flush();
+    let mut answer = String::new();
+    match io::stdin().read_line(&mut answer) {
+        Ok(_) => matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"),
+        Err(_) => false,
+    }
+}
+
+// Print the text under a header like "── Program output ──",
+// unless there's nothing to print
+pub fn print_section(title: &str, text: &str, color: console::Color) {
+    print!("{}", section(title, text, color));
+}
+
+// The text under a header like "── Program output ──", or nothing if there's
+// no text
+pub fn section(title: &str, text: &str, color: console::Color) -> String {
+    let text = text.trim_end();
+    if text.is_empty() {
+        return String::new();
+    }
+    let rule = if is_plain() { "--" } else { "──" };
+    format!(
+        "{}\n{text}\n",
+        console::style(format!("{rule} {title} {rule}"))
+            .fg(color)
+            .bold()
+    )
+}
+
+// Print a colored unified diff between two versions of a file.
+// Returns false if there were no differences to print.
+pub fn print_diff(old: &str, new: &str, old_name: &str, new_name: &str) -> bool {
+    let diff = diff(old, new, old_name, new_name);
+    print!("{diff}");
+    !diff.is_empty()
+}
+
+// A colored unified diff between two versions of a file, which is empty if
+// there are no differences
+pub fn diff(old: &str, new: &str, old_name: &str, new_name: &str) -> String {
+    use console::style;
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let diff = difflib::unified_diff(&old_lines, &new_lines, old_name, new_name, "", "", 3);
+    let mut text = String::new();
+    for line in &diff {
+        let line = line.trim_end_matches('\n');
+        let line = if line.starts_with("+++") || line.starts_with("---") {
+            style(line.trim_end()).bold().to_string()
+        } else if line.starts_with('+') {
+            style(line).green().to_string()
+        } else if line.starts_with('-') {
+            style(line).red().to_string()
+        } else if line.starts_with("@@") {
+            style(line).cyan().to_string()
+        } else {
+            line.to_string()
+        };
+        text.push_str(&line);
+        text.push('\n');
+    }
+    text
+}
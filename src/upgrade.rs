@@ -0,0 +1,370 @@
+// Updating Rustlings to its latest release, for those who installed a
+// prebuilt binary or ran `cargo install` a long time ago.
+//
+// The release is looked up with the GitHub API and downloaded with `curl`,
+// which the install scripts already rely on. Every binary of a release comes
+// with a `.sha256` file, which the download has to match.
+use crate::ui;
+use serde::Deserialize;
+use std::env::consts::{ARCH, OS};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const LATEST_RELEASE_URL: &str =
+    "https://api.github.com/repos/wirybeaver/rustlings/releases/latest";
+
+#[derive(Deserialize)]
+struct Release {
+    tag_name: String,
+    #[serde(default)]
+    assets: Vec<Asset>,
+}
+
+#[derive(Deserialize)]
+struct Asset {
+    name: String,
+    browser_download_url: String,
+}
+
+// Replace the running binary with the one of the latest release, if there's
+// a newer one. With `check`, only tell whether there is. Unless `force` is
+// given, the user is asked first.
+pub fn upgrade(check: bool, force: bool) -> Result<(), ()> {
+    let current = env!("CARGO_PKG_VERSION");
+    let release: Release = curl(&[
+        "-H",
+        "Accept: application/vnd.github+json",
+        LATEST_RELEASE_URL,
+    ])
+    .and_then(|body| serde_json::from_slice(&body).map_err(|e| e.to_string()))
+    .map_err(|e| {
+        warn!(
+            "Couldn't look up the latest release at {}",
+            LATEST_RELEASE_URL
+        );
+        println!("{e}");
+    })?;
+    let latest = release.tag_name.trim_start_matches('v');
+    if !is_newer(latest, current) {
+        success!("Rustlings {} is the latest version", current);
+        return Ok(());
+    }
+    println!("Rustlings {latest} is available, you have {current}.");
+    if check {
+        return Ok(());
+    }
+
+    let Some(asset) = pick_asset(&release.assets) else {
+        warn!("The release has no binary for {ARCH}-{OS}");
+        println!("Update the repository and run `cargo install --force --path .` instead.");
+        return Err(());
+    };
+    let checksum_name = format!("{}.sha256", asset.name);
+    let Some(checksum_asset) = release.assets.iter().find(|a| a.name == checksum_name) else {
+        warn!("The release has no checksum for {}", asset.name);
+        println!("Update the repository and run `cargo install --force --path .` instead.");
+        return Err(());
+    };
+    let exe = std::env::current_exe().map_err(|e| {
+        warn!("Couldn't find the Rustlings binary: {}", e);
+    })?;
+    if !force
+        && !ui::confirm(&format!(
+            "Replace {} with Rustlings {latest}?",
+            exe.display()
+        ))
+    {
+        println!("Nothing was upgraded.");
+        return Err(());
+    }
+
+    println!("Downloading {}...", asset.name);
+    let download = |asset: &Asset| {
+        curl(&["-L", &asset.browser_download_url]).map_err(|e| {
+            warn!("Couldn't download {}", asset.name);
+            println!("{e}");
+        })
+    };
+    let binary = download(asset)?;
+    let checksum = download(checksum_asset)?;
+    let expected = String::from_utf8_lossy(&checksum);
+    if parse_checksum(&expected) != Some(hex(&sha256(&binary))) {
+        warn!(
+            "{} doesn't match its checksum, so it wasn't installed",
+            asset.name
+        );
+        return Err(());
+    }
+    let old = replace(&exe, &binary).map_err(|e| {
+        warn!("Couldn't replace {}", exe.display());
+        println!("{e}");
+    })?;
+
+    // Make sure the new binary runs before declaring victory, and go back
+    // to the old one if it doesn't
+    let works = Command::new(&exe)
+        .arg("--version")
+        .output()
+        .is_ok_and(|output| output.status.success());
+    if !works {
+        warn!("The new binary at {} doesn't run", exe.display());
+        match roll_back(&exe, &old) {
+            Ok(()) => println!("Went back to Rustlings {current}."),
+            Err(e) => println!(
+                "Couldn't go back to the old binary at {}: {e}",
+                old.display()
+            ),
+        }
+        return Err(());
+    }
+    // Windows doesn't let a running binary be removed, it's cleaned up on
+    // the next upgrade instead
+    let _ = fs::remove_file(&old);
+    success!("Upgraded Rustlings to {}", latest);
+    Ok(())
+}
+
+// Fetch the URL with curl, returning what it printed to stderr if it failed
+fn curl(args: &[&str]) -> Result<Vec<u8>, String> {
+    let output = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail"])
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run curl: {e}"))?;
+    if output.status.success() {
+        Ok(output.stdout)
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+// Whether the version is newer than the other one, comparing the numbers
+// of both versions one by one
+pub fn is_newer(version: &str, than: &str) -> bool {
+    let numbers = |version: &str| -> Vec<u64> {
+        version
+            .split(['.', '-', '+'])
+            .map_while(|part| part.parse().ok())
+            .collect()
+    };
+    numbers(version) > numbers(than)
+}
+
+// The binary built for this platform, going by the names of the assets,
+// like `rustlings-x86_64-unknown-linux-gnu`
+fn pick_asset(assets: &[Asset]) -> Option<&Asset> {
+    let os_names: &[&str] = match OS {
+        "macos" => &["apple", "darwin", "macos"],
+        "windows" => &["windows"],
+        os => &[os],
+    };
+    assets.iter().find(|asset| {
+        let name = asset.name.to_lowercase();
+        name.contains(ARCH) && os_names.iter().any(|os| name.contains(os))
+    })
+}
+
+// Swap the binary for the new one, returning where the old one was moved to.
+// The running binary is moved out of the way first, which is the only way to
+// replace it on Windows. The old one is kept until the new one is known to
+// work.
+fn replace(exe: &Path, binary: &[u8]) -> std::io::Result<PathBuf> {
+    let new = with_suffix(exe, ".new");
+    let old = with_suffix(exe, ".old");
+    fs::write(&new, binary)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&new, fs::Permissions::from_mode(0o755))?;
+    }
+    fs::rename(exe, &old)?;
+    if let Err(e) = fs::rename(&new, exe) {
+        let _ = fs::rename(&old, exe);
+        return Err(e);
+    }
+    Ok(old)
+}
+
+// Put the old binary back in place of the new one
+fn roll_back(exe: &Path, old: &Path) -> std::io::Result<()> {
+    // Windows doesn't rename over existing files
+    let _ = fs::remove_file(exe);
+    fs::rename(old, exe)
+}
+
+// The hash in a checksum file, which is laid out like the output of
+// `sha256sum`: the hash in hex, followed by the file name
+fn parse_checksum(text: &str) -> Option<String> {
+    let hash = text.split_whitespace().next()?;
+    (hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit())).then(|| hash.to_lowercase())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+// The SHA-256 hash of the bytes, see FIPS 180-4
+fn sha256(bytes: &[u8]) -> [u8; 32] {
+    const K: [u32; 64] = [
+        0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4,
+        0xab1c5ed5, 0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe,
+        0x9bdc06a7, 0xc19bf174, 0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f,
+        0x4a7484aa, 0x5cb0a9dc, 0x76f988da, 0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7,
+        0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967, 0x27b70a85, 0x2e1b2138, 0x4d2c6dfc,
+        0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85, 0xa2bfe8a1, 0xa81a664b,
+        0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070, 0x19a4c116,
+        0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+        0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7,
+        0xc67178f2,
+    ];
+    let mut state: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+        0x5be0cd19,
+    ];
+    // The message is padded with a one bit, zeros and its length in bits
+    let mut message = bytes.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend((bytes.len() as u64 * 8).to_be_bytes());
+
+    for block in message.chunks_exact(64) {
+        let mut w = [0u32; 64];
+        for (i, word) in block.chunks_exact(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[i - 7])
+                .wrapping_add(s1);
+        }
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+        for i in 0..64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let t1 = h
+                .wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(K[i])
+                .wrapping_add(w[i]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(maj);
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+        for (word, value) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *word = word.wrapping_add(value);
+        }
+    }
+
+    let mut hash = [0; 32];
+    for (bytes, word) in hash.chunks_exact_mut(4).zip(state) {
+        bytes.copy_from_slice(&word.to_be_bytes());
+    }
+    hash
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_is_newer() {
+        assert!(is_newer("5.7.0", "5.6.1"));
+        assert!(is_newer("6.0.0", "5.6.1"));
+        assert!(!is_newer("5.6.1", "5.6.1"));
+        assert!(!is_newer("5.5.10", "5.6.1"));
+        assert!(!is_newer("5.6.1-beta", "5.6.1"));
+    }
+
+    #[test]
+    fn test_sha256() {
+        assert_eq!(
+            hex(&sha256(b"")),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            hex(&sha256(b"abc")),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        // Long enough for the padding to take a second block
+        assert_eq!(
+            hex(&sha256(
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+            )),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+        );
+    }
+
+    #[test]
+    fn test_parse_checksum() {
+        let hash = "E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855";
+        assert_eq!(
+            parse_checksum(&format!("{hash}  rustlings-x86_64-unknown-linux-gnu\n")),
+            Some(hash.to_lowercase())
+        );
+        assert_eq!(parse_checksum("Not Found"), None);
+        assert_eq!(parse_checksum(""), None);
+    }
+
+    #[test]
+    fn test_replace_and_roll_back() {
+        let dir = std::env::temp_dir().join(format!("rustlings-upgrade-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let exe = dir.join("rustlings");
+        fs::write(&exe, "old").unwrap();
+
+        let old = replace(&exe, b"new").unwrap();
+        assert_eq!(fs::read_to_string(&exe).unwrap(), "new");
+        assert_eq!(fs::read_to_string(&old).unwrap(), "old");
+        roll_back(&exe, &old).unwrap();
+        let restored = fs::read_to_string(&exe).unwrap();
+        let old_exists = old.exists();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(restored, "old");
+        assert!(!old_exists);
+    }
+
+    #[test]
+    fn test_pick_asset() {
+        let asset = |name: &str| Asset {
+            name: name.to_string(),
+            browser_download_url: format!("https://example.com/{name}"),
+        };
+        let assets = [
+            asset("rustlings-aarch64-unknown-linux-gnu"),
+            asset("rustlings-x86_64-unknown-linux-gnu"),
+            asset("rustlings-x86_64-apple-darwin"),
+            asset("rustlings-x86_64-pc-windows-msvc.exe"),
+        ];
+        let expected = match (ARCH, OS) {
+            ("x86_64", "linux") => Some("rustlings-x86_64-unknown-linux-gnu"),
+            ("aarch64", "linux") => Some("rustlings-aarch64-unknown-linux-gnu"),
+            ("x86_64", "macos") => Some("rustlings-x86_64-apple-darwin"),
+            ("x86_64", "windows") => Some("rustlings-x86_64-pc-windows-msvc.exe"),
+            _ => None,
+        };
+        assert_eq!(
+            pick_asset(&assets).map(|asset| asset.name.as_str()),
+            expected
+        );
+    }
+}
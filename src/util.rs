@@ -0,0 +1,22 @@
+// Small helpers for writing the reports and pages of several commands
+
+// Escape text for putting it into HTML
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!(
+            escape_html(r#"Vec<&str> is "fine""#),
+            "Vec&lt;&amp;str&gt; is &quot;fine&quot;"
+        );
+    }
+}
@@ -1,80 +1,502 @@
-use crate::exercise::{CompiledExercise, Exercise, Mode, State};
+use crate::diagnostics::{explain_panics, tidy};
+use crate::exercise::{CompiledExercise, Exercise, Mode, RunInput, State};
+use crate::git;
+use crate::hooks::{self, Event};
+use crate::markdown;
+use crate::state::{self, ProgressState};
+use crate::ui;
+use crate::webhook;
 use console::style;
-use indicatif::{ProgressBar, ProgressStyle};
-use std::{env, time::Duration};
+use indicatif::ProgressBar;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// How exercises should be checked, as requested on the command line
+#[derive(Clone, Copy)]
+pub struct VerifyOptions {
+    // Show the output of test harnesses
+    pub verbose: bool,
+    // Show the hints of an exercise once it compiles
+    pub success_hints: bool,
+    // Additionally lint the exercises with Clippy
+    pub clippy: bool,
+    // Additionally check that all exercises are formatted with rustfmt
+    pub fmt: bool,
+    // How long an exercise may run before it's killed
+    pub timeout: Duration,
+    // Commit exercises to git once they're solved
+    pub git_autocommit: bool,
+    // Check exercises again even if they passed before and haven't changed since
+    pub no_cache: bool,
+}
+
+impl VerifyOptions {
+    // Whether exercises that were solved and haven't changed since can be
+    // skipped. The recorded results don't say whether Clippy or rustfmt
+    // were involved, so they can't be trusted when those are requested.
+    pub fn use_cache(&self) -> bool {
+        !self.no_cache && !self.clippy && !self.fmt
+    }
+
+    // The options that a recorded result only holds for. A pass within a
+    // longer timeout may not be one within a shorter one, and skipping an
+    // exercise would hide the test output asked for with `--nocapture`.
+    pub fn cache_key(&self) -> String {
+        let verbose = if self.verbose { ",verbose" } else { "" };
+        format!("timeout={}ms{verbose}", self.timeout.as_millis())
+    }
+
+    // Whether the exercise passed with these options before and hasn't
+    // changed since, so it doesn't have to be checked again
+    pub fn is_cached(&self, state: &ProgressState, exercise: &Exercise) -> bool {
+        self.use_cache() && state.is_verified(exercise, &self.cache_key())
+    }
+}
 
 // Verify that the provided container of Exercise objects
 // can be compiled and run without any failures.
 // Any such failures will be reported to the end user.
-// If the Exercise being verified is a test, the verbose boolean
+// If the Exercise being verified is a test, `options.verbose`
 // determines whether or not the test harness outputs are displayed.
 pub fn verify<'a>(
     exercises: impl IntoIterator<Item = &'a Exercise>,
     progress: (usize, usize),
-    verbose: bool,
-    success_hints: bool,
+    options: &VerifyOptions,
+) -> Result<(), &'a Exercise> {
+    verify_after_change(exercises, progress, options, None)
+}
+
+// Verify the exercises like `verify`, after the exercise that was just
+// changed in watch mode. That one is type-checked before it's built, so
+// errors in it show up quickly. The others aren't, since building them
+// type-checks them anyway.
+pub fn verify_after_change<'a>(
+    exercises: impl IntoIterator<Item = &'a Exercise>,
+    progress: (usize, usize),
+    options: &VerifyOptions,
+    changed: Option<&Exercise>,
 ) -> Result<(), &'a Exercise> {
     let (num_done, total) = progress;
-    let bar = ProgressBar::new(total as u64);
+    let bar = ui::progress_bar(total as u64);
     let mut percentage = num_done as f32 / total as f32 * 100.0;
-    bar.set_style(
-        ProgressStyle::default_bar()
-            .template("Progress: [{bar:60.green/red}] {pos}/{len} {msg}")
-            .expect("Progressbar template should be valid!")
-            .progress_chars("#>-"),
-    );
     bar.set_position(num_done as u64);
     bar.set_message(format!("({:.1} %)", percentage));
 
+    let mut state = ProgressState::load();
     for exercise in exercises {
+        let unchanged = options.is_cached(&state, exercise);
+        if unchanged || state.is_skipped(exercise) {
+            percentage += 100.0 / total as f32;
+            bar.inc(1);
+            bar.set_message(format!("({:.1} %)", percentage));
+            continue;
+        }
+        state.start_attempt(exercise, state::now());
+        let check_first = changed.is_some_and(|changed| changed.name == exercise.name);
         let compile_result = match exercise.mode {
-            Mode::Test => compile_and_test(exercise, RunMode::Interactive, verbose, success_hints),
-            Mode::Compile => compile_and_run_interactively(exercise, success_hints),
-            Mode::Clippy => compile_only(exercise, success_hints),
+            Mode::Test | Mode::Async => compile_and_test(
+                exercise,
+                RunMode::Interactive,
+                options,
+                &RunInput::default(),
+                check_first,
+            ),
+            Mode::Compile | Mode::Wasm => {
+                compile_and_run_interactively(exercise, options, check_first)
+            }
+            Mode::Clippy => compile_only(exercise, options, check_first),
         };
-        if !compile_result.unwrap_or(false) {
+        let solved = *compile_result.as_ref().unwrap_or(&false);
+        record(&mut state, exercise, solved, options);
+        if let Err(output) = &compile_result {
+            state.record_error(exercise, first_error_line(output));
+            hooks::fire(Event::Failed, Some(exercise));
+        }
+        if !solved {
+            let _ = state.save();
+            ui::print_progress(&bar);
             return Err(exercise);
         }
         percentage += 100.0 / total as f32;
         bar.inc(1);
         bar.set_message(format!("({:.1} %)", percentage));
     }
+    let _ = state.save();
+    ui::print_progress(&bar);
     Ok(())
 }
 
+// Verify the exercises like `verify`, but compile and run them on `jobs`
+// threads first. Since the output of concurrent builds would be interleaved,
+// this pass is silent. Verification then continues sequentially from the
+// first exercise that didn't pass, so failures are reported just like before.
+pub fn verify_parallel<'a>(
+    exercises: impl IntoIterator<Item = &'a Exercise>,
+    progress: (usize, usize),
+    options: &VerifyOptions,
+    jobs: usize,
+) -> Result<(), &'a Exercise> {
+    let exercises: Vec<&Exercise> = exercises.into_iter().collect();
+    let (num_done, total) = progress;
+
+    let spinner = ui::spinner(format!(
+        "Verifying {} exercises using {jobs} jobs...",
+        exercises.len()
+    ));
+
+    let mut state = ProgressState::load();
+    let next = AtomicUsize::new(0);
+    // Whether each exercise passed or was skipped, and when checking it started
+    let results: Vec<(usize, ParallelResult, u64)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..jobs.max(1))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::SeqCst);
+                        let Some(exercise) = exercises.get(index) else {
+                            break;
+                        };
+                        let started = state::now();
+                        let result = if state.is_skipped(exercise) {
+                            ParallelResult::Skipped
+                        } else if options.is_cached(&state, exercise)
+                            || passes_quietly(exercise, options)
+                        {
+                            ParallelResult::Passed
+                        } else {
+                            ParallelResult::Failed
+                        };
+                        results.push((index, result, started));
+                    }
+                    results
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap_or_default())
+            .collect()
+    });
+    spinner.finish_and_clear();
+
+    let mut outcomes = vec![ParallelResult::Failed; exercises.len()];
+    let mut started = vec![0; exercises.len()];
+    for (index, result, start) in results {
+        outcomes[index] = result;
+        started[index] = start;
+    }
+    // Skipped exercises are left out like `verify` does, rather than
+    // holding up the exercises after them
+    let first_failure = outcomes
+        .iter()
+        .position(|outcome| *outcome == ParallelResult::Failed)
+        .unwrap_or(exercises.len());
+
+    let checked = exercises.iter().zip(outcomes).zip(started);
+    for ((exercise, outcome), started) in checked.take(first_failure) {
+        if outcome == ParallelResult::Passed {
+            state.start_attempt(exercise, started);
+            record(&mut state, exercise, true, options);
+        }
+    }
+    let _ = state.save();
+
+    verify(
+        exercises[first_failure..].iter().copied(),
+        (num_done + first_failure, total),
+        options,
+    )
+}
+
+// What became of an exercise in the silent pass of `verify_parallel`
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ParallelResult {
+    Passed,
+    Skipped,
+    Failed,
+}
+
+// Why `verify` left out an exercise, and the result it was last checked with
+pub struct Skip {
+    reason: &'static str,
+    result: String,
+}
+
+// Why `verify` leaves out exercises it doesn't check again, for
+// `verify --report-skipped`. This is decided before verifying, since the
+// exercises solved along the way count as verified afterwards.
+pub fn skip_reasons(exercises: &[Exercise], options: &VerifyOptions) -> Vec<Option<Skip>> {
+    let state = ProgressState::load();
+    exercises
+        .iter()
+        .map(|exercise| {
+            let progress = state.exercises.get(&exercise.name)?;
+            if options.is_cached(&state, exercise) {
+                Some(Skip {
+                    reason: "passed before and hasn't changed since",
+                    result: format!("{} with {}", style("passed").green(), progress.checked_with),
+                })
+            } else if state.is_skipped(exercise) {
+                let result = match progress.last_error {
+                    Some(_) => style("failed").red(),
+                    None => style("not checked yet").dim(),
+                };
+                Some(Skip {
+                    reason: "skipped by you",
+                    result: result.to_string(),
+                })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+// List which exercises `verify` left out along with their last result, and
+// which it checked, up to the one that failed if any did
+pub fn report_skipped(exercises: &[Exercise], skips: &[Option<Skip>], failed: Option<&Exercise>) {
+    let reached = failed
+        .and_then(|failed| exercises.iter().position(|e| e.name == failed.name))
+        .map_or(exercises.len(), |index| index + 1);
+    let (skipped, checked): (Vec<_>, Vec<_>) = exercises[..reached]
+        .iter()
+        .zip(skips)
+        .partition(|(_, skip)| skip.is_some());
+    println!("Not checked again:");
+    for (exercise, skip) in &skipped {
+        if let Some(skip) = skip {
+            println!("  {:<17} {} ({})", exercise.name, skip.result, skip.reason);
+        }
+    }
+    if skipped.is_empty() {
+        println!("  —");
+    }
+    println!("Checked just now:");
+    for (exercise, _) in &checked {
+        let result = if failed.is_some_and(|failed| failed.name == exercise.name) {
+            style("failed").red()
+        } else {
+            style("passed").green()
+        };
+        println!("  {:<17} {result}", exercise.name);
+    }
+    if checked.is_empty() {
+        println!("  —");
+    }
+}
+
+// Remember whether the exercise was solved. If it was solved just now, the
+// hooks and the webhook are told, and it's committed to git if that was
+// asked for.
+pub fn record(
+    state: &mut ProgressState,
+    exercise: &Exercise,
+    solved: bool,
+    options: &VerifyOptions,
+) {
+    if state.record(exercise, solved, &options.cache_key()) {
+        hooks::fire(Event::Solved, Some(exercise));
+        webhook::report_solved(exercise, state.session_id());
+        if options.git_autocommit {
+            git::commit_solution(exercise);
+        }
+    }
+}
+
+// Clippy exercises share a single Cargo.toml, so they can't be checked concurrently
+static CLIPPY_LOCK: Mutex<()> = Mutex::new(());
+
+// Compile and run the exercise without printing anything,
+// returning whether it passed and is marked as done
+fn passes_quietly(exercise: &Exercise, options: &VerifyOptions) -> bool {
+    check_quietly(exercise, options).0.is_ok()
+}
+
+// Compile and run the exercise without printing anything, returning the
+// output of the step that failed, along with how long compiling took
+pub fn check_quietly(
+    exercise: &Exercise,
+    options: &VerifyOptions,
+) -> (Result<(), String>, Duration) {
+    let start = Instant::now();
+    let compilation = match exercise.mode {
+        Mode::Clippy => {
+            let _guard = CLIPPY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+            exercise.compile()
+        }
+        Mode::Compile | Mode::Test | Mode::Wasm | Mode::Async => exercise.compile(),
+    };
+    let compile_time = start.elapsed();
+    let result = compilation
+        .map_err(|output| output.stderr)
+        .and_then(|compilation| match exercise.mode {
+            Mode::Clippy => Ok(()),
+            _ => compilation
+                .run(options.timeout)
+                .map(|_| ())
+                .map_err(|output| output.stdout + &output.stderr),
+        })
+        .and_then(|_| {
+            if needs_lint(exercise, options) {
+                exercise.lint().map_err(|output| output.stderr)?;
+            }
+            if needs_fmt_check(exercise, options) {
+                exercise
+                    .check_formatting()
+                    .map_err(|output| output.stdout)?;
+            }
+            if exercise.looks_done() {
+                Ok(())
+            } else {
+                Err("The exercise passes, but it's still marked with `I AM NOT DONE`".to_string())
+            }
+        });
+    (result, compile_time)
+}
+
+// How checking an exercise went with `verify --keep-going`
+pub struct Outcome<'a> {
+    pub exercise: &'a Exercise,
+    // The line explaining why the exercise failed, if it did
+    pub error: Option<String>,
+    // The whole output of the failed check
+    pub output: String,
+    // How long compiling the exercise took, unless it wasn't checked
+    pub compile_time: Option<Duration>,
+    // Whether the learner skipped the exercise without solving it
+    pub skipped: bool,
+}
+
+// Verify all the exercises, without stopping at the first one that fails.
+// Since the output of every failing exercise would be too much to take in,
+// the exercises are checked quietly, and a table of all of them follows,
+// with the first error line of those that failed and how long compiling
+// each of them took.
+pub fn verify_keep_going<'a>(
+    exercises: impl IntoIterator<Item = &'a Exercise>,
+    options: &VerifyOptions,
+) -> Result<(), ()> {
+    print_outcomes(&check_all(exercises, options, false))
+}
+
+// Check all the exercises quietly, recording the results in the state. With
+// `fresh`, skipped exercises and those solved before are checked as well, so
+// the outcomes don't depend on what the learner did locally.
+pub fn check_all<'a>(
+    exercises: impl IntoIterator<Item = &'a Exercise>,
+    options: &VerifyOptions,
+    fresh: bool,
+) -> Vec<Outcome<'a>> {
+    let exercises: Vec<&Exercise> = exercises.into_iter().collect();
+    let bar = ui::progress_bar(exercises.len() as u64);
+    let mut state = ProgressState::load();
+    let mut outcomes = Vec::new();
+    for exercise in exercises {
+        bar.set_message(exercise.name.clone());
+        let skipped = !fresh && state.is_skipped(exercise);
+        let unchanged = !fresh && options.is_cached(&state, exercise);
+        if skipped || unchanged {
+            outcomes.push(Outcome {
+                exercise,
+                error: None,
+                output: String::new(),
+                compile_time: None,
+                skipped,
+            });
+            bar.inc(1);
+            continue;
+        }
+        let (result, compile_time) = check_quietly(exercise, options);
+        record(&mut state, exercise, result.is_ok(), options);
+        let (error, output) = match result {
+            Ok(()) => (None, String::new()),
+            Err(output) => {
+                let line = first_error_line(&output);
+                state.record_error(exercise, line.clone());
+                hooks::fire(Event::Failed, Some(exercise));
+                (Some(line.unwrap_or_default()), output)
+            }
+        };
+        outcomes.push(Outcome {
+            exercise,
+            error,
+            output,
+            compile_time: Some(compile_time),
+            skipped: false,
+        });
+        bar.inc(1);
+    }
+    let _ = state.save();
+    bar.finish_and_clear();
+    outcomes
+}
+
+// Print a table of the outcomes, failing if any exercise failed
+pub fn print_outcomes(outcomes: &[Outcome]) -> Result<(), ()> {
+    println!("{:<17}\t{:<12}\tFirst error", "Name", "Compile time");
+    for outcome in outcomes {
+        let compile_time = outcome.compile_time.map_or_else(
+            || "—".to_string(),
+            |time| format!("{:.1}s", time.as_secs_f32()),
+        );
+        let line = format!("{:<17}\t{:<12}", outcome.exercise.name, compile_time);
+        match &outcome.error {
+            Some(error) => println!("{}\t{}", style(line).red(), error),
+            None if outcome.skipped => println!("{line}\t{}", style("Skipped").yellow()),
+            None => println!("{line}"),
+        }
+    }
+    println!();
+    let failed = outcomes.iter().filter(|o| o.error.is_some()).count();
+    if failed == 0 {
+        success!("All {} exercises passed", outcomes.len());
+        Ok(())
+    } else {
+        warn!("{failed} of {} exercises failed", outcomes.len());
+        Err(())
+    }
+}
+
 enum RunMode {
     Interactive,
     NonInteractive,
 }
 
 // Compile and run the resulting test harness of the given Exercise
-pub fn test(exercise: &Exercise, verbose: bool) -> Result<(), ()> {
-    compile_and_test(exercise, RunMode::NonInteractive, verbose, false)?;
+pub fn test(exercise: &Exercise, options: &VerifyOptions, input: &RunInput) -> Result<(), ()> {
+    compile_and_test(exercise, RunMode::NonInteractive, options, input, false).map_err(|_| ())?;
     Ok(())
 }
 
 // Invoke the rust compiler without running the resulting binary
-fn compile_only(exercise: &Exercise, success_hints: bool) -> Result<bool, ()> {
-    let progress_bar = ProgressBar::new_spinner();
-    progress_bar.set_message(format!("Compiling {exercise}..."));
-    progress_bar.enable_steady_tick(Duration::from_millis(100));
+fn compile_only(
+    exercise: &Exercise,
+    options: &VerifyOptions,
+    check_first: bool,
+) -> Result<bool, String> {
+    let progress_bar = ui::spinner(format!("Compiling {exercise}..."));
 
-    let _ = compile(exercise, &progress_bar)?;
+    let _ = compile(exercise, options, &progress_bar, check_first)?;
     progress_bar.finish_and_clear();
 
-    Ok(prompt_for_completion(exercise, None, success_hints))
+    Ok(prompt_for_completion(exercise, None, options.success_hints))
 }
 
 // Compile the given Exercise and run the resulting binary in an interactive mode
-fn compile_and_run_interactively(exercise: &Exercise, success_hints: bool) -> Result<bool, ()> {
-    let progress_bar = ProgressBar::new_spinner();
-    progress_bar.set_message(format!("Compiling {exercise}..."));
-    progress_bar.enable_steady_tick(Duration::from_millis(100));
+fn compile_and_run_interactively(
+    exercise: &Exercise,
+    options: &VerifyOptions,
+    check_first: bool,
+) -> Result<bool, String> {
+    let progress_bar = ui::spinner(format!("Compiling {exercise}..."));
 
-    let compilation = compile(exercise, &progress_bar)?;
+    let compilation = compile(exercise, options, &progress_bar, check_first)?;
 
     progress_bar.set_message(format!("Running {exercise}..."));
-    let result = compilation.run();
+    let result = compilation.run(options.timeout);
     progress_bar.finish_and_clear();
 
     let output = match result {
@@ -83,14 +505,14 @@ fn compile_and_run_interactively(exercise: &Exercise, success_hints: bool) -> Re
             warn!("Ran {} with errors", exercise);
             println!("{}", output.stdout);
             println!("{}", output.stderr);
-            return Err(());
+            return Err(output.stderr);
         }
     };
 
     Ok(prompt_for_completion(
         exercise,
         Some(output.stdout),
-        success_hints,
+        options.success_hints,
     ))
 }
 
@@ -99,24 +521,27 @@ fn compile_and_run_interactively(exercise: &Exercise, success_hints: bool) -> Re
 fn compile_and_test(
     exercise: &Exercise,
     run_mode: RunMode,
-    verbose: bool,
-    success_hints: bool,
-) -> Result<bool, ()> {
-    let progress_bar = ProgressBar::new_spinner();
-    progress_bar.set_message(format!("Testing {exercise}..."));
-    progress_bar.enable_steady_tick(Duration::from_millis(100));
+    options: &VerifyOptions,
+    input: &RunInput,
+    check_first: bool,
+) -> Result<bool, String> {
+    let progress_bar = ui::spinner(format!("Testing {exercise}..."));
 
-    let compilation = compile(exercise, &progress_bar)?;
-    let result = compilation.run();
+    let compilation = compile(exercise, options, &progress_bar, check_first)?;
+    let input = RunInput {
+        nocapture: input.nocapture || options.verbose,
+        ..*input
+    };
+    let result = compilation.run_with(&input, options.timeout);
     progress_bar.finish_and_clear();
 
     match result {
         Ok(output) => {
-            if verbose {
+            if options.verbose {
                 println!("{}", output.stdout);
             }
             if let RunMode::Interactive = run_mode {
-                Ok(prompt_for_completion(exercise, None, success_hints))
+                Ok(prompt_for_completion(exercise, None, options.success_hints))
             } else {
                 Ok(true)
             }
@@ -126,32 +551,130 @@ fn compile_and_test(
                 "Testing of {} failed! Please try again. Here's the output:",
                 exercise
             );
+            // Panics inside the runtime are hard to spot in the output
+            if let Mode::Async = exercise.mode {
+                if let Some(explanation) = explain_panics(&output.stdout) {
+                    println!("{}\n", style(explanation).bold());
+                }
+            }
             println!("{}", output.stdout);
-            Err(())
+            if !output.stderr.is_empty() {
+                println!("{}", output.stderr);
+            }
+            Err(output.stdout + &output.stderr)
         }
     }
 }
 
 // Compile the given Exercise and return an object with information
-// about the state of the compilation
+// about the state of the compilation, or the compiler output on failure.
+// With `check_first`, it's type-checked before it's built.
 fn compile<'a>(
     exercise: &'a Exercise,
+    options: &VerifyOptions,
     progress_bar: &ProgressBar,
-) -> Result<CompiledExercise<'a>, ()> {
-    let compilation_result = exercise.compile();
+    check_first: bool,
+) -> Result<CompiledExercise<'a>, String> {
+    // Type errors show up much sooner this way than after a full build
+    let checked = if check_first {
+        let message = progress_bar.message();
+        progress_bar.set_message(format!("Checking {exercise}..."));
+        let checked = exercise.check();
+        progress_bar.set_message(message);
+        checked
+    } else {
+        Ok(())
+    };
+    let compilation_result = checked.and_then(|_| exercise.compile());
 
-    match compilation_result {
-        Ok(compilation) => Ok(compilation),
+    let compilation = match compilation_result {
+        Ok(compilation) => compilation,
         Err(output) => {
             progress_bar.finish_and_clear();
             warn!(
                 "Compiling of {} failed! Please try again. Here's the output:",
                 exercise
             );
-            println!("{}", output.stderr);
-            Err(())
+            println!("{}", tidy(&output.stderr));
+            return Err(output.stderr);
         }
+    };
+
+    check_style(exercise, options, progress_bar)?;
+    Ok(compilation)
+}
+
+// Run the checks that come on top of compiling the exercise,
+// returning the output of the first one that failed
+pub fn check_style(
+    exercise: &Exercise,
+    options: &VerifyOptions,
+    progress_bar: &ProgressBar,
+) -> Result<(), String> {
+    if needs_lint(exercise, options) {
+        progress_bar.set_message(format!("Linting {exercise}..."));
+        lint(exercise, progress_bar)?;
     }
+    if needs_fmt_check(exercise, options) {
+        progress_bar.set_message(format!("Checking the formatting of {exercise}..."));
+        check_formatting(exercise, progress_bar)?;
+    }
+    Ok(())
+}
+
+// Whether the exercise should be linted on top of being compiled.
+// Clippy exercises are already linted while they're compiled.
+pub fn needs_lint(exercise: &Exercise, options: &VerifyOptions) -> bool {
+    options.clippy && !matches!(exercise.mode, Mode::Clippy)
+}
+
+// Whether the formatting of the exercise should be checked,
+// either because it was requested or because the exercise demands it
+pub fn needs_fmt_check(exercise: &Exercise, options: &VerifyOptions) -> bool {
+    options.fmt || exercise.check_fmt
+}
+
+// Lint the given Exercise with Clippy, returning its output on failure
+fn lint(exercise: &Exercise, progress_bar: &ProgressBar) -> Result<(), String> {
+    exercise.lint().map_err(|output| {
+        progress_bar.finish_and_clear();
+        warn!(
+            "Clippy found problems in {}! Please try again. Here's the output:",
+            exercise
+        );
+        println!("{}", tidy(&output.stderr));
+        output.stderr
+    })
+}
+
+// Check that the given Exercise is formatted, showing the changes rustfmt
+// would make and returning them on failure
+fn check_formatting(exercise: &Exercise, progress_bar: &ProgressBar) -> Result<(), String> {
+    exercise.check_formatting().map_err(|output| {
+        progress_bar.finish_and_clear();
+        warn!(
+            "{} isn't formatted! Run `rustfmt` on it or apply these changes:",
+            exercise
+        );
+        println!("{}", output.stdout);
+        println!("{}", output.stderr);
+        output.stdout
+    })
+}
+
+// Pick the line of a compiler or test harness output that explains best what went wrong
+pub fn first_error_line(output: &str) -> Option<String> {
+    let output = console::strip_ansi_codes(output);
+    let lines = || {
+        output
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+    };
+    lines()
+        .find(|line| line.starts_with("error") || line.contains("panicked"))
+        .or_else(|| lines().next())
+        .map(str::to_string)
 }
 
 fn prompt_for_completion(
@@ -165,11 +688,12 @@ fn prompt_for_completion(
     };
     match exercise.mode {
         Mode::Compile => success!("Successfully ran {}!", exercise),
-        Mode::Test => success!("Successfully tested {}!", exercise),
+        Mode::Test | Mode::Async => success!("Successfully tested {}!", exercise),
         Mode::Clippy => success!("Successfully compiled {}!", exercise),
+        Mode::Wasm => success!("Successfully built {}!", exercise),
     }
 
-    let no_emoji = env::var("NO_EMOJI").is_ok();
+    let no_emoji = !ui::wants_emoji();
 
     let clippy_success_msg = if no_emoji {
         "The code is compiling, and Clippy is happy!"
@@ -179,8 +703,9 @@ fn prompt_for_completion(
 
     let success_msg = match exercise.mode {
         Mode::Compile => "The code is compiling!",
-        Mode::Test => "The code is compiling, and the tests pass!",
+        Mode::Test | Mode::Async => "The code is compiling, and the tests pass!",
         Mode::Clippy => clippy_success_msg,
+        Mode::Wasm => "The code compiles to WebAssembly!",
     };
     println!();
     if no_emoji {
@@ -200,7 +725,8 @@ fn prompt_for_completion(
     if success_hints {
         println!("Hints:");
         println!("{}", separator());
-        println!("{}", exercise.hint);
+        let hints: Vec<String> = exercise.hints().into_iter().map(markdown::render).collect();
+        println!("{}", hints.join("\n\n"));
         println!("{}", separator());
         println!();
     }
@@ -232,3 +758,23 @@ fn prompt_for_completion(
 fn separator() -> console::StyledObject<&'static str> {
     style("====================").bold()
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_first_error_line() {
+        let output =
+            "\x1b[1m\x1b[31merror[E0425]\x1b[0m: cannot find value `x`\n --> intro2.rs:3:5\n";
+        assert_eq!(
+            first_error_line(output).as_deref(),
+            Some("error[E0425]: cannot find value `x`")
+        );
+        assert_eq!(
+            first_error_line("\nrunning 1 test\n").as_deref(),
+            Some("running 1 test")
+        );
+        assert_eq!(first_error_line(""), None);
+    }
+}
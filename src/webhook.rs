@@ -0,0 +1,102 @@
+// Reporting progress to online courses and bootcamps, which collect it from
+// the machines of their learners. It's off unless `progress_webhook` is set
+// in the config, and then every solved exercise is posted to that URL as
+// JSON, with the name of the exercise, the time and an anonymous session ID
+// that is kept in the state file. Nothing else about the learner is sent.
+//
+// Like upgrading, the request is made with `curl`, so Rustlings doesn't have
+// to come with an HTTP client of its own.
+use crate::exercise::Exercise;
+use serde::Serialize;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// How long the request may take, in seconds
+const MAX_TIME_SECS: u32 = 10;
+
+#[derive(Serialize, Debug)]
+struct Solved<'a> {
+    event: &'static str,
+    exercise: &'a str,
+    // Seconds since the Unix epoch, like the times in the state file
+    timestamp: u64,
+    session_id: &'a str,
+}
+
+static WEBHOOK: OnceLock<String> = OnceLock::new();
+
+// Post solved exercises to the given URL from now on
+pub fn enable(url: String) {
+    let _ = WEBHOOK.set(url);
+}
+
+// Post that the exercise was solved, if a webhook is set. It's posted in the
+// background, and a request that fails is only reported, since neither
+// should hold up the learner.
+pub fn report_solved(exercise: &Exercise, session_id: &str) {
+    let Some(url) = WEBHOOK.get() else {
+        return;
+    };
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|time| time.as_secs())
+        .unwrap_or_default();
+    let body = payload(&exercise.name, timestamp, session_id);
+    let spawned = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--output"])
+        .arg(if cfg!(windows) { "NUL" } else { "/dev/null" })
+        // Only ever post to web servers, whatever the URL says
+        .args(["--proto", "=http,https", "--max-time"])
+        .arg(MAX_TIME_SECS.to_string())
+        .args(["--header", "Content-Type: application/json"])
+        .args(["--data-binary", "@-", url])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn();
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(e) => {
+            println!("Couldn't report the progress to {url}: failed to run curl: {e}");
+            return;
+        }
+    };
+    // The body is small enough for the pipe, so this doesn't wait for curl.
+    // Dropping stdin closes it, which tells curl that the body is complete.
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(body.as_bytes());
+    }
+    thread::spawn(move || match child.wait_with_output() {
+        Ok(output) if !output.status.success() => println!(
+            "Couldn't report the progress to {url}: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ),
+        _ => {}
+    });
+}
+
+fn payload(exercise: &str, timestamp: u64, session_id: &str) -> String {
+    serde_json::to_string(&Solved {
+        event: "solved",
+        exercise,
+        timestamp,
+        session_id,
+    })
+    .expect("Failed to serialize the progress")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_payload() {
+        assert_eq!(
+            payload("intro1", 1700000000, "0123456789abcdef"),
+            r#"{"event":"solved","exercise":"intro1","timestamp":1700000000,"session_id":"0123456789abcdef"}"#
+        );
+    }
+}
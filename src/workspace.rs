@@ -0,0 +1,120 @@
+// Building all exercises as binaries of a single Cargo package, instead of
+// compiling each of them on its own with rustc. The exercises then share a
+// target directory and incremental compilation, which makes rebuilding an
+// exercise after a small change a lot faster.
+//
+// The generated manifest lives in `.rustlings/workspace` and refers to the
+// exercises relative to the course, so it works for any copy of the course.
+use crate::exercise::{Exercise, Mode};
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+const WORKSPACE_DIR: &str = ".rustlings/workspace";
+
+// How the exercises are built
+#[derive(Deserialize, ValueEnum, Copy, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Backend {
+    // Every exercise on its own with rustc
+    Rustc,
+    // All exercises as binaries of one Cargo package
+    Workspace,
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+// Build the given exercises through the workspace from now on
+pub fn enable(exercises: &[Exercise]) -> Result<(), String> {
+    let cargo_toml = cargo_toml(exercises)?;
+    let path = manifest_path();
+    // Rewriting an unchanged manifest would make Cargo check it all again
+    if fs::read_to_string(&path).ok().as_deref() != Some(cargo_toml.as_str()) {
+        fs::create_dir_all(WORKSPACE_DIR)
+            .and_then(|_| fs::write(&path, cargo_toml))
+            .map_err(|e| format!("Couldn't write {}: {e}", path.display()))?;
+    }
+    ENABLED.store(true, Ordering::Relaxed);
+    Ok(())
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+pub fn manifest_path() -> PathBuf {
+    Path::new(WORKSPACE_DIR).join("Cargo.toml")
+}
+
+// A package with a binary for every exercise and the dependencies of all of
+// them. Clippy exercises keep their own manifest, and exercises whose files
+// are missing are left out, since Cargo refuses manifests with missing targets.
+fn cargo_toml(exercises: &[Exercise]) -> Result<String, String> {
+    let mut bins = String::new();
+    let mut dependencies = toml::Table::new();
+    for exercise in exercises {
+        if matches!(exercise.mode, Mode::Clippy) || !exercise.main_file().exists() {
+            continue;
+        }
+        let path = Path::new("../..").join(exercise.main_file());
+        bins.push_str(&format!(
+            "[[bin]]\nname = \"{}\"\npath = '{}'\n\n",
+            exercise.name,
+            path.display()
+        ));
+        dependencies.extend(exercise.dependency_table()?);
+    }
+    let mut manifest = toml::Table::new();
+    manifest.insert("dependencies".into(), toml::Value::Table(dependencies));
+    let dependencies = toml::to_string(&manifest).map_err(|e| e.to_string())?;
+    Ok(format!(
+        r#"[package]
+name = "exercises"
+version = "0.0.1"
+edition = "2021"
+
+[workspace]
+
+{bins}{dependencies}"#
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn exercise(name: &str, path: &str, mode: Mode) -> Exercise {
+        Exercise {
+            mode,
+            ..Exercise::new(name, path)
+        }
+    }
+
+    #[test]
+    fn test_cargo_toml() {
+        let exercises = [
+            exercise(
+                "compSuccess",
+                "tests/fixture/success/compSuccess.rs",
+                Mode::Compile,
+            ),
+            exercise(
+                "multiFile",
+                "tests/fixture/success/multiFile",
+                Mode::Compile,
+            ),
+            exercise("missing", "tests/fixture/success/missing.rs", Mode::Test),
+            exercise("lint", "tests/fixture/success/compSuccess.rs", Mode::Clippy),
+        ];
+        let cargo_toml = cargo_toml(&exercises).unwrap();
+        assert!(cargo_toml.contains(
+            "[[bin]]\nname = \"compSuccess\"\npath = '../../tests/fixture/success/compSuccess.rs'\n"
+        ));
+        assert!(cargo_toml.contains("path = '../../tests/fixture/success/multiFile/main.rs'"));
+        assert!(!cargo_toml.contains("missing"));
+        assert!(!cargo_toml.contains("\"lint\""));
+        assert!(cargo_toml.ends_with("[dependencies]\n"));
+    }
+}
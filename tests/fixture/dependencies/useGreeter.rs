@@ -0,0 +1,8 @@
+fn main() {
+    println!("{}", greeter::greet());
+}
+
+#[test]
+fn greets() {
+    assert_eq!(greeter::greet(), "Hello from a dependency!");
+}
@@ -0,0 +1,7 @@
+fn main() {
+    let mut total = 0;
+    for x in Some(12) {
+        total += x;
+    }
+    println!("{total}");
+}
@@ -0,0 +1,10 @@
+use std::io::Read;
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    println!("args: {}", args.join(" "));
+
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input).unwrap();
+    println!("stdin: {}", input.trim());
+}
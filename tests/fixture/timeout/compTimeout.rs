@@ -0,0 +1,4 @@
+fn main() {
+    #[allow(clippy::empty_loop)]
+    loop {}
+}
@@ -3,8 +3,140 @@ use glob::glob;
 use predicates::boolean::PredicateBooleanExt;
 use std::fs::File;
 use std::io::Read;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+// Copy a fixture course to a new directory, so that what the test leaves
+// behind, like the state file or backups, doesn't end up in the repository
+fn copy_fixture(fixture: &str, test: &str) -> PathBuf {
+    fn copy_dir(from: &Path, to: &Path) {
+        std::fs::create_dir_all(to).unwrap();
+        for entry in std::fs::read_dir(from).unwrap() {
+            let entry = entry.unwrap();
+            let name = entry.file_name();
+            if name.to_string_lossy().starts_with(".rustlings") {
+                continue;
+            }
+            if entry.file_type().unwrap().is_dir() {
+                copy_dir(&entry.path(), &to.join(name));
+            } else {
+                std::fs::copy(entry.path(), to.join(name)).unwrap();
+            }
+        }
+    }
+    let dir = std::env::temp_dir().join(format!("rustlings-{test}-{}", std::process::id()));
+    copy_dir(&Path::new("tests/fixture").join(fixture), &dir);
+    dir
+}
+
+// Add the `I AM NOT DONE` comment to an exercise. The exercises of the
+// fixtures have none, so they look done until they're checked.
+fn mark_not_done(path: &Path) {
+    let source = std::fs::read_to_string(path).unwrap();
+    std::fs::write(path, format!("// I AM NOT DONE\n{source}")).unwrap();
+}
+
+// A course in a temporary directory with the exercise made of several files
+// from the success fixture, at `exercises/multiFile`
+fn directory_exercise_dir(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rustlings-{test}-{}", std::process::id()));
+    let exercise = dir.join("exercises/multiFile");
+    std::fs::create_dir_all(&exercise).unwrap();
+    for file in ["main.rs", "greeting.rs"] {
+        std::fs::copy(
+            Path::new("tests/fixture/success/multiFile").join(file),
+            exercise.join(file),
+        )
+        .unwrap();
+    }
+    std::fs::write(
+        dir.join("info.toml"),
+        "[[exercises]]\nname = \"multiFile\"\npath = \"exercises/multiFile\"\nmode = \"compile\"\nhint = \"\"\n",
+    )
+    .unwrap();
+    dir
+}
+
+// Who commits in the git repositories of the tests
+const GIT_AUTHOR: [(&str, &str); 4] = [
+    ("GIT_AUTHOR_NAME", "Ferris"),
+    ("GIT_AUTHOR_EMAIL", "ferris@example.com"),
+    ("GIT_COMMITTER_NAME", "Ferris"),
+    ("GIT_COMMITTER_EMAIL", "ferris@example.com"),
+];
+
+// Run git in the directory, failing the test if git fails
+fn git(dir: &Path, args: &[&str]) {
+    let status = std::process::Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .envs(GIT_AUTHOR)
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+// Make the directory a git repository with all its files committed
+fn commit_all(dir: &Path) {
+    git(dir, &["init", "--quiet"]);
+    git(dir, &["add", "."]);
+    git(dir, &["commit", "--quiet", "-m", "start"]);
+}
+
+// A course in a temporary directory with a single exercise named `exercise`
+// at `exercises/exercise.rs`
+fn single_exercise_dir(test: &str, mode: &str, source: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rustlings-{test}-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("exercises")).unwrap();
+    std::fs::write(dir.join("exercises/exercise.rs"), source).unwrap();
+    std::fs::write(
+        dir.join("info.toml"),
+        format!("[[exercises]]\nname = \"exercise\"\npath = \"exercises/exercise.rs\"\nmode = \"{mode}\"\nhint = \"\"\n"),
+    )
+    .unwrap();
+    dir
+}
+
+// Start watch mode on the exercise of `single_exercise_dir`, change the
+// exercise once it's checked, and return what watch mode printed until it
+// finished, or until it was stopped 10 seconds later
+fn watch_after_change(dir: &Path, args: &[&str], source: &str) -> String {
+    let mut watch = std::process::Command::new(assert_cmd::cargo::cargo_bin("rustlings"))
+        .arg("watch")
+        .args(args)
+        .current_dir(dir)
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    std::thread::sleep(std::time::Duration::from_secs(3));
+    std::fs::write(dir.join("exercises/exercise.rs"), source).unwrap();
+    let mut status = None;
+    for _ in 0..100 {
+        status = watch.try_wait().unwrap();
+        if status.is_some() {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+    if status.is_none() {
+        watch.kill().unwrap();
+    }
+    String::from_utf8(watch.wait_with_output().unwrap().stdout).unwrap()
+}
+
+// Hooks run in the background, so they may finish after rustlings
+fn wait_for_file(path: &Path) -> String {
+    for _ in 0..50 {
+        let contents = std::fs::read_to_string(path).unwrap_or_default();
+        if !contents.is_empty() {
+            return contents;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+    String::new()
+}
+
 #[test]
 fn runs_without_arguments() {
     let mut cmd = Command::cargo_bin("rustlings").unwrap();
@@ -22,72 +154,142 @@ fn fails_when_in_wrong_dir() {
 
 #[test]
 fn verify_all_success() {
+    let dir = copy_fixture("success", "verify-all-success");
     Command::cargo_bin("rustlings")
         .unwrap()
         .arg("verify")
-        .current_dir("tests/fixture/success")
+        .current_dir(&dir)
         .assert()
         .success();
+    std::fs::remove_dir_all(&dir).unwrap();
 }
 
 #[test]
 fn verify_fails_if_some_fails() {
+    let dir = copy_fixture("failure", "verify-fails-if-some-fails");
     Command::cargo_bin("rustlings")
         .unwrap()
         .arg("verify")
-        .current_dir("tests/fixture/failure")
+        .current_dir(&dir)
         .assert()
         .code(1);
+    std::fs::remove_dir_all(&dir).unwrap();
 }
 
 #[test]
 fn run_single_compile_success() {
+    let dir = copy_fixture("success", "run-single-compile-success");
     Command::cargo_bin("rustlings")
         .unwrap()
         .args(["run", "compSuccess"])
-        .current_dir("tests/fixture/success/")
+        .current_dir(&dir)
+        .assert()
+        .success();
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn run_with_workspace_backend() {
+    let success = copy_fixture("success", "run-with-workspace-backend-success");
+    let failure = copy_fixture("failure", "run-with-workspace-backend-failure");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--backend", "workspace", "run", "testSuccess"])
+        .current_dir(&success)
         .assert()
         .success();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--backend", "workspace", "run", "compFailure"])
+        .current_dir(&failure)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(
+            "First error at compFailure.rs:3:1",
+        ));
+    std::fs::remove_dir_all(&success).unwrap();
+    std::fs::remove_dir_all(&failure).unwrap();
+}
+
+#[test]
+fn run_labels_program_output_and_compiler_messages() {
+    let success = copy_fixture(
+        "success",
+        "run-labels-program-output-and-compiler-messages-success",
+    );
+    let failure = copy_fixture(
+        "failure",
+        "run-labels-program-output-and-compiler-messages-failure",
+    );
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--plain", "run", "compArgs", "--", "hello"])
+        .current_dir(&success)
+        .assert()
+        .code(0)
+        .stdout(predicates::str::contains(
+            "-- Program output --\nargs: hello",
+        ));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--plain", "run", "compFailure"])
+        .current_dir(&failure)
+        .assert()
+        .code(1)
+        .stdout(
+            predicates::str::contains("-- Compiler messages --")
+                .and(predicates::str::contains("Program output").not()),
+        );
+    std::fs::remove_dir_all(&success).unwrap();
+    std::fs::remove_dir_all(&failure).unwrap();
 }
 
 #[test]
 fn run_single_compile_failure() {
+    let dir = copy_fixture("failure", "run-single-compile-failure");
     Command::cargo_bin("rustlings")
         .unwrap()
         .args(["run", "compFailure"])
-        .current_dir("tests/fixture/failure/")
+        .current_dir(&dir)
         .assert()
         .code(1);
+    std::fs::remove_dir_all(&dir).unwrap();
 }
 
 #[test]
 fn run_single_test_success() {
+    let dir = copy_fixture("success", "run-single-test-success");
     Command::cargo_bin("rustlings")
         .unwrap()
         .args(["run", "testSuccess"])
-        .current_dir("tests/fixture/success/")
+        .current_dir(&dir)
         .assert()
         .success();
+    std::fs::remove_dir_all(&dir).unwrap();
 }
 
 #[test]
 fn run_single_test_failure() {
+    let dir = copy_fixture("failure", "run-single-test-failure");
     Command::cargo_bin("rustlings")
         .unwrap()
         .args(["run", "testFailure"])
-        .current_dir("tests/fixture/failure/")
+        .current_dir(&dir)
         .assert()
         .code(1);
+    std::fs::remove_dir_all(&dir).unwrap();
 }
 
 #[test]
 fn run_single_test_not_passed() {
+    let dir = copy_fixture("failure", "run-single-test-not-passed");
     Command::cargo_bin("rustlings")
         .unwrap()
         .args(["run", "testNotPassed.rs"])
-        .current_dir("tests/fixture/failure/")
+        .current_dir(&dir)
         .assert()
         .code(1);
+    std::fs::remove_dir_all(&dir).unwrap();
 }
 
 #[test]
@@ -120,6 +322,9 @@ fn reset_single_exercise() {
         .args(["reset", "intro1"])
         .assert()
         .code(0);
+    // The reset backs up intro1 in the repository itself
+    std::fs::remove_dir_all(".rustlings/backups/intro1").unwrap();
+    let _ = std::fs::remove_dir(".rustlings/backups");
 }
 
 #[test]
@@ -136,13 +341,15 @@ fn reset_no_exercise() {
 
 #[test]
 fn get_hint_for_single_test() {
+    let dir = copy_fixture("failure", "get-hint-for-single-test");
     Command::cargo_bin("rustlings")
         .unwrap()
         .args(["hint", "testFailure"])
-        .current_dir("tests/fixture/failure")
+        .current_dir(&dir)
         .assert()
         .code(0)
         .stdout("Hello!\n");
+    std::fs::remove_dir_all(&dir).unwrap();
 }
 
 #[test]
@@ -172,46 +379,70 @@ fn all_exercises_require_confirmation() {
 
 #[test]
 fn run_compile_exercise_does_not_prompt() {
+    let dir = copy_fixture("state", "run-compile-exercise-does-not-prompt");
     Command::cargo_bin("rustlings")
         .unwrap()
         .args(["run", "pending_exercise"])
-        .current_dir("tests/fixture/state")
+        .current_dir(&dir)
         .assert()
         .code(0)
         .stdout(predicates::str::contains("I AM NOT DONE").not());
+    std::fs::remove_dir_all(&dir).unwrap();
 }
 
 #[test]
 fn run_test_exercise_does_not_prompt() {
+    let dir = copy_fixture("state", "run-test-exercise-does-not-prompt");
     Command::cargo_bin("rustlings")
         .unwrap()
         .args(["run", "pending_test_exercise"])
-        .current_dir("tests/fixture/state")
+        .current_dir(&dir)
         .assert()
         .code(0)
         .stdout(predicates::str::contains("I AM NOT DONE").not());
+    std::fs::remove_dir_all(&dir).unwrap();
 }
 
 #[test]
 fn run_single_test_success_with_output() {
+    let dir = copy_fixture("success", "run-single-test-success-with-output");
     Command::cargo_bin("rustlings")
         .unwrap()
         .args(["--nocapture", "run", "testSuccess"])
-        .current_dir("tests/fixture/success/")
+        .current_dir(&dir)
         .assert()
         .code(0)
         .stdout(predicates::str::contains("THIS TEST TOO SHALL PASS"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn run_single_test_success_with_nocapture() {
+    let dir = copy_fixture("success", "run-single-test-success-with-nocapture");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "testSuccess", "--nocapture"])
+        .current_dir(&dir)
+        .assert()
+        .code(0)
+        .stdout(
+            predicates::str::contains("THIS TEST TOO SHALL PASS")
+                .and(predicates::str::contains("---- passing stdout ----").not()),
+        );
+    std::fs::remove_dir_all(&dir).unwrap();
 }
 
 #[test]
 fn run_single_test_success_without_output() {
+    let dir = copy_fixture("success", "run-single-test-success-without-output");
     Command::cargo_bin("rustlings")
         .unwrap()
         .args(["run", "testSuccess"])
-        .current_dir("tests/fixture/success/")
+        .current_dir(&dir)
         .assert()
         .code(0)
         .stdout(predicates::str::contains("THIS TEST TOO SHALL PASS").not());
+    std::fs::remove_dir_all(&dir).unwrap();
 }
 
 #[test]
@@ -267,3 +498,2071 @@ fn run_rustlings_list_without_done() {
         .success()
         .stdout(predicates::str::contains("Done").not());
 }
+
+#[test]
+fn run_single_compile_missing_target() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "compSuccess", "--target", "not-a-real-target"])
+        .current_dir("tests/fixture/success/")
+        .assert()
+        .code(1);
+}
+
+#[test]
+fn reset_from_solution_refuses_pending_exercise() {
+    let dir = copy_fixture("state", "reset-from-solution-refuses-pending-exercise");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["reset", "--from-solution-if-done", "pending_exercise"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("isn't done yet"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn reset_from_solution_done_exercise() {
+    let dir = copy_fixture("state", "reset-from-solution-done-exercise");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["reset", "--from-solution-if-done", "finished_exercise"])
+        .current_dir(&dir)
+        .assert()
+        .code(0)
+        .stdout(predicates::str::contains("already matches its solution"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn reset_from_solution_replaces_a_done_exercise() {
+    let dir = single_exercise_dir("solution-if-done", "compile", "fn main() { () }\n");
+    std::fs::create_dir_all(dir.join("solutions")).unwrap();
+    std::fs::write(dir.join("solutions/exercise.rs"), "fn main() {}\n").unwrap();
+    let rustlings = |args: &[&str]| {
+        Command::cargo_bin("rustlings")
+            .unwrap()
+            .args(args)
+            .current_dir(&dir)
+            .output()
+            .unwrap()
+    };
+    let reset = rustlings(&["reset", "--from-solution-if-done", "exercise"]);
+    let replaced = std::fs::read_to_string(dir.join("exercises/exercise.rs")).unwrap();
+    rustlings(&["unreset", "exercise"]);
+    let restored = std::fs::read_to_string(dir.join("exercises/exercise.rs")).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(reset.status.success());
+    assert!(String::from_utf8(reset.stdout)
+        .unwrap()
+        .contains("Replaced exercises/exercise.rs with its solution"));
+    assert_eq!(replaced, "fn main() {}\n");
+    assert_eq!(restored, "fn main() { () }\n");
+}
+
+#[test]
+fn reset_from_solution_replaces_a_directory_exercise() {
+    let dir = directory_exercise_dir("solution-if-done-directory");
+    std::fs::create_dir_all(dir.join("solutions")).unwrap();
+    std::fs::rename(
+        dir.join("exercises/multiFile"),
+        dir.join("solutions/multiFile"),
+    )
+    .unwrap();
+    directory_exercise_dir("solution-if-done-directory");
+    let greeting = dir.join("exercises/multiFile/greeting.rs");
+    let attempt = std::fs::read_to_string(&greeting).unwrap() + "// mine\n";
+    std::fs::write(&greeting, &attempt).unwrap();
+    let rustlings = |args: &[&str]| {
+        Command::cargo_bin("rustlings")
+            .unwrap()
+            .args(args)
+            .current_dir(&dir)
+            .output()
+            .unwrap()
+    };
+    let reset = rustlings(&["reset", "--from-solution-if-done", "multiFile"]);
+    let replaced = std::fs::read_to_string(&greeting).unwrap();
+    let solution = std::fs::read_to_string(dir.join("solutions/multiFile/greeting.rs")).unwrap();
+    let again = rustlings(&["reset", "--from-solution-if-done", "multiFile"]);
+    rustlings(&["unreset", "multiFile"]);
+    let restored = std::fs::read_to_string(&greeting).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(reset.status.success());
+    assert_eq!(replaced, solution);
+    assert!(String::from_utf8(again.stdout)
+        .unwrap()
+        .contains("already matches its solution"));
+    assert_eq!(restored, attempt);
+}
+
+#[test]
+fn reset_from_solution_without_a_solution() {
+    let dir = single_exercise_dir("solution-missing", "compile", "fn main() {}\n");
+    let reset = Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["reset", "--from-solution-if-done", "exercise"])
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(reset.status.code(), Some(1));
+    assert!(String::from_utf8(reset.stdout)
+        .unwrap()
+        .contains("No solution found at solutions/exercise.rs"));
+}
+
+#[test]
+fn run_emit_metadata() {
+    let dir = copy_fixture("success", "run-emit-metadata");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "testSuccess", "--emit-metadata"])
+        .current_dir(&dir)
+        .assert()
+        .code(0)
+        .stdout(
+            predicates::str::contains(r#""mode": "test""#)
+                .and(predicates::str::contains("THIS TEST TOO SHALL PASS").not()),
+        );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn verify_parallel_all_success() {
+    let dir = copy_fixture("success", "verify-parallel-all-success");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify", "--jobs", "2"])
+        .current_dir(&dir)
+        .assert()
+        .success();
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn verify_parallel_keeps_the_results_after_a_skipped_exercise() {
+    let dir = copy_fixture("success", "verify-parallel-skipped");
+    let rustlings = |args: &[&str]| {
+        Command::cargo_bin("rustlings")
+            .unwrap()
+            .args(args)
+            .current_dir(&dir)
+            .output()
+            .unwrap()
+    };
+    // An exercise to skip ahead of the others
+    let info = std::fs::read_to_string(dir.join("info.toml")).unwrap();
+    std::fs::write(
+        dir.join("info.toml"),
+        format!("[[exercises]]\nname = \"pending\"\npath = \"pending.rs\"\nmode = \"compile\"\nhint = \"\"\n\n{info}"),
+    )
+    .unwrap();
+    std::fs::write(dir.join("pending.rs"), "// I AM NOT DONE\nfn main() {}\n").unwrap();
+    rustlings(&["skip", "pending"]);
+    let verify = rustlings(&["--nocapture", "verify", "--jobs", "2"]);
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(verify.status.success());
+    // The test exercise after the skipped one passed in the silent parallel
+    // pass, so it isn't checked again one by one, which would show its output
+    assert!(!String::from_utf8(verify.stdout)
+        .unwrap()
+        .contains("THIS TEST TOO SHALL PASS"));
+}
+
+#[test]
+fn verify_parallel_fails_if_some_fails() {
+    let dir = copy_fixture("failure", "verify-parallel-fails-if-some-fails");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify", "--jobs", "2"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("compFailure"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn misspelled_exercise_gets_suggestions() {
+    let dir = copy_fixture("failure", "misspelled-exercise-gets-suggestions");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["hint", "testFailur"])
+        .current_dir(&dir)
+        .assert()
+        .code(0)
+        .stdout("Hello!\n");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["hint", "tsetFailure"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("Did you mean testFailure"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn run_several_exercises() {
+    let success = copy_fixture("success", "run-several-exercises-success");
+    let failure = copy_fixture("failure", "run-several-exercises-failure");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "compSuccess", "test*"])
+        .current_dir(&success)
+        .assert()
+        .code(0)
+        .stdout(predicates::str::contains(
+            "All 2 exercises ran successfully",
+        ));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "compFailure", "testFailure"])
+        .current_dir(&failure)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(
+            "2 of 2 exercises failed: compFailure, testFailure",
+        ));
+    std::fs::remove_dir_all(&success).unwrap();
+    std::fs::remove_dir_all(&failure).unwrap();
+}
+
+#[test]
+fn get_hints_for_several_exercises() {
+    let dir = copy_fixture("failure", "get-hints-for-several-exercises");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["hint", "testFailure", "compFailure"])
+        .current_dir(&dir)
+        .env("NO_COLOR", "1")
+        .assert()
+        .code(0)
+        .stdout(predicates::str::contains("-- testFailure --\nHello!").and(
+            predicates::str::contains("There is no hint for compFailure"),
+        ));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn verify_only_matching_exercises() {
+    let dir = copy_fixture("failure", "verify-only-matching-exercises");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify", "--only", "test*"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(
+            predicates::str::contains("testFailure.rs")
+                .and(predicates::str::contains("compFailure.rs").not()),
+        );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn verify_keep_going_lists_all_failures() {
+    let dir = copy_fixture("failure", "verify-keep-going-lists-all-failures");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify", "--keep-going"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(
+            predicates::str::contains("error: cannot find macro `asset` in this scope")
+                .and(predicates::str::contains("error: expected pattern"))
+                .and(predicates::str::contains("3 of 4 exercises failed")),
+        );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn verify_ci_writes_junit_report() {
+    let dir = copy_fixture("failure", "verify-ci-writes-junit-report");
+    let report = std::env::temp_dir().join(format!("rustlings-junit-{}.xml", std::process::id()));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("verify")
+        .arg("--ci")
+        .arg("--report")
+        .arg(&report)
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(
+            predicates::str::contains("3 of 4 exercises failed")
+                .and(predicates::str::contains("\x1b[").not()),
+        );
+    let xml = std::fs::read_to_string(&report).unwrap();
+    std::fs::remove_file(&report).unwrap();
+    assert!(xml.contains("<testsuite name=\"rustlings\" tests=\"4\" failures=\"3\""));
+    assert!(xml.contains("<failure message=\"error: cannot find macro `asset` in this scope\">"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn verify_ci_checks_skipped_exercises() {
+    let dir = std::env::temp_dir().join(format!("rustlings-ci-skip-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("compFailure.rs"),
+        "// I AM NOT DONE\nfn main() {\n    let x: i32 = \"\";\n}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("info.toml"),
+        "[[exercises]]\nname = \"compFailure\"\npath = \"compFailure.rs\"\nmode = \"compile\"\nhint = \"\"\n",
+    )
+    .unwrap();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["skip", "compFailure"])
+        .current_dir(&dir)
+        .assert()
+        .success();
+    let assert = Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify", "--ci"])
+        .current_dir(&dir)
+        .assert();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert
+        .code(1)
+        .stdout(predicates::str::contains("1 of 1 exercises failed"));
+}
+
+#[test]
+fn verify_ci_rejects_unknown_report_format() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify", "--ci", "--report", "report.pdf"])
+        .current_dir("tests/fixture/success")
+        .assert()
+        .code(3)
+        .stdout(predicates::str::contains(
+            "Don't know how to write report.pdf",
+        ));
+}
+
+#[test]
+fn grade_prints_points_for_classroom() {
+    let config =
+        std::env::temp_dir().join(format!("rustlings-grading-{}.toml", std::process::id()));
+    std::fs::write(&config, "[points]\ncompFailure = 2\ncompLint = 1\n").unwrap();
+    let assert = Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("grade")
+        .arg("--config")
+        .arg(&config)
+        .current_dir("tests/fixture/failure")
+        .assert()
+        .code(1);
+    std::fs::remove_file(&config).unwrap();
+    assert
+        .stdout(
+            predicates::str::contains("\"max_score\": 3")
+                .and(predicates::str::contains("\"status\": \"fail\""))
+                .and(predicates::str::contains("\"name\": \"compLint\"")),
+        )
+        .stderr(predicates::str::contains("Scored 1 of 3 points"));
+}
+
+#[test]
+fn about_shows_chapter_readme() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--plain", "about", "intro"])
+        .assert()
+        .code(0)
+        .stdout(predicates::str::contains("-- Chapter 1: Intro --").and(
+            predicates::str::contains("macros to print text to the console"),
+        ));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["about", "intro"])
+        .current_dir("tests/fixture/success")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("There is no chapter intro"));
+}
+
+#[test]
+fn verify_skips_optional_exercises() {
+    let dir = copy_fixture("optional", "verify-skips-optional-exercises");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("verify")
+        .current_dir(&dir)
+        .assert()
+        .success();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify", "--include-optional"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("bonus.rs"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn verify_reports_the_exercises_it_did_not_check_again() {
+    let dir = single_exercise_dir("report-skipped", "compile", "fn main() {}\n");
+    let verify = |args: &[&str]| {
+        Command::cargo_bin("rustlings")
+            .unwrap()
+            .args(["verify", "--report-skipped"])
+            .args(args)
+            .current_dir(&dir)
+            .output()
+            .unwrap()
+    };
+    let first = String::from_utf8(verify(&[]).stdout).unwrap();
+    let second = String::from_utf8(verify(&[]).stdout).unwrap();
+    let uncached = String::from_utf8(verify(&["--no-cache"]).stdout).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(first.contains("Not checked again:\n  —\nChecked just now:\n  exercise"));
+    assert!(second.contains(
+        "  exercise          passed with timeout=30000ms (passed before and hasn't changed since)"
+    ));
+    assert!(second.ends_with("Checked just now:\n  —\n"));
+    assert!(uncached.contains("Not checked again:\n  —\nChecked just now:\n  exercise"));
+}
+
+#[test]
+fn verify_checks_again_with_other_options() {
+    let dir = single_exercise_dir("cache-options", "compile", "fn main() {}\n");
+    let verify = |args: &[&str]| {
+        let output = Command::cargo_bin("rustlings")
+            .unwrap()
+            .args(args)
+            .args(["verify", "--report-skipped"])
+            .current_dir(&dir)
+            .output()
+            .unwrap();
+        String::from_utf8(output.stdout).unwrap()
+    };
+    verify(&[]);
+    let unchanged = verify(&[]);
+    let verbose = verify(&["--nocapture"]);
+    let longer_timeout = verify(&["--timeout", "60"]);
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(unchanged.ends_with("Checked just now:\n  —\n"));
+    assert!(verbose.contains("Not checked again:\n  —\n"));
+    assert!(longer_timeout.contains("Not checked again:\n  —\n"));
+}
+
+#[test]
+fn verify_from_exercise() {
+    let dir = copy_fixture("failure", "verify-from-exercise");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify", "--from", "compUnformatted"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(
+            predicates::str::contains("compUnformatted.rs")
+                .and(predicates::str::contains("compFailure.rs").not()),
+        );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn get_progressive_hint_for_single_test() {
+    let dir = copy_fixture("success", "get-progressive-hint-for-single-test");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["hint", "testSuccess"])
+        .current_dir(&dir)
+        .assert()
+        .code(0)
+        .stdout(predicates::str::contains("of 2:"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn get_all_hints_after_confirmation() {
+    let dir = copy_fixture("success", "get-all-hints-after-confirmation");
+    assert_cmd::Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["hint", "--all"])
+        .current_dir(&dir)
+        .write_stdin("y\n")
+        .assert()
+        .code(0)
+        .stdout(
+            predicates::str::contains("testSuccess")
+                .and(predicates::str::contains("Look at the test."))
+                .and(predicates::str::contains("The test already passes.")),
+        );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn all_hints_require_confirmation() {
+    assert_cmd::Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["hint", "--all"])
+        .current_dir("tests/fixture/success")
+        .write_stdin("n\n")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("Look at the test.").not());
+}
+
+#[test]
+fn show_exercise_with_line_numbers() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["show", "compSuccess"])
+        .current_dir("tests/fixture/success")
+        .assert()
+        .success()
+        .stdout("1 |  fn main() {\n2 |  }\n");
+}
+
+#[test]
+fn solution_requires_confirmation() {
+    assert_cmd::Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["solution", "finished_exercise"])
+        .current_dir("tests/fixture/state")
+        .write_stdin("n\n")
+        .assert()
+        .code(0)
+        .stdout(predicates::str::contains("fn main").not());
+}
+
+#[test]
+fn solution_diff_against_attempt() {
+    assert_cmd::Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["solution", "--diff", "finished_exercise"])
+        .current_dir("tests/fixture/state")
+        .write_stdin("y\n")
+        .assert()
+        .code(0)
+        .stdout(predicates::str::contains("identical to the solution"));
+}
+
+#[test]
+fn solution_missing() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["solution", "pending_exercise"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .code(1);
+}
+
+#[test]
+fn verify_custom_course() {
+    let dir = copy_fixture("success", "verify-custom-course");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("--course")
+        .arg(dir.file_name().unwrap())
+        .arg("verify")
+        .current_dir(dir.parent().unwrap())
+        .assert()
+        .success();
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn fails_with_missing_course() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--course", "fixture/missing", "verify"])
+        .current_dir("tests/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("Couldn't open the course"));
+}
+
+#[test]
+fn init_from_missing_repository() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["init", "--from", "does/not/exist/course.git"])
+        .current_dir(std::env::temp_dir())
+        .assert()
+        .code(1);
+}
+
+#[test]
+fn run_rustlings_list_summary_only() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--summary-only"])
+        .current_dir("tests/fixture/state")
+        .env("NO_COLOR", "1")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::starts_with(
+                "Progress: [#############---------------------------] 1/3 exercises done",
+            )
+            .and(predicates::str::contains("pending_exercise").not()),
+        );
+}
+
+#[test]
+fn run_rustlings_list_search() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--search", r"it_\w+"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("    pending_test_exercise.rs:4: fn it_works() {}")
+                .and(predicates::str::contains("finished_exercise").not()),
+        );
+}
+
+#[test]
+fn run_rustlings_list_invalid_search() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--search", "("])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("Invalid search pattern"));
+}
+
+#[test]
+fn pick_needs_a_terminal() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("pick")
+        .current_dir("tests/fixture/state")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("needs a terminal"));
+}
+
+#[test]
+fn run_rustlings_list_by_topic() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--topic", "testing"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("pending_test_exercise")
+                .and(predicates::str::contains("finished_exercise").not()),
+        );
+}
+
+#[test]
+fn run_rustlings_list_as_json() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--format", "json", "--solved"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains(r#""name": "finished_exercise""#)
+                .and(predicates::str::contains(r#""status": "Done""#))
+                .and(predicates::str::contains("Progress").not()),
+        );
+}
+
+#[test]
+fn run_rustlings_list_as_csv() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--format", "csv", "--topic", "testing"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .success()
+        .stdout(
+            "name,path,status,topics,has_hint\n\
+             pending_test_exercise,pending_test_exercise.rs,Pending,testing,false\n",
+        );
+}
+
+#[test]
+fn run_exercise_by_topic() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "--topic", "testing", "--emit-metadata"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .code(0)
+        .stdout(predicates::str::contains("pending_test_exercise"));
+}
+
+#[test]
+fn reset_all_requires_confirmation() {
+    assert_cmd::Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["reset", "--all"])
+        .current_dir("tests/fixture/state")
+        .write_stdin("n\n")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("Nothing was reset"));
+}
+
+#[test]
+fn reset_all_requires_confirmation_with_a_single_exercise() {
+    let dir = single_exercise_dir("reset-all", "compile", "fn main() {}\n");
+    let assert = assert_cmd::Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["reset", "--all"])
+        .current_dir(&dir)
+        .write_stdin("n\n")
+        .assert();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert
+        .code(1)
+        .stdout(predicates::str::contains("This will reset 1 exercises"));
+}
+
+#[test]
+fn reset_pattern_without_matches() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["reset", "does_not_exist*"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("No exercise matches"));
+}
+
+#[test]
+fn reset_requires_the_full_name() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["reset", "--force", "finished_exer"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .code(1)
+        .stdout(
+            predicates::str::contains("No exercise is called 'finished_exer'")
+                .and(predicates::str::contains("Did you mean finished_exercise?")),
+        );
+}
+
+#[test]
+fn unreset_without_backup() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["unreset", "finished_exercise"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("no backup"));
+}
+
+#[test]
+fn doctor_finds_no_problems_in_the_course() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("doctor")
+        .assert()
+        .stdout(
+            predicates::str::contains("rustc 1.")
+                .and(predicates::str::contains("exercises are in place"))
+                .and(predicates::str::contains("can watch the exercises")),
+        );
+}
+
+#[test]
+fn doctor_without_exercises_directory() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("doctor")
+        .current_dir("tests/fixture/success")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("no exercises directory"));
+}
+
+#[test]
+fn run_rustlings_stats_json() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["stats", "--json"])
+        .current_dir("tests/fixture/success")
+        .assert()
+        .success()
+        .stdout(predicates::str::starts_with("["));
+}
+
+#[test]
+fn run_single_compile_with_args_and_stdin() {
+    let dir = copy_fixture("success", "run-single-compile-with-args-and-stdin");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args([
+            "run",
+            "compArgs",
+            "--stdin",
+            "input.txt",
+            "--",
+            "hello",
+            "world",
+        ])
+        .current_dir(&dir)
+        .assert()
+        .code(0)
+        .stdout(
+            predicates::str::contains("args: hello world")
+                .and(predicates::str::contains("stdin: some input")),
+        );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn run_single_test_with_a_filter_still_shows_the_output() {
+    let dir = copy_fixture(
+        "success",
+        "run-single-test-with-a-filter-still-shows-the-output",
+    );
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--nocapture", "run", "testSuccess", "--", "passing"])
+        .current_dir(&dir)
+        .assert()
+        .code(0)
+        // The summary of the passing tests that `--show-output` adds
+        .stdout(predicates::str::contains("successes:\n    passing"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn run_single_compile_lint_failure() {
+    let dir = copy_fixture("failure", "run-single-compile-lint-failure");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "compLint"])
+        .current_dir(&dir)
+        .assert()
+        .code(0);
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--clippy", "run", "compLint"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("Clippy found problems"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn run_single_compile_lint_failure_from_user_config() {
+    let dir = copy_fixture(
+        "failure",
+        "run-single-compile-lint-failure-from-user-config",
+    );
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "compLint"])
+        .current_dir(&dir)
+        .env(
+            "XDG_CONFIG_HOME",
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixture/user_config"),
+        )
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("Clippy found problems"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn run_single_compile_with_clippy() {
+    let dir = copy_fixture("success", "run-single-compile-with-clippy");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--clippy", "run", "compSuccess"])
+        .current_dir(&dir)
+        .assert()
+        .code(0);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn run_single_compile_unformatted() {
+    let dir = copy_fixture("failure", "run-single-compile-unformatted");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "compUnformatted"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("isn't formatted"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn run_single_compile_timeout() {
+    let dir = copy_fixture("timeout", "run-single-compile-timeout");
+    assert_cmd::Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--timeout", "1", "run", "compTimeout"])
+        .current_dir(&dir)
+        .timeout(std::time::Duration::from_secs(30))
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("infinite loop"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn dev_check_reports_incomplete_exercises() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["dev", "check"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .code(1)
+        .stdout(
+            predicates::str::contains("pending_exercise: there's no solution")
+                .and(predicates::str::contains("finished_exercise: there's no solution").not()),
+        );
+}
+
+#[test]
+fn dev_new_passes_dev_check() {
+    let dir = single_exercise_dir("dev-new", "compile", "fn main() {}\n");
+    let rustlings = |args: &[&str]| {
+        Command::cargo_bin("rustlings")
+            .unwrap()
+            .args(args)
+            .current_dir(&dir)
+            .output()
+            .unwrap()
+    };
+    let new = rustlings(&["dev", "new", "fresh1"]);
+    let check = String::from_utf8(rustlings(&["dev", "check"]).stdout).unwrap();
+    let hint = String::from_utf8(rustlings(&["hint", "fresh1"]).stdout).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(new.status.success());
+    assert!(!check.contains("fresh1"));
+    assert!(hint.contains("No hint for fresh1 has been written yet."));
+}
+
+#[test]
+fn watch_with_polling_notices_changes() {
+    let dir = single_exercise_dir("watch-poll", "compile", "fn main() {\n");
+    let output = watch_after_change(&dir, &["--poll", "1"], "fn main() {}\n");
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(output.contains("All exercises completed"));
+}
+
+#[test]
+fn watch_single_exercise_success() {
+    let dir = copy_fixture("success", "watch-single-exercise-success");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["watch", "--poll", "--exercise", "compSuccess"])
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("compSuccess is done!")
+                .and(predicates::str::contains("All exercises completed").not()),
+        );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn watch_rerun_checks_exercise_again() {
+    let dir = copy_fixture("failure", "watch-rerun-checks-exercise-again");
+    assert_cmd::Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["watch", "--poll", "--exercise", "compFailure"])
+        .current_dir(&dir)
+        .write_stdin("rerun\nquit\n")
+        .assert()
+        .success()
+        .stdout(predicates::function::function(|out: &str| {
+            out.matches("Compiling of compFailure.rs failed!").count() == 2
+        }));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn watch_rerun_is_no_attempt_for_the_auto_hint() {
+    let dir = copy_fixture("failure", "watch-rerun-is-no-attempt");
+    assert_cmd::Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["watch", "--poll", "--exercise", "compFailure"])
+        .args(["--auto-hint-after", "1"])
+        .current_dir(&dir)
+        .write_stdin("rerun\nquit\n")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("It looks like you're stuck").not());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn watch_auto_hint_after_zero_is_rejected() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["watch", "--auto-hint-after", "0"])
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn watch_hint_works_right_away() {
+    let dir = copy_fixture("failure", "watch-hint-works-right-away");
+    mark_not_done(&dir.join("testFailure.rs"));
+    assert_cmd::Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["watch", "--poll", "--exercise", "testFailure"])
+        .current_dir(&dir)
+        .write_stdin("hint\nquit\n")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Hello!"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn watch_quit_sums_up_session() {
+    let dir = copy_fixture("failure", "watch-quit-sums-up-session");
+    assert_cmd::Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["watch", "--poll", "--exercise", "compFailure"])
+        .current_dir(&dir)
+        .write_stdin("quit\n")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("You didn't solve any exercises this session.").and(
+                predicates::str::contains("Current exercise: compFailure at compFailure.rs"),
+            ),
+        );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn watch_edit_opens_exercise_in_editor() {
+    let dir = copy_fixture("failure", "watch-edit-opens-exercise-in-editor");
+    mark_not_done(&dir.join("compFailure.rs"));
+    assert_cmd::Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["watch", "--poll", "--exercise", "compFailure"])
+        .current_dir(&dir)
+        .env_remove("VISUAL")
+        .env("EDITOR", "echo editing")
+        .write_stdin("edit\nquit\n")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("editing compFailure.rs"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn run_plain_prints_no_escape_codes() {
+    let dir = copy_fixture("failure", "run-plain-prints-no-escape-codes");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--plain", "run", "compFailure"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(
+            predicates::str::contains("expected pattern")
+                .and(predicates::str::contains("\x1b").not()),
+        );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn verify_with_no_color_prints_no_escape_codes() {
+    let dir = copy_fixture("failure", "verify-with-no-color-prints-no-escape-codes");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify"])
+        .current_dir(&dir)
+        .env("NO_COLOR", "1")
+        .assert()
+        .code(1)
+        .stdout(
+            predicates::str::contains("Progress: 0/").and(predicates::str::contains("\x1b").not()),
+        );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn verify_as_json_reports_diagnostics() {
+    let dir = copy_fixture("failure", "verify-as-json-reports-diagnostics");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify", "--format", "json"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(
+            predicates::str::contains(r#"{"event":"started","exercise":"compFailure""#)
+                .and(predicates::str::contains(r#""event":"diagnostic""#))
+                .and(predicates::str::contains(r#""level":"error""#))
+                .and(predicates::str::contains(
+                    r#"{"event":"finished","exercise":"compFailure","status":"failed","stage":"compile"}"#,
+                ))
+                .and(predicates::str::contains("Progress").not()),
+        );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn run_test_exercise_as_json() {
+    let dir = copy_fixture("success", "run-test-exercise-as-json");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "--format", "json", "testSuccess"])
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains(
+                r#"{"event":"test_result","exercise":"testSuccess","name":"passing","passed":true}"#,
+            )
+            .and(predicates::str::contains(
+                r#"{"event":"finished","exercise":"testSuccess","status":"passed"}"#,
+            )),
+        );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+// Frame messages of the Language Server Protocol
+fn lsp_messages(messages: &[&str]) -> String {
+    messages
+        .iter()
+        .map(|message| format!("Content-Length: {}\r\n\r\n{message}", message.len()))
+        .collect()
+}
+
+#[test]
+fn lsp_publishes_diagnostics_and_hints() {
+    let dir = copy_fixture("failure", "lsp");
+    let opened = format!(
+        r#"{{"jsonrpc":"2.0","method":"textDocument/didOpen","params":{{"textDocument":{{"uri":"file://{}"}}}}}}"#,
+        dir.join("compFailure.rs").display()
+    );
+    let hint = format!(
+        r#"{{"jsonrpc":"2.0","id":2,"method":"workspace/executeCommand","params":{{"command":"rustlings.hint","arguments":["file://{}"]}}}}"#,
+        dir.join("testFailure.rs").display()
+    );
+    assert_cmd::Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["lsp", "--stdio"])
+        .current_dir(&dir)
+        .write_stdin(lsp_messages(&[
+            r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{}}"#,
+            &opened,
+            &hint,
+            r#"{"jsonrpc":"2.0","id":3,"method":"shutdown"}"#,
+            r#"{"jsonrpc":"2.0","method":"exit"}"#,
+        ]))
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains(r#""commands":["rustlings.hint"]"#)
+                .and(predicates::str::contains("textDocument/publishDiagnostics"))
+                .and(predicates::str::contains(r#""severity":1"#))
+                .and(predicates::str::contains(r#""hint":"Hello!""#))
+                .and(predicates::str::contains(
+                    r#""id":3,"jsonrpc":"2.0","result":null"#,
+                )),
+        );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn lsp_publishes_diagnostics_for_a_file_of_a_directory_exercise() {
+    let dir = directory_exercise_dir("lsp-directory");
+    let greeting = dir.join("exercises/multiFile/greeting.rs");
+    std::fs::write(&greeting, "pub fn hello() -> &'static str {\n    42\n}\n").unwrap();
+    let opened = format!(
+        r#"{{"jsonrpc":"2.0","method":"textDocument/didOpen","params":{{"textDocument":{{"uri":"file://{}"}}}}}}"#,
+        greeting.display()
+    );
+    let output = assert_cmd::Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["lsp", "--stdio"])
+        .current_dir(&dir)
+        .write_stdin(lsp_messages(&[
+            r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{}}"#,
+            &opened,
+            r#"{"jsonrpc":"2.0","method":"exit"}"#,
+        ]))
+        .output()
+        .unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("textDocument/publishDiagnostics"));
+    assert!(stdout.contains("mismatched types"));
+}
+
+#[test]
+fn watch_in_another_language() {
+    let dir = copy_fixture("failure", "watch-in-another-language");
+    assert_cmd::Command::cargo_bin("rustlings")
+        .unwrap()
+        .args([
+            "--lang",
+            "de",
+            "watch",
+            "--poll",
+            "--exercise",
+            "compFailure",
+        ])
+        .current_dir(&dir)
+        .write_stdin("quit\n")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("Bearbeiten: compFailure.rs")
+                .and(predicates::str::contains("Tschüss!")),
+        );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn list_exercises_by_attempts() {
+    let dir = copy_fixture("state", "attempts");
+    let exercise = dir.join("pending_test_exercise.rs");
+    for attempt in 0..3 {
+        // The second run checks the same contents again, which isn't another attempt
+        if attempt != 1 {
+            let mut source = std::fs::read_to_string(&exercise).unwrap();
+            source.push_str(&format!("// attempt {attempt}\n"));
+            std::fs::write(&exercise, source).unwrap();
+        }
+        Command::cargo_bin("rustlings")
+            .unwrap()
+            .args(["run", "pending_test_exercise"])
+            .current_dir(&dir)
+            .output()
+            .unwrap();
+    }
+    let rustlings = |args: &[&str]| {
+        let output = Command::cargo_bin("rustlings")
+            .unwrap()
+            .args(args)
+            .current_dir(&dir)
+            .output()
+            .unwrap();
+        String::from_utf8(output.stdout).unwrap()
+    };
+    let names = rustlings(&["list", "--names", "--sort", "attempts"]);
+    let json = rustlings(&["list", "--format", "json"]);
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(names.starts_with("pending_test_exercise\n"));
+    assert!(json.contains(r#""attempts": 2"#));
+}
+
+#[test]
+fn list_estimates_time_at_the_pace_of_the_learner() {
+    let dir = copy_fixture("state", "estimate-time");
+    let list = || {
+        let output = Command::cargo_bin("rustlings")
+            .unwrap()
+            .args(["list", "--estimate-time"])
+            .current_dir(&dir)
+            .output()
+            .unwrap();
+        String::from_utf8(output.stdout).unwrap()
+    };
+    let without_data = list();
+    std::fs::write(
+        dir.join(".rustlings-state.json"),
+        r#"{"exercises": {"finished_exercise": {"hash": 0, "solved": true, "first_attempt": 1000, "solved_at": 1120}}}"#,
+    )
+    .unwrap();
+    let with_data = list();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(without_data.contains("Left at your pace: —"));
+    assert!(with_data.contains("2m 0s"));
+    assert!(with_data.contains("Left at your pace: 4m 0s"));
+}
+
+#[test]
+fn report_progress_as_markdown() {
+    let dir = copy_fixture("state", "report");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["report", "--output", "progress.md"])
+        .current_dir(&dir)
+        .assert()
+        .success();
+    let report = std::fs::read_to_string(dir.join("progress.md")).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(report.contains("Completed **1 / 3 exercises** (33.3 %)."));
+    assert!(report.contains("| finished_exercise |  | Done |"));
+}
+
+#[test]
+fn progress_moves_to_another_copy() {
+    let from = copy_fixture("state", "export");
+    let to = copy_fixture("state", "import");
+    let export = from.join("progress.json");
+    std::fs::write(
+        from.join("pending_exercise.rs"),
+        "// solved elsewhere\nfn main() {}\n",
+    )
+    .unwrap();
+    let rustlings = |dir: &Path, args: &[&str]| {
+        Command::cargo_bin("rustlings")
+            .unwrap()
+            .args(args)
+            .current_dir(dir)
+            .assert()
+            .success();
+    };
+    rustlings(&from, &["skip", "pending_test_exercise"]);
+    rustlings(&from, &["run", "pending_exercise"]);
+    rustlings(&from, &["note", "pending_exercise", "main can be empty"]);
+    rustlings(&from, &["progress", "export", export.to_str().unwrap()]);
+    rustlings(&to, &["progress", "import", export.to_str().unwrap()]);
+
+    let source = std::fs::read_to_string(to.join("pending_exercise.rs")).unwrap();
+    let notes = std::fs::read_to_string(to.join(".rustlings/notes/pending_exercise.md")).unwrap();
+    let list = Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--format", "csv"])
+        .current_dir(&to)
+        .output()
+        .unwrap();
+    std::fs::remove_dir_all(&from).unwrap();
+    std::fs::remove_dir_all(&to).unwrap();
+    assert_eq!(source, "// solved elsewhere\nfn main() {}\n");
+    assert!(notes.contains("main can be empty"));
+    let list = String::from_utf8(list.stdout).unwrap();
+    assert!(list.contains("pending_exercise,pending_exercise.rs,Done"));
+    assert!(list.contains("pending_test_exercise,pending_test_exercise.rs,Skipped"));
+}
+
+#[test]
+fn sync_progress_through_a_file() {
+    let from = copy_fixture("state", "sync-from");
+    let to = copy_fixture("state", "sync-to");
+    let remote = format!("file://{}", from.join("remote.json").display());
+    let sync = |dir: &Path| {
+        Command::cargo_bin("rustlings")
+            .unwrap()
+            .args(["sync", "--remote", &remote])
+            .current_dir(dir)
+            .assert()
+    };
+    let rustlings = |dir: &Path, args: &[&str]| {
+        Command::cargo_bin("rustlings")
+            .unwrap()
+            .args(args)
+            .current_dir(dir)
+            .assert()
+            .success();
+    };
+    rustlings(&from, &["skip", "pending_test_exercise"]);
+    let pushed = sync(&from).success().get_output().stdout.clone();
+    let pulled = sync(&to).success().get_output().stdout.clone();
+    let list = Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--format", "csv"])
+        .current_dir(&to)
+        .output()
+        .unwrap();
+    // Both copies made progress since, so neither may overwrite the other
+    rustlings(&from, &["run", "pending_exercise"]);
+    sync(&from).success();
+    rustlings(&to, &["skip", "pending_exercise"]);
+    let conflict = sync(&to).code(1).get_output().stdout.clone();
+    std::fs::remove_dir_all(&from).unwrap();
+    std::fs::remove_dir_all(&to).unwrap();
+
+    assert!(String::from_utf8(pushed)
+        .unwrap()
+        .contains("Pushed the progress"));
+    assert!(String::from_utf8(pulled)
+        .unwrap()
+        .contains("Pulled the progress"));
+    let list = String::from_utf8(list.stdout).unwrap();
+    assert!(list.contains("pending_test_exercise,pending_test_exercise.rs,Skipped"));
+    assert!(String::from_utf8(conflict)
+        .unwrap()
+        .contains("both changed"));
+}
+
+#[test]
+fn solved_exercises_are_committed_to_git() {
+    let dir = copy_fixture("state", "autocommit");
+    commit_all(&dir);
+    std::fs::write(dir.join("pending_exercise.rs"), "fn main() {}\n").unwrap();
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--git-autocommit", "verify"])
+        .current_dir(&dir)
+        .envs(GIT_AUTHOR)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(
+            "Committed pending_exercise.rs to git",
+        ));
+
+    let log = std::process::Command::new("git")
+        .args(["log", "--format=%s"])
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(
+        String::from_utf8(log.stdout).unwrap(),
+        "solve pending_exercise\nstart\n"
+    );
+}
+
+#[test]
+fn classroom_report_of_students() {
+    let dir = std::env::temp_dir().join(format!("rustlings-classroom-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let export = |solved: &[&str]| {
+        let exercises: Vec<String> = solved
+            .iter()
+            .map(|name| format!(r#""{name}": {{ "hash": 0, "solved": true }}"#))
+            .collect();
+        format!(
+            r#"{{ "version": 1, "state": {{ "exercises": {{ {} }} }}, "sources": {{}} }}"#,
+            exercises.join(", ")
+        )
+    };
+    std::fs::write(
+        dir.join("alex.json"),
+        export(&["pending_exercise", "finished_exercise"]),
+    )
+    .unwrap();
+    std::fs::write(dir.join("sam.json"), export(&[])).unwrap();
+
+    let output = Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["classroom", "report", "--csv", "--inputs"])
+        .arg(&dir)
+        .current_dir("tests/fixture/state")
+        .output()
+        .unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "student,total (3)\nalex,2\nsam,0\n"
+    );
+}
+
+#[test]
+fn skip_exercise_moves_past_it() {
+    let dir = copy_fixture("state", "skip");
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["skip", "pending_exercise"])
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Skipped pending_exercise.rs"));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--format", "csv"])
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "pending_exercise,pending_exercise.rs,Skipped",
+        ));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "next", "--emit-metadata"])
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            r#""name": "pending_test_exercise""#,
+        ));
+
+    // Once the skipped exercise is edited, it's pending again
+    let path = dir.join("pending_exercise.rs");
+    let source = std::fs::read_to_string(&path).unwrap();
+    std::fs::write(&path, source + "// Edited\n").unwrap();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--format", "csv"])
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "pending_exercise,pending_exercise.rs,Pending",
+        ));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn notes_are_kept_per_exercise() {
+    let dir = copy_fixture("state", "notes");
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["note", "pending_exercise", "Remember", "the", "semicolon"])
+        .current_dir(&dir)
+        .assert()
+        .success();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["note", "--show", "pending_exercise"])
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Remember the semicolon"));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--with-notes"])
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("    Remember the semicolon")
+                .and(predicates::str::contains("finished_exercise").not()),
+        );
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["note", "--show", "finished_exercise"])
+        .current_dir(&dir)
+        .assert()
+        .code(1);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn notes_left_as_created_are_no_notes() {
+    let dir = copy_fixture("state", "empty-notes");
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["note", "pending_exercise"])
+        .env("VISUAL", "true")
+        .current_dir(&dir)
+        .assert()
+        .success();
+    let show = Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["note", "--show", "pending_exercise"])
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    let created = dir.join(".rustlings/notes/pending_exercise.md").exists();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(created);
+    assert_eq!(show.status.code(), Some(1));
+}
+
+#[test]
+fn exercises_come_after_their_prerequisites() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--names"])
+        .current_dir("tests/fixture/prerequisites")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("first\nsecond\nthird\n"));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--names", "--prereqs", "second"])
+        .current_dir("tests/fixture/prerequisites")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("first\nsecond\n")
+                .and(predicates::str::contains("third").not()),
+        );
+}
+
+#[test]
+fn list_exercises_of_a_difficulty() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--names", "--difficulty", "hard"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("pending_test_exercise\n")
+                .and(predicates::str::contains("finished_exercise").not()),
+        );
+}
+
+#[test]
+fn plan_proposes_exercises_fitting_the_time() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["plan", "--minutes", "45"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("10m  pending_exercise.rs")
+                .and(predicates::str::contains("20m  pending_test_exercise.rs"))
+                .and(predicates::str::contains("That's about 30m.")),
+        );
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["plan", "--minutes", "5"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(
+            "likely takes 10m, more than you have",
+        ));
+}
+
+#[test]
+fn info_shows_everything_about_an_exercise() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["info", "finished_exercise"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("Mode        compile\n")
+                .and(predicates::str::contains("Difficulty  easy\n"))
+                .and(predicates::str::contains(
+                    "Solution    solutions/finished_exercise.rs\n",
+                )),
+        );
+}
+
+#[test]
+fn init_replaces_a_broken_course_with_force() {
+    let course = copy_fixture("state", "init-course");
+    commit_all(&course);
+    let target = course.with_extension("copy");
+    std::fs::create_dir_all(&target).unwrap();
+    std::fs::write(target.join("info.toml"), "broken").unwrap();
+
+    let init = |force: bool| {
+        let mut command = Command::cargo_bin("rustlings").unwrap();
+        command
+            .args(["init", "--non-interactive", "--from"])
+            .arg(format!("file://{}", course.display()))
+            .arg("--path")
+            .arg(&target);
+        if force {
+            command.arg("--force");
+        }
+        command.assert()
+    };
+    init(false)
+        .code(1)
+        .stdout(predicates::str::contains("already exists"));
+    init(true).success();
+    let info = std::fs::read_to_string(target.join("info.toml")).unwrap();
+    std::fs::remove_dir_all(&course).unwrap();
+    std::fs::remove_dir_all(&target).unwrap();
+    assert!(info.contains("pending_exercise"));
+}
+
+#[test]
+fn init_warms_up_the_builds() {
+    let course = copy_fixture("state", "warmup-course");
+    commit_all(&course);
+    let target = course.with_extension("copy");
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["init", "--non-interactive", "--warmup", "--from"])
+        .arg(format!("file://{}", course.display()))
+        .arg("--path")
+        .arg(&target)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Warmed up"));
+    let warmed_up = target.join(".rustlings/prime/main").exists();
+    std::fs::remove_dir_all(&course).unwrap();
+    std::fs::remove_dir_all(&target).unwrap();
+    assert!(warmed_up);
+}
+
+#[test]
+fn repair_restores_missing_exercises() {
+    let dir = copy_fixture("state", "repair");
+    commit_all(&dir);
+    std::fs::write(dir.join("finished_exercise.rs"), "fn main() {}\n").unwrap();
+    std::fs::remove_file(dir.join("pending_exercise.rs")).unwrap();
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("verify")
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(
+            "1 exercises are missing their file",
+        ));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("repair")
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Restored pending_exercise.rs"));
+
+    let restored = std::fs::read_to_string(dir.join("pending_exercise.rs")).unwrap();
+    let kept = std::fs::read_to_string(dir.join("finished_exercise.rs")).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(restored.contains("I AM NOT DONE"));
+    assert_eq!(kept, "fn main() {}\n");
+}
+
+// An exercise of the fixtures, as tools using the library would make it
+fn library_exercise(name: &str, mode: rustlings::Mode) -> rustlings::Exercise {
+    rustlings::Exercise {
+        name: format!("library_{name}"),
+        path: PathBuf::from(format!("tests/fixture/{name}.rs")),
+        mode,
+        hint: String::new(),
+        hints: Vec::new(),
+        topics: Vec::new(),
+        check_fmt: false,
+        requires: Vec::new(),
+        difficulty: None,
+        estimated_minutes: None,
+        optional: false,
+        dependencies: Vec::new(),
+    }
+}
+
+fn library_options() -> rustlings::VerifyOptions {
+    rustlings::VerifyOptions {
+        verbose: false,
+        success_hints: false,
+        clippy: false,
+        fmt: false,
+        timeout: std::time::Duration::from_secs(30),
+        git_autocommit: false,
+        no_cache: false,
+    }
+}
+
+#[test]
+fn library_compiles_and_runs_exercises() {
+    let exercise = |name: &str| library_exercise(name, rustlings::Mode::Compile);
+    let output = exercise("success/compSuccess")
+        .compile()
+        .unwrap()
+        .run(std::time::Duration::from_secs(30))
+        .unwrap();
+    assert!(output.stderr.is_empty());
+    let error = exercise("failure/compFailure").compile().err().unwrap();
+    assert!(error.stderr.contains("expected pattern"));
+}
+
+#[test]
+fn library_returns_failures_as_errors() {
+    let options = library_options();
+    let passing = library_exercise("success/testSuccess", rustlings::Mode::Test);
+    assert!(rustlings::verify(&passing, &options).is_ok());
+    let failing = library_exercise("failure/testFailure", rustlings::Mode::Test);
+    let Err(rustlings::Error::Exercise { name, .. }) = rustlings::verify(&failing, &options) else {
+        panic!("the failing exercise passed");
+    };
+    assert_eq!(name, "library_failure/testFailure");
+
+    // A missing stdin file fails the run instead of ending the process
+    let input = rustlings::RunInput {
+        stdin: Some(Path::new("tests/fixture/success/missing.txt")),
+        ..Default::default()
+    };
+    let compiling = library_exercise("success/compSuccess", rustlings::Mode::Compile);
+    let error = rustlings::run(&compiling, &options, &input).unwrap_err();
+    assert!(error.to_string().contains("Failed to open"));
+}
+
+#[cfg(unix)]
+#[test]
+fn run_hook_when_exercise_fails() {
+    let dir = std::env::temp_dir().join(format!("rustlings-hooks-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::copy(
+        "tests/fixture/failure/compFailure.rs",
+        dir.join("compFailure.rs"),
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("info.toml"),
+        "[[exercises]]\nname = \"compFailure\"\npath = \"compFailure.rs\"\nmode = \"compile\"\nhint = \"\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("rustlings.toml"),
+        "[hooks]\nfailed = \"echo $RUSTLINGS_EVENT $RUSTLINGS_EXERCISE > failed.txt\"\n",
+    )
+    .unwrap();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("verify")
+        .current_dir(&dir)
+        .assert()
+        .code(1);
+    // The hook runs in the background, so it may finish after rustlings
+    let mut failed = String::new();
+    for _ in 0..50 {
+        failed = std::fs::read_to_string(dir.join("failed.txt")).unwrap_or_default();
+        if !failed.is_empty() {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(failed, "failed compFailure\n");
+}
+
+#[cfg(unix)]
+#[test]
+fn run_hook_when_exercise_is_solved_with_json_output() {
+    let dir = single_exercise_dir("json-hooks", "compile", "fn main() {}\n");
+    std::fs::write(
+        dir.join("rustlings.toml"),
+        "[hooks]\nsolved = \"echo $RUSTLINGS_EVENT $RUSTLINGS_EXERCISE > solved.txt\"\n",
+    )
+    .unwrap();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "--format", "json", "exercise"])
+        .current_dir(&dir)
+        .assert()
+        .success();
+    let solved = wait_for_file(&dir.join("solved.txt"));
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(solved, "solved exercise\n");
+}
+
+#[cfg(unix)]
+#[test]
+fn run_hook_when_exercise_is_solved_with_run() {
+    let dir = single_exercise_dir("run-hooks", "compile", "fn main() {}\n");
+    std::fs::write(
+        dir.join("rustlings.toml"),
+        "[hooks]\nsolved = \"echo $RUSTLINGS_EVENT $RUSTLINGS_EXERCISE > solved.txt\"\n",
+    )
+    .unwrap();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "exercise"])
+        .current_dir(&dir)
+        .assert()
+        .success();
+    let solved = wait_for_file(&dir.join("solved.txt"));
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(solved, "solved exercise\n");
+}
+
+#[test]
+fn watch_rebuilds_when_the_marker_is_removed() {
+    let dir = single_exercise_dir(
+        "rebuild-unsolved",
+        "compile",
+        "// I AM NOT DONE\nfn main() {}\n",
+    );
+    let output = watch_after_change(&dir, &["--poll", "--rebuild", "unsolved"], "fn main() {}\n");
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(output.contains("All exercises completed"));
+}
+
+#[test]
+fn repair_restores_exercises_made_of_several_files() {
+    let dir = directory_exercise_dir("repair-directory");
+    commit_all(&dir);
+    std::fs::remove_dir_all(dir.join("exercises/multiFile")).unwrap();
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("repair")
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Restored exercises/multiFile"));
+
+    let greeting = std::fs::read_to_string(dir.join("exercises/multiFile/greeting.rs")).unwrap();
+    let ran = Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "multiFile"])
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(greeting.contains("Hello from another file!"));
+    assert!(String::from_utf8(ran.stdout)
+        .unwrap()
+        .contains("Hello from another file!"));
+}
+
+#[test]
+fn watch_restores_a_deleted_file_of_a_directory_exercise() {
+    let dir = directory_exercise_dir("watch-restore-directory");
+    let main = dir.join("exercises/multiFile/main.rs");
+    let source = std::fs::read_to_string(&main).unwrap();
+    std::fs::write(&main, format!("// I AM NOT DONE\n{source}")).unwrap();
+    commit_all(&dir);
+    let mut watch = std::process::Command::new(assert_cmd::cargo::cargo_bin("rustlings"))
+        .args(["watch", "--poll"])
+        .current_dir(&dir)
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdin = watch.stdin.take().unwrap();
+    std::thread::sleep(std::time::Duration::from_secs(3));
+    std::fs::remove_file(&main).unwrap();
+    std::thread::sleep(std::time::Duration::from_secs(3));
+    std::io::Write::write_all(&mut stdin, b"restore\nquit\n").unwrap();
+    drop(stdin);
+    let output = watch.wait_with_output().unwrap();
+    let restored = std::fs::read_to_string(&main).unwrap_or_default();
+    std::fs::remove_dir_all(&dir).unwrap();
+    let output = String::from_utf8(output.stdout).unwrap();
+    assert!(output.contains("was deleted: exercises/multiFile/main.rs"));
+    assert!(restored.starts_with("// I AM NOT DONE"));
+}
+
+#[test]
+fn review_copies_every_file_of_directory_exercises() {
+    let dir = directory_exercise_dir("review-directory");
+    let main = dir.join("exercises/multiFile/main.rs");
+    let source = std::fs::read_to_string(&main).unwrap();
+    std::fs::write(&main, format!("// I AM NOT DONE\n{source}")).unwrap();
+    commit_all(&dir);
+    std::fs::write(&main, &source).unwrap();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("verify")
+        .current_dir(&dir)
+        .assert()
+        .success();
+
+    let review = assert_cmd::Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["review", "--count", "1"])
+        .current_dir(&dir)
+        .write_stdin("quit\n")
+        .output()
+        .unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    let output = String::from_utf8(review.stdout).unwrap();
+    assert!(output.contains("Successfully ran exercises/multiFile"));
+    assert!(output.contains("I AM NOT DONE"));
+}
+
+#[test]
+fn progress_export_includes_every_file_of_directory_exercises() {
+    let from = directory_exercise_dir("export-directory");
+    let to = directory_exercise_dir("import-directory");
+    let export = from.join("progress.json");
+    let greeting = "pub fn hello() -> &'static str {\n    \"Changed\"\n}\n";
+    std::fs::write(from.join("exercises/multiFile/greeting.rs"), greeting).unwrap();
+    let rustlings = |dir: &Path, args: &[&str]| {
+        Command::cargo_bin("rustlings")
+            .unwrap()
+            .args(args)
+            .current_dir(dir)
+            .assert()
+            .success();
+    };
+    rustlings(&from, &["run", "multiFile"]);
+    rustlings(&from, &["progress", "export", export.to_str().unwrap()]);
+    rustlings(&to, &["progress", "import", export.to_str().unwrap()]);
+
+    let imported = std::fs::read_to_string(to.join("exercises/multiFile/greeting.rs")).unwrap();
+    let main = std::fs::read_to_string(to.join("exercises/multiFile/main.rs")).unwrap();
+    std::fs::remove_dir_all(&from).unwrap();
+    std::fs::remove_dir_all(&to).unwrap();
+    assert_eq!(imported, greeting);
+    assert!(main.contains("mod greeting;"));
+}
+
+#[test]
+fn reset_shows_the_changes_to_every_file_of_directory_exercises() {
+    let dir = directory_exercise_dir("reset-directory");
+    commit_all(&dir);
+    let greeting = "pub fn hello() -> &'static str {\n    \"Changed\"\n}\n";
+    std::fs::write(dir.join("exercises/multiFile/greeting.rs"), greeting).unwrap();
+    let output = assert_cmd::Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["reset", "multiFile"])
+        .current_dir(&dir)
+        .write_stdin("n\n")
+        .output()
+        .unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout.contains("greeting.rs (yours)"));
+    assert!(stdout.contains("Changed"));
+    assert!(stdout.contains("Nothing was reset"));
+}
+
+#[test]
+fn verify_checks_every_file_of_directory_exercises() {
+    let dir = directory_exercise_dir("verify-directory");
+    let verify = || {
+        Command::cargo_bin("rustlings")
+            .unwrap()
+            .arg("verify")
+            .current_dir(&dir)
+            .output()
+            .unwrap()
+    };
+    assert!(verify().status.success());
+
+    let greeting = "pub fn hello() -> &'static str {\n    42\n}\n";
+    std::fs::write(dir.join("exercises/multiFile/greeting.rs"), greeting).unwrap();
+    let output = verify();
+    std::fs::remove_dir_all(&dir).unwrap();
+    let printed = [output.stdout, output.stderr].concat();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&printed).contains("greeting.rs"));
+}
+
+#[test]
+fn next_exercise_and_plan_leave_out_optional_exercises() {
+    let dir = single_exercise_dir(
+        "next-optional",
+        "compile",
+        "// I AM NOT DONE\nfn main() {}\n",
+    );
+    std::fs::write(
+        dir.join("exercises/bonus.rs"),
+        "// I AM NOT DONE\nfn main() {}\n",
+    )
+    .unwrap();
+    let info = std::fs::read_to_string(dir.join("info.toml")).unwrap();
+    let bonus = "[[exercises]]\nname = \"bonus\"\npath = \"exercises/bonus.rs\"\nmode = \"compile\"\noptional = true\nhint = \"\"\n\n";
+    std::fs::write(dir.join("info.toml"), format!("{bonus}{info}")).unwrap();
+    let rustlings = |args: &[&str]| {
+        let output = Command::cargo_bin("rustlings")
+            .unwrap()
+            .args(args)
+            .current_dir(&dir)
+            .output()
+            .unwrap();
+        String::from_utf8(output.stdout).unwrap()
+    };
+    let next = rustlings(&["run", "next", "--emit-metadata"]);
+    let plan = rustlings(&["plan", "--minutes", "60"]);
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(next.contains(r#""name": "exercise""#));
+    assert!(plan.contains("exercises/exercise.rs"));
+    assert!(!plan.contains("bonus"));
+}